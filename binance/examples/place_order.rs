@@ -0,0 +1,59 @@
+//! Place orders on the Binance testnet
+//!
+//! ```sh
+//! BINANCE_API_KEY=.. BINANCE_SECRET_KEY=.. ALLOW_WRITES=1 cargo run --example place_order
+//! ```
+//!
+//! Without `ALLOW_WRITES=1` the orders are only printed.
+
+use std::env;
+
+use binance_api::auth::BinanceAuth;
+use binance_api::builder::{BinanceEndpoint, BinanceEndpointType};
+use binance_api::client::BinanceClient;
+use binance_api::prelude::{Price, Quantity};
+use binance_api::request::{OrderAmount, OrderRequest};
+use binance_api::response::OrderSide;
+
+#[tokio::main]
+async fn main() {
+    // Testnet keys are issued separately: https://testnet.binance.vision
+    let auth = BinanceAuth::ApiKeys {
+        api_key: env::var("BINANCE_API_KEY").expect("BINANCE_API_KEY not set"),
+        secret_key: env::var("BINANCE_SECRET_KEY").expect("BINANCE_SECRET_KEY not set"),
+    };
+
+    let client = BinanceClient::builder()
+        .auth(auth)
+        .endpoint(BinanceEndpoint::from_type(BinanceEndpointType::Testnet))
+        .build()
+        .unwrap();
+
+    // Buy 10 USDT of BTC
+    let market = OrderRequest::market(
+        "BTCUSDT",
+        OrderSide::Buy,
+        OrderAmount::Quote(Quantity(10.0)),
+    );
+
+    // Sell 0.001 BTC at 1,000,000 USDT (won't be filled)
+    let limit = OrderRequest::limit(
+        "BTCUSDT",
+        OrderSide::Sell,
+        Quantity(0.001),
+        Price(1_000_000.0),
+    );
+
+    if env::var("ALLOW_WRITES").as_deref() != Ok("1") {
+        println!("Dry run, set ALLOW_WRITES=1 to place the orders:");
+        println!("{market:#?}");
+        println!("{limit:#?}");
+        return;
+    }
+
+    let order = client.place_order(market).await.unwrap();
+    println!("Market order: {order:#?}");
+
+    let order = client.place_order(limit).await.unwrap();
+    println!("Limit order: {order:#?}");
+}
@@ -17,22 +17,41 @@ impl BinanceApi {
 }
 
 pub(super) enum Spot {
-    // Ping,
-    // Time,
+    /// Test connectivity
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/general-endpoints#test-connectivity>
+    Ping,
+    /// Check server time
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/general-endpoints#check-server-time>
+    Time,
     ExchangeInfo,
     // Depth,
     // Trades,
-    // HistoricalTrades,
+    /// Old trade lookup
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/market-data-endpoints#old-trade-lookup>
+    HistoricalTrades,
     // AggTrades,
     // Klines,
     // AvgPrice,
     // Ticker24hr,
     // Price,
     // BookTicker,
-    // Order,
+    /// New order
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#new-order-trade>
+    Order,
+    /// Cancel an existing order and send a new order
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#cancel-an-existing-order-and-send-a-new-order-trade>
+    OrderCancelReplace,
     // OrderTest,
     // OpenOrders,
-    // AllOrders,
+    /// All orders
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/account-endpoints#all-orders-user_data>
+    AllOrders,
     // Oco,
     // OrderList,
     // AllOrderList,
@@ -47,32 +66,52 @@ pub(super) enum Spot {
     /// <https://developers.binance.com/docs/wallet/capital/deposite-history>
     DepositHistory,
     MyTrades,
+    /// Query margin account's trade list
+    ///
+    /// <https://developers.binance.com/docs/margin_trading/trade/Query-Margin-Account-Trade-List>
+    MarginMyTrades,
     /// Withdrawal history
     ///
     /// <https://developers.binance.com/docs/wallet/capital/withdraw-history>
     WithdrawalHistory,
-    // UserDataStream,
+    /// Withdraw
+    ///
+    /// <https://developers.binance.com/docs/wallet/capital/withdraw>
+    Withdraw,
+    /// Dust transfer
+    ///
+    /// <https://developers.binance.com/docs/wallet/asset/dust-transfer>
+    DustTransfer,
+    /// Daily account snapshot
+    ///
+    /// <https://developers.binance.com/docs/wallet/account/daily-account-snapshoot>
+    AccountSnapshot,
+    /// User data stream listen key
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/user-data-stream-endpoints-deprecated>
+    UserDataStream,
 }
 
 impl Spot {
     pub(super) fn http_path(&self) -> &str {
         match self {
-            // Self::Ping => "/api/v3/ping",
-            // Self::Time => "/api/v3/time",
+            Self::Ping => "/api/v3/ping",
+            Self::Time => "/api/v3/time",
             Self::ExchangeInfo => "/api/v3/exchangeInfo",
             // Self::Depth => "/api/v3/depth",
             // Self::Trades => "/api/v3/trades",
-            // Self::HistoricalTrades => "/api/v3/historicalTrades",
+            Self::HistoricalTrades => "/api/v3/historicalTrades",
             // Self::AggTrades => "/api/v3/aggTrades",
             // Self::Klines => "/api/v3/klines",
             // Self::AvgPrice => "/api/v3/avgPrice",
             // Self::Ticker24hr => "/api/v3/ticker/24hr",
             // Self::Price => "/api/v3/ticker/price",
             // Self::BookTicker => "/api/v3/ticker/bookTicker",
-            // Self::Order => "/api/v3/order",
+            Self::Order => "/api/v3/order",
+            Self::OrderCancelReplace => "/api/v3/order/cancelReplace",
             // Self::OrderTest => "/api/v3/order/test",
             // Self::OpenOrders => "/api/v3/openOrders",
-            // Self::AllOrders => "/api/v3/allOrders",
+            Self::AllOrders => "/api/v3/allOrders",
             // Self::Oco => "/api/v3/order/oco",
             // Self::OrderList => "/api/v3/orderList",
             // Self::AllOrderList => "/api/v3/allOrderList",
@@ -81,8 +120,12 @@ impl Spot {
             Self::DepositAddress => "/sapi/v1/capital/deposit/address",
             Self::DepositHistory => "/sapi/v1/capital/deposit/hisrec",
             Self::MyTrades => "/api/v3/myTrades",
+            Self::MarginMyTrades => "/sapi/v1/margin/myTrades",
             Self::WithdrawalHistory => "/sapi/v1/capital/withdraw/history",
-            // Self::UserDataStream => "/api/v3/userDataStream",
+            Self::Withdraw => "/sapi/v1/capital/withdraw/apply",
+            Self::DustTransfer => "/sapi/v1/asset/dust",
+            Self::AccountSnapshot => "/sapi/v1/accountSnapshot",
+            Self::UserDataStream => "/api/v3/userDataStream",
         }
     }
 
@@ -92,8 +135,14 @@ impl Spot {
             | Self::Account
             | Self::DepositHistory
             | Self::MyTrades
+            | Self::AllOrders
             | Self::WithdrawalHistory => 20,
-            Self::DepositAddress => 10,
+            Self::HistoricalTrades => 25,
+            Self::Ping | Self::Time | Self::Order | Self::OrderCancelReplace => 1,
+            Self::UserDataStream => 2,
+            Self::DepositAddress | Self::DustTransfer | Self::MarginMyTrades => 10,
+            Self::Withdraw => 900,
+            Self::AccountSnapshot => 2400,
         }
     }
 }
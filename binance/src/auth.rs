@@ -2,6 +2,8 @@
 
 use std::fmt;
 
+use common::config::ApiKeys;
+
 use crate::error::Error;
 
 /// Binance authentication
@@ -25,6 +27,15 @@ impl fmt::Debug for BinanceAuth {
     }
 }
 
+impl From<ApiKeys> for BinanceAuth {
+    fn from(keys: ApiKeys) -> Self {
+        Self::ApiKeys {
+            api_key: keys.api_key,
+            secret_key: keys.api_secret,
+        }
+    }
+}
+
 impl BinanceAuth {
     /// Get API Key
     pub(super) fn get_api_key(&self) -> Result<&str, Error> {
@@ -0,0 +1,412 @@
+//! Blocking Binance client
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use common::blocking::Runtime;
+
+use crate::client::BinanceClient;
+use crate::error::Error;
+use crate::request::{CancelReplaceMode, OrderRequest, SnapshotAccountType, WithdrawRequest};
+use crate::response::{
+    AccountInformation, AccountSnapshot, AssetInfo, CancelReplaceResponse, DepositTransaction,
+    DustTransfer, ExchangeInformation, MarginTrade, MarketTrade, Order, OrderResponse, Trade,
+    WithdrawalTransaction,
+};
+
+impl BinanceClient {
+    /// Get a blocking client sharing this client
+    ///
+    /// See [`BlockingBinanceClient`].
+    pub fn blocking(&self) -> Result<BlockingBinanceClient, Error> {
+        Ok(BlockingBinanceClient {
+            client: self.clone(),
+            runtime: Runtime::new()?,
+        })
+    }
+}
+
+/// Blocking Binance client
+///
+/// Thin wrapper that runs the [`BinanceClient`] requests on an internal current-thread runtime.
+/// Must NOT be used from within an async context.
+#[derive(Debug, Clone)]
+pub struct BlockingBinanceClient {
+    client: BinanceClient,
+    runtime: Runtime,
+}
+
+impl BlockingBinanceClient {
+    /// Get the async client
+    #[inline]
+    pub fn as_async(&self) -> &BinanceClient {
+        &self.client
+    }
+
+    /// Test connectivity to the REST API
+    ///
+    /// See [`BinanceClient::ping`].
+    #[inline]
+    pub fn ping(&self) -> Result<(), Error> {
+        self.runtime.block_on(self.client.ping())
+    }
+
+    /// Check that the API is reachable
+    ///
+    /// See [`BinanceClient::healthcheck`].
+    #[inline]
+    pub fn healthcheck(&self) -> Result<(), Error> {
+        self.runtime.block_on(self.client.healthcheck())
+    }
+
+    /// Clear the response cache
+    ///
+    /// See [`BinanceClient::clear_cache`].
+    #[inline]
+    pub fn clear_cache(&self) {
+        self.client.clear_cache()
+    }
+
+    /// Get exchange information
+    ///
+    /// See [`BinanceClient::exchange_info`].
+    #[inline]
+    pub fn exchange_info(&self) -> Result<ExchangeInformation, Error> {
+        self.runtime.block_on(self.client.exchange_info())
+    }
+
+    /// Get the metadata of an asset (i.e., `BTC`)
+    ///
+    /// See [`BinanceClient::asset_info`].
+    #[inline]
+    pub fn asset_info(&self, code: &str) -> Result<Option<AssetInfo>, Error> {
+        self.runtime.block_on(self.client.asset_info(code))
+    }
+
+    /// Get older market trades for a symbol
+    ///
+    /// See [`BinanceClient::historical_trades`].
+    #[inline]
+    pub fn historical_trades<S>(
+        &self,
+        symbol: S,
+        from_id: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<MarketTrade>, Error>
+    where
+        S: Into<String>,
+    {
+        self.runtime
+            .block_on(self.client.historical_trades(symbol, from_id, limit))
+    }
+
+    /// Get account information
+    ///
+    /// See [`BinanceClient::get_account`].
+    #[inline]
+    pub fn get_account(&self) -> Result<AccountInformation, Error> {
+        self.runtime.block_on(self.client.get_account())
+    }
+
+    /// Get account information
+    ///
+    /// See [`BinanceClient::get_account_with_options`].
+    #[inline]
+    pub fn get_account_with_options(
+        &self,
+        omit_zero_balances: bool,
+    ) -> Result<AccountInformation, Error> {
+        self.runtime
+            .block_on(self.client.get_account_with_options(omit_zero_balances))
+    }
+
+    /// Get all the non-zero balances, as `(asset, total)` pairs
+    ///
+    /// See [`BinanceClient::all_balances`].
+    #[inline]
+    pub fn all_balances(&self) -> Result<Vec<(String, f64)>, Error> {
+        self.runtime.block_on(self.client.all_balances())
+    }
+
+    /// Get a **bitcoin** deposit address.
+    ///
+    /// See [`BinanceClient::bitcoin_deposit_address`].
+    #[inline]
+    pub fn bitcoin_deposit_address(&self) -> Result<String, Error> {
+        self.runtime.block_on(self.client.bitcoin_deposit_address())
+    }
+
+    /// Get **bitcoin** account deposit history
+    ///
+    /// See [`BinanceClient::deposit_history_bitcoin`].
+    #[inline]
+    pub fn deposit_history_bitcoin(&self) -> Result<Vec<DepositTransaction>, Error> {
+        self.runtime.block_on(self.client.deposit_history_bitcoin())
+    }
+
+    /// Get **bitcoin** account withdrawals history
+    ///
+    /// See [`BinanceClient::withdrawal_history_bitcoin`].
+    #[inline]
+    pub fn withdrawal_history_bitcoin(&self) -> Result<Vec<WithdrawalTransaction>, Error> {
+        self.runtime
+            .block_on(self.client.withdrawal_history_bitcoin())
+    }
+
+    /// Submit a withdrawal
+    ///
+    /// See [`BinanceClient::withdraw`].
+    #[inline]
+    pub fn withdraw(&self, req: WithdrawRequest) -> Result<String, Error> {
+        self.runtime.block_on(self.client.withdraw(req))
+    }
+
+    /// Measure the drift between the local clock and the server time
+    ///
+    /// See [`BinanceClient::sync_time`].
+    #[inline]
+    pub fn sync_time(&self) -> Result<i64, Error> {
+        self.runtime.block_on(self.client.sync_time())
+    }
+
+    /// Get the last measured clock drift, in milliseconds
+    ///
+    /// See [`BinanceClient::clock_drift`].
+    #[inline]
+    pub fn clock_drift(&self) -> Option<i64> {
+        self.client.clock_drift()
+    }
+
+    /// Get the daily account snapshots
+    ///
+    /// See [`BinanceClient::account_snapshots`].
+    #[inline]
+    pub fn account_snapshots(
+        &self,
+        account_type: SnapshotAccountType,
+        start_time: Option<DateTime<Utc>>,
+        end_time: Option<DateTime<Utc>>,
+        limit: Option<u8>,
+    ) -> Result<Vec<AccountSnapshot>, Error> {
+        self.runtime.block_on(self.client.account_snapshots(
+            account_type,
+            start_time,
+            end_time,
+            limit,
+        ))
+    }
+
+    /// Convert small balances to BNB
+    ///
+    /// See [`BinanceClient::dust_transfer`].
+    #[inline]
+    pub fn dust_transfer(&self, assets: &[&str]) -> Result<DustTransfer, Error> {
+        self.runtime.block_on(self.client.dust_transfer(assets))
+    }
+
+    /// Create a user data stream listen key
+    ///
+    /// See [`BinanceClient::create_listen_key`].
+    #[inline]
+    pub fn create_listen_key(&self) -> Result<String, Error> {
+        self.runtime.block_on(self.client.create_listen_key())
+    }
+
+    /// Extend the validity of a listen key
+    ///
+    /// See [`BinanceClient::keepalive_listen_key`].
+    #[inline]
+    pub fn keepalive_listen_key(&self, listen_key: &str) -> Result<(), Error> {
+        self.runtime
+            .block_on(self.client.keepalive_listen_key(listen_key))
+    }
+
+    /// Close a listen key
+    ///
+    /// See [`BinanceClient::close_listen_key`].
+    #[inline]
+    pub fn close_listen_key(&self, listen_key: &str) -> Result<(), Error> {
+        self.runtime
+            .block_on(self.client.close_listen_key(listen_key))
+    }
+
+    /// Place a new order
+    ///
+    /// See [`BinanceClient::place_order`].
+    #[inline]
+    pub fn place_order(&self, req: OrderRequest) -> Result<OrderResponse, Error> {
+        self.runtime.block_on(self.client.place_order(req))
+    }
+
+    /// Get the orders of a symbol, including the canceled and expired ones
+    ///
+    /// See [`BinanceClient::all_orders`].
+    #[inline]
+    pub fn all_orders<S>(
+        &self,
+        symbol: S,
+        order_id: Option<u64>,
+        start_time: Option<DateTime<Utc>>,
+        end_time: Option<DateTime<Utc>>,
+        limit: Option<u16>,
+    ) -> Result<Vec<Order>, Error>
+    where
+        S: Into<String>,
+    {
+        self.runtime.block_on(
+            self.client
+                .all_orders(symbol, order_id, start_time, end_time, limit),
+        )
+    }
+
+    /// Get all the orders of a symbol from `order_id` onwards
+    ///
+    /// See [`BinanceClient::all_orders_from_id`].
+    #[inline]
+    pub fn all_orders_from_id<S>(&self, symbol: S, order_id: u64) -> Result<Vec<Order>, Error>
+    where
+        S: Into<String>,
+    {
+        self.runtime
+            .block_on(self.client.all_orders_from_id(symbol, order_id))
+    }
+
+    /// Cancel an existing order and place a new one, in a single request
+    ///
+    /// See [`BinanceClient::cancel_replace`].
+    #[inline]
+    pub fn cancel_replace(
+        &self,
+        symbol: &str,
+        cancel_order_id: u64,
+        new_order: OrderRequest,
+        mode: CancelReplaceMode,
+    ) -> Result<CancelReplaceResponse, Error> {
+        self.runtime.block_on(
+            self.client
+                .cancel_replace(symbol, cancel_order_id, new_order, mode),
+        )
+    }
+
+    /// Get trades for a specific symbol
+    ///
+    /// See [`BinanceClient::trade_history_for_pair`].
+    #[inline]
+    pub fn trade_history_for_pair<S>(&self, symbol: S) -> Result<Vec<Trade>, Error>
+    where
+        S: Into<String>,
+    {
+        self.runtime
+            .block_on(self.client.trade_history_for_pair(symbol))
+    }
+
+    /// Get the margin trades of a symbol
+    ///
+    /// See [`BinanceClient::margin_trade_history`].
+    #[inline]
+    pub fn margin_trade_history<S>(
+        &self,
+        symbol: S,
+        is_isolated: bool,
+    ) -> Result<Vec<MarginTrade>, Error>
+    where
+        S: Into<String>,
+    {
+        self.runtime
+            .block_on(self.client.margin_trade_history(symbol, is_isolated))
+    }
+
+    /// Get the trades of an order
+    ///
+    /// See [`BinanceClient::trades_for_order`].
+    #[inline]
+    pub fn trades_for_order<S>(&self, symbol: S, order_id: u64) -> Result<Vec<Trade>, Error>
+    where
+        S: Into<String>,
+    {
+        self.runtime
+            .block_on(self.client.trades_for_order(symbol, order_id))
+    }
+
+    /// Get all the trades of a symbol since `start_time`
+    ///
+    /// See [`BinanceClient::all_trades_since`].
+    #[inline]
+    pub fn all_trades_since<S>(
+        &self,
+        symbol: S,
+        start_time: DateTime<Utc>,
+    ) -> Result<Vec<Trade>, Error>
+    where
+        S: Into<String>,
+    {
+        self.runtime
+            .block_on(self.client.all_trades_since(symbol, start_time))
+    }
+
+    /// Simple incremental sync for **bitcoin pairs only**.
+    ///
+    /// See [`BinanceClient::trade_history_bitcoin_incremental`].
+    #[inline]
+    pub fn trade_history_bitcoin_incremental(
+        &self,
+        account: &AccountInformation,
+        cursor: &mut HashMap<String, u64>,
+    ) -> Result<HashMap<String, Vec<Trade>>, Error> {
+        self.runtime.block_on(
+            self.client
+                .trade_history_bitcoin_incremental(account, cursor),
+        )
+    }
+
+    /// Get trades for BTC pairs related to assets with non-zero balance.
+    ///
+    /// See [`BinanceClient::trade_history_bitcoin`].
+    #[inline]
+    pub fn trade_history_bitcoin(
+        &self,
+        account: &AccountInformation,
+    ) -> Result<HashMap<String, Vec<Trade>>, Error> {
+        self.runtime
+            .block_on(self.client.trade_history_bitcoin(account))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::builder::BinanceEndpoint;
+
+    #[test]
+    fn test_blocking_exchange_info() {
+        // The mock server needs its own runtime, as the blocking client can't run inside one
+        let server_runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = server_runtime.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/api/v3/exchangeInfo"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"{"timezone":"UTC","serverTime":1565246363776,"rateLimits":[],"symbols":[]}"#,
+                ))
+                .mount(&server)
+                .await;
+            server
+        });
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .build()
+            .unwrap()
+            .blocking()
+            .unwrap();
+
+        let info = client.exchange_info().unwrap();
+        assert_eq!(info.timezone, "UTC");
+        assert!(info.symbols.is_empty());
+    }
+}
@@ -2,14 +2,21 @@
 
 use std::time::Duration;
 
+use common::cache::CacheConfig;
+use common::interceptor::Interceptors;
+use reqwest::header::HeaderMap;
 use url::Url;
 
 use crate::auth::BinanceAuth;
 use crate::client::BinanceClient;
 use crate::constant::{
-    DEFAULT_RECV_WINDOW, DEFAULT_TIMEOUT, SPOT_MAINNET, SPOT_MAINNET_US, SPOT_TESTNET,
+    DEFAULT_CONCURRENCY, DEFAULT_RECV_WINDOW, DEFAULT_TIMEOUT, MAX_WEIGHT_PER_MIN, SPOT_MAINNET,
+    SPOT_MAINNET_US, SPOT_TESTNET, STREAM_MAINNET, STREAM_MAINNET_US, STREAM_TESTNET,
 };
 use crate::error::Error;
+use crate::response::RateLimit;
+
+const REQUEST_WEIGHT_RATE_LIMIT: &str = "REQUEST_WEIGHT";
 
 /// Binance endpoint type
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -60,6 +67,40 @@ impl BinanceEndpoint {
         }
     }
 
+    /// Check if the endpoint is a mainnet (international or US) one
+    pub fn is_mainnet(&self) -> bool {
+        [SPOT_MAINNET, SPOT_MAINNET_US].into_iter().any(|mainnet| {
+            Url::parse(mainnet)
+                .map(|mainnet| mainnet.host_str() == self.url.host_str())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Get the WebSocket endpoint of the streams
+    ///
+    /// Custom endpoints keep their host, with the `ws`/`wss` scheme.
+    pub fn stream_url(&self) -> Url {
+        let streams = [
+            (SPOT_MAINNET, STREAM_MAINNET),
+            (SPOT_MAINNET_US, STREAM_MAINNET_US),
+            (SPOT_TESTNET, STREAM_TESTNET),
+        ];
+
+        for (rest, stream) in streams {
+            if let Ok(rest) = Url::parse(rest) {
+                if rest.host_str() == self.url.host_str() {
+                    return Url::parse(stream).expect("Invalid stream endpoint");
+                }
+            }
+        }
+
+        let mut url: Url = self.url.clone();
+        let scheme: &str = if url.scheme() == "http" { "ws" } else { "wss" };
+        // Switching between special schemes can't fail
+        let _ = url.set_scheme(scheme);
+        url
+    }
+
     /// Get URL endpoint
     #[inline]
     pub fn as_url(&self) -> &Url {
@@ -73,6 +114,104 @@ impl BinanceEndpoint {
     }
 }
 
+/// Rate limit interval
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RateLimitInterval {
+    /// Second
+    Second,
+    /// Minute
+    #[default]
+    Minute,
+    /// Hour
+    Hour,
+    /// Day
+    Day,
+}
+
+impl RateLimitInterval {
+    /// Get the interval name, as in the exchange info (i.e., `MINUTE`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Second => "SECOND",
+            Self::Minute => "MINUTE",
+            Self::Hour => "HOUR",
+            Self::Day => "DAY",
+        }
+    }
+
+    /// Get the interval letter, as in the response headers (i.e., `M`)
+    fn letter(&self) -> char {
+        match self {
+            Self::Second => 'S',
+            Self::Minute => 'M',
+            Self::Hour => 'H',
+            Self::Day => 'D',
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        match self {
+            Self::Second => Duration::from_secs(1),
+            Self::Minute => Duration::from_secs(60),
+            Self::Hour => Duration::from_secs(60 * 60),
+            Self::Day => Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+/// Request weight limit
+///
+/// The used weight is read from the `X-MBX-USED-WEIGHT-<interval>` response header (i.e.,
+/// `X-MBX-USED-WEIGHT-1M`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeightLimit {
+    /// Max weight per interval
+    pub limit: u32,
+    /// Interval number (i.e., `1` for `1M`)
+    pub interval_num: u16,
+    /// Interval
+    pub interval: RateLimitInterval,
+}
+
+impl Default for WeightLimit {
+    fn default() -> Self {
+        Self::new(MAX_WEIGHT_PER_MIN, 1, RateLimitInterval::Minute)
+    }
+}
+
+impl WeightLimit {
+    /// Construct a new weight limit
+    #[inline]
+    pub fn new(limit: u32, interval_num: u16, interval: RateLimitInterval) -> Self {
+        Self {
+            limit,
+            interval_num,
+            interval,
+        }
+    }
+
+    /// Name of the response header with the used weight
+    pub(crate) fn header_name(&self) -> String {
+        format!(
+            "X-MBX-USED-WEIGHT-{}{}",
+            self.interval_num,
+            self.interval.letter()
+        )
+    }
+
+    /// Duration of the interval
+    pub(crate) fn window(&self) -> Duration {
+        self.interval.duration() * u32::from(self.interval_num)
+    }
+
+    /// Check if an exchange info rate limit is the request weight one of this interval
+    pub(crate) fn matches(&self, rate_limit: &RateLimit) -> bool {
+        rate_limit.rate_limit_type == REQUEST_WEIGHT_RATE_LIMIT
+            && rate_limit.interval == self.interval.as_str()
+            && rate_limit.interval_num == self.interval_num
+    }
+}
+
 /// Binance client builder
 #[derive(Debug, Clone)]
 pub struct BinanceClientBuilder {
@@ -82,8 +221,30 @@ pub struct BinanceClientBuilder {
     pub auth: BinanceAuth,
     /// Recv window
     pub recv_window: u64,
-    /// Request timeout
+    /// Request timeout, including the connection phase
     pub timeout: Duration,
+    /// Connection timeout
+    pub connect_timeout: Option<Duration>,
+    /// Upper bound of a whole operation, including retries and pagination
+    pub overall_deadline: Option<Duration>,
+    /// Built-in rate limiter
+    pub rate_limit: bool,
+    /// Request weight limit, used by the built-in rate limiter
+    ///
+    /// If not set, the limit of the exchange info is used once fetched (default: 6000 per minute).
+    pub weight_limit: Option<WeightLimit>,
+    /// Max number of pairs fetched concurrently
+    pub concurrency: usize,
+    /// Live trading confirmed (see [`BinanceClientBuilder::confirm_live`])
+    pub live_confirmed: bool,
+    /// Response cache
+    pub cache: CacheConfig,
+    /// Request and response interceptors
+    pub interceptors: Interceptors,
+    /// Headers added to every request
+    pub default_headers: HeaderMap,
+    /// WebSocket endpoint of the streams (derived from the endpoint if not set)
+    pub stream_endpoint: Option<Url>,
 }
 
 impl Default for BinanceClientBuilder {
@@ -93,6 +254,16 @@ impl Default for BinanceClientBuilder {
             auth: BinanceAuth::default(),
             recv_window: DEFAULT_RECV_WINDOW,
             timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            overall_deadline: None,
+            rate_limit: true,
+            weight_limit: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            live_confirmed: false,
+            cache: CacheConfig::default(),
+            interceptors: Interceptors::default(),
+            default_headers: HeaderMap::new(),
+            stream_endpoint: None,
         }
     }
 }
@@ -126,9 +297,150 @@ impl BinanceClientBuilder {
         self
     }
 
+    /// Set connection timeout
+    #[inline]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the overall deadline of an operation (default: none)
+    ///
+    /// Unlike [`BinanceClientBuilder::timeout`], it covers the retries and the pagination (i.e.,
+    /// [`BinanceClient::trade_history_bitcoin`]). [`Error::Deadline`] is returned if exceeded.
+    #[inline]
+    pub fn overall_deadline(mut self, deadline: Duration) -> Self {
+        self.overall_deadline = Some(deadline);
+        self
+    }
+
+    /// Enable or disable the built-in rate limiter (default: enabled)
+    ///
+    /// When disabled, requests are sent once, without retrying on `429` and without the
+    /// weight-based throttling. Useful when rate limits are already handled externally.
+    #[inline]
+    pub fn rate_limit(mut self, enable: bool) -> Self {
+        self.rate_limit = enable;
+        self
+    }
+
+    /// Set the request weight limit and the interval to track (default: 6000 per minute)
+    ///
+    /// Accounts on higher VIP tiers have higher limits. An explicit limit is always kept: if not
+    /// set, the limit of [`BinanceClient::exchange_info`] for the same interval is used once
+    /// fetched.
+    #[inline]
+    pub fn weight_limit(mut self, weight_limit: WeightLimit) -> Self {
+        self.weight_limit = Some(weight_limit);
+        self
+    }
+
+    /// Set max number of pairs fetched concurrently (default: 5)
+    ///
+    /// Used by [`BinanceClient::trade_history_bitcoin`]. Requests still go through the built-in
+    /// rate limiter: use `1` to fetch the pairs sequentially.
+    #[inline]
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Confirm that orders are meant to be placed on mainnet
+    ///
+    /// Without the confirmation, a warning is logged (once) when an order method is called
+    /// against a mainnet endpoint. Use [`BinanceEndpointType::Testnet`] during development.
+    #[inline]
+    pub fn confirm_live(mut self) -> Self {
+        self.live_confirmed = true;
+        self
+    }
+
+    /// Set response cache (default: disabled)
+    ///
+    /// Cacheable endpoints: `exchange_info`.
+    #[inline]
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Set request and response interceptors (default: none)
+    ///
+    /// Applied to every request, including the retries.
+    #[inline]
+    pub fn interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
+    /// Set headers added to every request (default: none)
+    ///
+    /// The Binance headers (i.e., `X-MBX-APIKEY`) win on conflict.
+    #[inline]
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Set the WebSocket endpoint of the streams (default: derived from the endpoint)
+    ///
+    /// See [`BinanceEndpoint::stream_url`].
+    #[inline]
+    pub fn stream_endpoint(mut self, url: Url) -> Self {
+        self.stream_endpoint = Some(url);
+        self
+    }
+
     /// Build client
     #[inline]
     pub fn build(self) -> Result<BinanceClient, Error> {
         BinanceClient::from_builder(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_is_mainnet() {
+        assert!(BinanceEndpoint::from_type(BinanceEndpointType::Mainnet).is_mainnet());
+        assert!(BinanceEndpoint::from_type(BinanceEndpointType::MainnetUs).is_mainnet());
+        assert!(!BinanceEndpoint::from_type(BinanceEndpointType::Testnet).is_mainnet());
+
+        let url: Url = Url::parse("http://127.0.0.1:8080").unwrap();
+        assert!(!BinanceEndpoint::new(url).is_mainnet());
+    }
+
+    #[test]
+    fn test_endpoint_stream_url() {
+        let endpoint = BinanceEndpoint::from_type(BinanceEndpointType::Mainnet);
+        assert_eq!(
+            endpoint.stream_url().as_str(),
+            "wss://stream.binance.com:9443/"
+        );
+
+        let endpoint = BinanceEndpoint::from_type(BinanceEndpointType::Testnet);
+        assert_eq!(
+            endpoint.stream_url().as_str(),
+            "wss://stream.testnet.binance.vision/"
+        );
+
+        let url: Url = Url::parse("http://127.0.0.1:8080").unwrap();
+        assert_eq!(
+            BinanceEndpoint::new(url).stream_url().as_str(),
+            "ws://127.0.0.1:8080/"
+        );
+    }
+
+    #[test]
+    fn test_weight_limit_interval() {
+        let limit = WeightLimit::default();
+        assert_eq!(limit.header_name(), "X-MBX-USED-WEIGHT-1M");
+        assert_eq!(limit.window(), Duration::from_secs(60));
+
+        let limit = WeightLimit::new(1_000, 10, RateLimitInterval::Second);
+        assert_eq!(limit.header_name(), "X-MBX-USED-WEIGHT-10S");
+        assert_eq!(limit.window(), Duration::from_secs(10));
+    }
+}
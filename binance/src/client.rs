@@ -1,13 +1,20 @@
 //! Binance client
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, Ordering};
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+use common::cache::ResponseCache;
+use common::interceptor::{self, Interceptors, RequestError};
+use futures_util::{StreamExt, stream};
 use hmac::{Hmac, Mac};
 use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
-use reqwest::{Client, RequestBuilder, Response, StatusCode};
-use serde::de::DeserializeOwned;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde::de::{DeserializeOwned, IgnoredAny};
 use sha2::Sha256;
 use tokio::sync::OnceCell;
 use tokio::time;
@@ -15,25 +22,53 @@ use url::Url;
 
 use crate::api::{BinanceApi, Spot};
 use crate::auth::BinanceAuth;
-use crate::builder::BinanceClientBuilder;
-use crate::constant::{BTC_TICKER, MAX_WEIGHT_PER_MIN, USER_AGENT_NAME};
+use crate::builder::{BinanceClientBuilder, WeightLimit};
+use crate::constant::{
+    BTC_TICKER, MAX_SERVER_ERROR_RETRIES, SERVER_ERROR_RETRY_DELAY, TRACING_TARGET, USER_AGENT_NAME,
+};
 use crate::error::Error;
+use crate::request::{CancelReplaceMode, OrderRequest, SnapshotAccountType, WithdrawRequest};
 use crate::response::{
-    AccountInformation, Balance, DepositAddress, DepositTransaction, ExchangeInformation, Symbol,
-    Trade, WithdrawalTransaction,
+    AccountInformation, AccountSnapshot, AccountSnapshots, AssetInfo, Balance,
+    CancelReplaceErrorResponse, CancelReplaceResponse, DepositAddress, DepositTransaction,
+    DustTransfer, ExchangeInformation, ListenKey, MarginTrade, MarketTrade, Order, OrderResponse,
+    ServerTime, Symbol, Trade, Withdrawal, WithdrawalTransaction,
 };
-use crate::util::build_signed_request;
+use crate::util::{build_request, build_signed_request, normalize_symbol};
 
 const MY_TRADES_MAX_LIMIT: usize = 500;
+const MARGIN_TRADES_MAX_LIMIT: usize = 1000;
+const ALL_ORDERS_MAX_LIMIT: usize = 1000;
+const CACHE_EXCHANGE_INFO: &str = "exchange_info";
 
 /// Binance client
+///
+/// Cheap to clone: clones share the same connection pool and caches.
 #[derive(Clone)]
 pub struct BinanceClient {
     client: Client,
     host: Url,
+    #[cfg(feature = "stream")]
+    pub(crate) stream_host: Url,
     auth: BinanceAuth,
     recv_window: u64,
-    bitcoin_pairs: OnceCell<Vec<Symbol>>,
+    rate_limit: bool,
+    overall_deadline: Option<Duration>,
+    concurrency: usize,
+    weight_limit: WeightLimit,
+    /// Weight limit set explicitly in the builder: not overwritten by the exchange info
+    weight_limit_fixed: bool,
+    /// Weight limit, updated from the exchange info
+    weight_cap: Arc<AtomicU32>,
+    /// Warn before placing orders on mainnet
+    live_guard: bool,
+    live_warned: Arc<AtomicBool>,
+    bitcoin_pairs: Arc<OnceCell<Vec<Symbol>>>,
+    assets: Arc<OnceCell<HashMap<String, AssetInfo>>>,
+    cache: Arc<ResponseCache>,
+    interceptors: Interceptors,
+    /// Last measured clock drift, in milliseconds (`i64::MIN` if never measured)
+    clock_drift: Arc<AtomicI64>,
 }
 
 impl fmt::Debug for BinanceClient {
@@ -56,17 +91,43 @@ impl BinanceClient {
         BinanceClientBuilder::default()
     }
 
-    #[inline]
     pub(super) fn from_builder(builder: BinanceClientBuilder) -> Result<Self, Error> {
+        let mut client = Client::builder()
+            .default_headers(builder.default_headers)
+            .user_agent(USER_AGENT_NAME)
+            .timeout(builder.timeout);
+
+        if let Some(connect_timeout) = builder.connect_timeout {
+            client = client.connect_timeout(connect_timeout);
+        }
+
+        let live_guard: bool = builder.endpoint.is_mainnet() && !builder.live_confirmed;
+        let weight_limit: WeightLimit = builder.weight_limit.unwrap_or_default();
+        #[cfg(feature = "stream")]
+        let stream_host: Url = builder
+            .stream_endpoint
+            .unwrap_or_else(|| builder.endpoint.stream_url());
+
         Ok(Self {
-            client: Client::builder()
-                .user_agent(USER_AGENT_NAME)
-                .timeout(builder.timeout)
-                .build()?,
+            client: client.build()?,
             host: builder.endpoint.into_url(),
+            #[cfg(feature = "stream")]
+            stream_host,
             auth: builder.auth,
             recv_window: builder.recv_window,
-            bitcoin_pairs: OnceCell::new(),
+            rate_limit: builder.rate_limit,
+            overall_deadline: builder.overall_deadline,
+            concurrency: builder.concurrency.max(1),
+            weight_limit,
+            weight_limit_fixed: builder.weight_limit.is_some(),
+            weight_cap: Arc::new(AtomicU32::new(weight_limit.limit)),
+            live_guard,
+            live_warned: Arc::new(AtomicBool::new(false)),
+            bitcoin_pairs: Arc::new(OnceCell::new()),
+            assets: Arc::new(OnceCell::new()),
+            cache: Arc::new(ResponseCache::new(builder.cache)),
+            interceptors: builder.interceptors,
+            clock_drift: Arc::new(AtomicI64::new(i64::MIN)),
         })
     }
 
@@ -114,12 +175,54 @@ impl BinanceClient {
         Ok(custom_headers)
     }
 
-    async fn handle_http_response<T>(&self, response: Response) -> Result<T, Error>
+    async fn handle_http_response<T>(
+        &self,
+        api: &BinanceApi,
+        response: Response,
+    ) -> Result<T, Error>
     where
         T: DeserializeOwned,
     {
-        let response: Response = response.error_for_status()?;
-        Ok(response.json().await?)
+        let status: StatusCode = response.status();
+
+        tracing::debug!(
+            target: TRACING_TARGET,
+            endpoint = api.http_path(),
+            status = status.as_u16(),
+            "response received"
+        );
+
+        if !status.is_success() {
+            tracing::error!(
+                target: TRACING_TARGET,
+                endpoint = api.http_path(),
+                status = status.as_u16(),
+                "HTTP error"
+            );
+        }
+
+        let response: Response = interceptor::error_for_server_status(response).await?;
+
+        // A failed cancel-replace still reports the outcome of both operations
+        if status.is_client_error() && matches!(api, BinanceApi::Spot(Spot::OrderCancelReplace)) {
+            if let Err(e) = response.error_for_status_ref() {
+                let method: Option<Method> = response.extensions().get::<Method>().cloned();
+                let body: String = response.text().await.unwrap_or_default();
+
+                return match serde_json::from_str::<CancelReplaceErrorResponse>(&body) {
+                    Ok(res) => Err(Error::CancelReplaceFailed {
+                        status: status.as_u16(),
+                        code: res.code,
+                        msg: res.msg,
+                        data: Box::new(res.data),
+                    }),
+                    Err(..) => Err(RequestError::new(method, e).into()),
+                };
+            }
+        }
+
+        let response: Response = interceptor::error_for_status(response)?;
+        Ok(interceptor::json(response).await?)
     }
 
     async fn get<T>(&self, api: BinanceApi, request: Option<String>) -> Result<T, Error>
@@ -137,7 +240,56 @@ impl BinanceClient {
 
         let req = self.client.get(url);
 
-        self.send_req(req, api.request_weight()).await
+        self.send_req(req, &api).await
+    }
+
+    /// Send a GET request with the API key header, without signing it.
+    async fn get_with_api_key<T>(
+        &self,
+        api: BinanceApi,
+        request: Option<String>,
+    ) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        // Build URL endpoint
+        let mut url: Url = self.host.join(api.http_path())?;
+
+        if let Some(request) = request {
+            if !request.is_empty() {
+                url.set_query(Some(&request));
+            }
+        }
+
+        let headers = self.build_headers(false)?;
+        let req = self.client.get(url).headers(headers);
+
+        self.send_req(req, &api).await
+    }
+
+    /// Send a request with the API key header, without signing it.
+    async fn send_with_api_key<T>(
+        &self,
+        method: Method,
+        api: BinanceApi,
+        request: Option<String>,
+    ) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        // Build URL endpoint
+        let mut url: Url = self.host.join(api.http_path())?;
+
+        if let Some(request) = request {
+            if !request.is_empty() {
+                url.set_query(Some(&request));
+            }
+        }
+
+        let headers = self.build_headers(false)?;
+        let req = self.client.request(method, url).headers(headers);
+
+        self.send_req(req, &api).await
     }
 
     async fn get_signed<T>(&self, api: BinanceApi, request: Option<String>) -> Result<T, Error>
@@ -148,31 +300,111 @@ impl BinanceClient {
         let headers = self.build_headers(true)?;
         let req = self.client.get(url).headers(headers);
 
-        self.send_req(req, api.request_weight()).await
+        self.send_req(req, &api).await
+    }
+
+    async fn post_signed<T>(&self, api: BinanceApi, request: String) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let url = self.sign_request(&api, Some(request))?;
+        let headers = self.build_headers(true)?;
+        let req = self.client.post(url).headers(headers);
+
+        self.send_req(req, &api).await
+    }
+
+    /// Run an operation (i.e., including retries or pagination) within the overall deadline
+    async fn with_deadline<F, T>(&self, operation: F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        match self.overall_deadline {
+            Some(deadline) => time::timeout(deadline, operation)
+                .await
+                .map_err(|_| Error::Deadline(deadline))?,
+            None => operation.await,
+        }
+    }
+
+    #[inline]
+    async fn send_req<T>(&self, req: RequestBuilder, api: &BinanceApi) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.with_deadline(self.send_req_with_retries(req, api))
+            .await
     }
 
-    async fn send_req<T>(&self, req: RequestBuilder, request_weight: u32) -> Result<T, Error>
+    /// Send the request through the interceptors
+    async fn send_intercepted(&self, req: RequestBuilder) -> Result<Response, Error> {
+        Ok(self.interceptors.send(req).await?)
+    }
+
+    async fn send_req_with_retries<T>(
+        &self,
+        req: RequestBuilder,
+        api: &BinanceApi,
+    ) -> Result<T, Error>
     where
         T: DeserializeOwned,
     {
+        let request_weight: u32 = api.request_weight();
+        let weight_limit: WeightLimit = self.current_weight_limit();
+
+        // Rate limiter disabled: send the request once
+        if !self.rate_limit {
+            let response: Response = self.send_intercepted(req).await?;
+            return self.handle_http_response(api, response).await;
+        }
+
+        // Only idempotent requests are retried on server errors (i.e., an order may have been
+        // placed even if a 5xx is returned)
+        let idempotent: bool = is_idempotent(&req);
+        let mut server_error_retries: u32 = 0;
+
         loop {
             // Try to clone the request builder
             let req: RequestBuilder = req.try_clone().ok_or(Error::CantCloneRequest)?;
 
             // Send the request
-            let response: Response = req.send().await?;
-            let used_weight: u32 = used_weight_1m(response.headers());
+            let response: Response = self.send_intercepted(req).await?;
+            let used_weight: u32 = used_weight(response.headers(), &weight_limit);
             let status: StatusCode = response.status();
 
+            if status.is_server_error()
+                && idempotent
+                && server_error_retries < MAX_SERVER_ERROR_RETRIES
+            {
+                server_error_retries += 1;
+
+                let sleep: Duration = SERVER_ERROR_RETRY_DELAY * server_error_retries;
+
+                tracing::warn!(
+                    target: TRACING_TARGET,
+                    endpoint = api.http_path(),
+                    status = status.as_u16(),
+                    sleep_ms = sleep.as_millis() as u64,
+                    "server error, retrying"
+                );
+
+                time::sleep(sleep).await;
+
+                continue;
+            }
+
             if status == StatusCode::TOO_MANY_REQUESTS {
                 let sleep: Duration = retry_after_ms(response.headers())
-                    .or_else(|| throttle_delay(used_weight, request_weight))
+                    .or_else(|| throttle_delay(used_weight, request_weight, &weight_limit))
                     .unwrap_or_else(|| Duration::from_millis(200));
 
                 tracing::warn!(
-                    "Rate limit hit (429)! used={}. Sleeping {} ms before retry",
+                    target: TRACING_TARGET,
+                    endpoint = api.http_path(),
+                    status = status.as_u16(),
                     used_weight,
-                    sleep.as_millis()
+                    sleep_ms = sleep.as_millis() as u64,
+                    "rate limit hit, retrying"
                 );
 
                 time::sleep(sleep).await;
@@ -180,50 +412,244 @@ impl BinanceClient {
                 continue;
             }
 
-            if let Some(sleep) = throttle_delay(used_weight, request_weight) {
-                let available: u32 = MAX_WEIGHT_PER_MIN.saturating_sub(used_weight);
+            if let Some(sleep) = throttle_delay(used_weight, request_weight, &weight_limit) {
+                let available: u32 = weight_limit.limit.saturating_sub(used_weight);
                 let deficit: u32 = used_weight
                     .saturating_add(request_weight)
-                    .saturating_sub(MAX_WEIGHT_PER_MIN);
+                    .saturating_sub(weight_limit.limit);
 
                 tracing::warn!(
-                    "Rate limit near! used={} available={} deficit={}. Sleeping {} ms",
+                    target: TRACING_TARGET,
+                    endpoint = api.http_path(),
                     used_weight,
                     available,
                     deficit,
-                    sleep.as_millis()
+                    sleep_ms = sleep.as_millis() as u64,
+                    "rate limit near, throttling"
                 );
 
                 time::sleep(sleep).await;
             }
 
-            return self.handle_http_response(response).await;
+            return self.handle_http_response(api, response).await;
+        }
+    }
+
+    /// Weight limit, with the cap learned from the exchange info
+    fn current_weight_limit(&self) -> WeightLimit {
+        WeightLimit {
+            limit: self.weight_cap.load(Ordering::Relaxed),
+            ..self.weight_limit
+        }
+    }
+
+    /// Update the weight cap from the exchange info, if it lists the tracked interval
+    ///
+    /// Skipped if the limit was set explicitly in the builder.
+    fn update_weight_cap(&self, info: &ExchangeInformation) {
+        if self.weight_limit_fixed {
+            return;
+        }
+
+        let limit: Option<u32> = info
+            .rate_limits
+            .iter()
+            .find(|rate_limit| self.weight_limit.matches(rate_limit))
+            .map(|rate_limit| u32::try_from(rate_limit.limit).unwrap_or(u32::MAX));
+
+        if let Some(limit) = limit {
+            self.weight_cap.store(limit, Ordering::Relaxed);
+        }
+    }
+
+    /// Test connectivity to the REST API
+    ///
+    /// Doesn't require authentication.
+    pub async fn ping(&self) -> Result<(), Error> {
+        let _: IgnoredAny = self.get(BinanceApi::Spot(Spot::Ping), None).await?;
+        Ok(())
+    }
+
+    /// Check that the API is reachable
+    ///
+    /// If API keys are configured, they are verified by fetching the account
+    /// (see [`BinanceClient::get_account_with_options`]).
+    pub async fn healthcheck(&self) -> Result<(), Error> {
+        self.ping().await?;
+
+        if let BinanceAuth::ApiKeys { .. } = self.auth {
+            self.get_account_with_options(true).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Measure the drift between the local clock and the server time
+    ///
+    /// Returns the drift in milliseconds: positive if the server is ahead of the local clock.
+    /// The drift is also recorded, see [`BinanceClient::clock_drift`].
+    ///
+    /// Signed requests are rejected (error `-1021`) when the drift approaches the `recvWindow`:
+    /// a warning is logged if it exceeds half of it.
+    pub async fn sync_time(&self) -> Result<i64, Error> {
+        let sent_at: i64 = Utc::now().timestamp_millis();
+        let time: ServerTime = self.get(BinanceApi::Spot(Spot::Time), None).await?;
+        let received_at: i64 = Utc::now().timestamp_millis();
+
+        let drift: i64 = clock_drift(sent_at, time.server_time, received_at);
+        self.clock_drift.store(drift, Ordering::Relaxed);
+
+        if drift.unsigned_abs() > self.recv_window / 2 {
+            tracing::warn!(
+                target: TRACING_TARGET,
+                drift_ms = drift,
+                recv_window_ms = self.recv_window,
+                "local clock drifting from the server time"
+            );
         }
+
+        Ok(drift)
+    }
+
+    /// Get the clock drift measured by the last [`BinanceClient::sync_time`], in milliseconds
+    ///
+    /// Returns `None` if never measured.
+    pub fn clock_drift(&self) -> Option<i64> {
+        let drift: i64 = self.clock_drift.load(Ordering::Relaxed);
+        (drift != i64::MIN).then_some(drift)
+    }
+
+    /// Clear the response cache
+    #[inline]
+    pub fn clear_cache(&self) {
+        self.cache.clear();
     }
 
     /// Get exchange information
+    ///
+    /// Cached if enabled in [`BinanceClientBuilder::cache`].
     pub async fn exchange_info(&self) -> Result<ExchangeInformation, Error> {
-        self.get(BinanceApi::Spot(Spot::ExchangeInfo), None).await
+        if let Some(info) = self.cache.get(CACHE_EXCHANGE_INFO, "") {
+            return Ok(info);
+        }
+
+        let info: ExchangeInformation =
+            self.get(BinanceApi::Spot(Spot::ExchangeInfo), None).await?;
+        self.update_weight_cap(&info);
+        self.cache.insert(CACHE_EXCHANGE_INFO, "", &info);
+        Ok(info)
+    }
+
+    /// Get older market trades for a symbol (i.e., "BTCUSDT")
+    ///
+    /// Trades are fetched starting from `from_id` (most recent if `None`), up to `limit`
+    /// (default: 500, max: 1000).
+    ///
+    /// Requires an API key, but no signature.
+    pub async fn historical_trades<S>(
+        &self,
+        symbol: S,
+        from_id: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<MarketTrade>, Error>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = Vec::new();
+        parameters.push((String::from("symbol"), normalize_symbol(&symbol.into())));
+
+        if let Some(from_id) = from_id {
+            parameters.push((String::from("fromId"), from_id.to_string()));
+        }
+
+        if let Some(limit) = limit {
+            parameters.push((String::from("limit"), limit.to_string()));
+        }
+
+        let request: String = build_request(parameters);
+        self.get_with_api_key(BinanceApi::Spot(Spot::HistoricalTrades), Some(request))
+            .await
     }
 
     /// Get account information
+    #[inline]
     pub async fn get_account(&self) -> Result<AccountInformation, Error> {
-        let mut parameters = BTreeMap::new();
-        parameters.insert(String::from("omitZeroBalances"), true.to_string());
+        self.get_account_with_options(false).await
+    }
+
+    /// Get account information
+    ///
+    /// If `omit_zero_balances` is `true`, the empty balances are dropped by the server,
+    /// shrinking the response of accounts that have traded many assets.
+    pub async fn get_account_with_options(
+        &self,
+        omit_zero_balances: bool,
+    ) -> Result<AccountInformation, Error> {
+        let mut parameters = Vec::new();
+
+        if omit_zero_balances {
+            parameters.push((String::from("omitZeroBalances"), String::from("true")));
+        }
 
         // Build signed request
-        let request: String = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        let request: String = build_signed_request(parameters, self.recv_window)?;
 
         // Get signed request
         self.get_signed(BinanceApi::Spot(Spot::Account), Some(request))
             .await
     }
 
+    /// Get the daily account snapshots (i.e., the historical balances)
+    ///
+    /// Only the last 30 days are available, `limit` defaults to 7 (min: 7, max: 30).
+    pub async fn account_snapshots(
+        &self,
+        account_type: SnapshotAccountType,
+        start_time: Option<DateTime<Utc>>,
+        end_time: Option<DateTime<Utc>>,
+        limit: Option<u8>,
+    ) -> Result<Vec<AccountSnapshot>, Error> {
+        let mut parameters = vec![(String::from("type"), account_type.as_str().to_string())];
+
+        if let Some(start_time) = start_time {
+            parameters.push((
+                String::from("startTime"),
+                start_time.timestamp_millis().to_string(),
+            ));
+        }
+
+        if let Some(end_time) = end_time {
+            parameters.push((
+                String::from("endTime"),
+                end_time.timestamp_millis().to_string(),
+            ));
+        }
+
+        if let Some(limit) = limit {
+            parameters.push((String::from("limit"), limit.to_string()));
+        }
+
+        let request: String = build_signed_request(parameters, self.recv_window)?;
+        let snapshots: AccountSnapshots = self
+            .get_signed(BinanceApi::Spot(Spot::AccountSnapshot), Some(request))
+            .await?;
+        Ok(snapshots.snapshot_vos)
+    }
+
+    /// Get all the non-zero balances, as `(asset, total)` pairs
+    ///
+    /// The total includes the locked amount.
+    pub async fn all_balances(&self) -> Result<Vec<(String, f64)>, Error> {
+        let account: AccountInformation = self.get_account_with_options(true).await?;
+        Ok(non_zero_balances(account.balances))
+    }
+
     /// Get a **bitcoin** deposit address.
     pub async fn bitcoin_deposit_address(&self) -> Result<String, Error> {
-        let mut parameters = BTreeMap::new();
-        parameters.insert(String::from("coin"), BTC_TICKER.to_string());
-        parameters.insert(String::from("network"), BTC_TICKER.to_string());
+        let parameters = vec![
+            (String::from("coin"), BTC_TICKER.to_string()),
+            (String::from("network"), BTC_TICKER.to_string()),
+        ];
 
         let request: String = build_signed_request(parameters, self.recv_window)?;
         let address: DepositAddress = self
@@ -239,8 +665,7 @@ impl BinanceClient {
 
     /// Get **bitcoin** account deposit history
     pub async fn deposit_history_bitcoin(&self) -> Result<Vec<DepositTransaction>, Error> {
-        let mut parameters = BTreeMap::new();
-        parameters.insert(String::from("coin"), BTC_TICKER.to_string());
+        let parameters = vec![(String::from("coin"), BTC_TICKER.to_string())];
 
         let request: String = build_signed_request(parameters, self.recv_window)?;
         self.get_signed(BinanceApi::Spot(Spot::DepositHistory), Some(request))
@@ -249,14 +674,152 @@ impl BinanceClient {
 
     /// Get **bitcoin** account withdrawals history
     pub async fn withdrawal_history_bitcoin(&self) -> Result<Vec<WithdrawalTransaction>, Error> {
-        let mut parameters = BTreeMap::new();
-        parameters.insert(String::from("coin"), BTC_TICKER.to_string());
+        let parameters = vec![(String::from("coin"), BTC_TICKER.to_string())];
 
         let request: String = build_signed_request(parameters, self.recv_window)?;
         self.get_signed(BinanceApi::Spot(Spot::WithdrawalHistory), Some(request))
             .await
     }
 
+    /// Submit a withdrawal
+    ///
+    /// Returns the withdrawal ID.
+    pub async fn withdraw(&self, req: WithdrawRequest) -> Result<String, Error> {
+        let request: String = build_signed_request(req.into_parameters(), self.recv_window)?;
+        let withdrawal: Withdrawal = self
+            .post_signed(BinanceApi::Spot(Spot::Withdraw), request)
+            .await?;
+        Ok(withdrawal.id)
+    }
+
+    /// Convert small balances (i.e., `["ETH", "LTC"]`) to BNB
+    pub async fn dust_transfer(&self, assets: &[&str]) -> Result<DustTransfer, Error> {
+        // The `asset` parameter is repeated once per asset
+        let parameters: Vec<(String, String)> = assets
+            .iter()
+            .map(|asset| (String::from("asset"), asset.to_string()))
+            .collect();
+
+        let request: String = build_signed_request(parameters, self.recv_window)?;
+        self.post_signed(BinanceApi::Spot(Spot::DustTransfer), request)
+            .await
+    }
+
+    /// Create a user data stream listen key
+    ///
+    /// The key expires after 60 minutes unless kept alive with
+    /// [`BinanceClient::keepalive_listen_key`] (i.e., every 30 minutes).
+    ///
+    /// Requires an API key, but no signature.
+    pub async fn create_listen_key(&self) -> Result<String, Error> {
+        let key: ListenKey = self
+            .send_with_api_key(Method::POST, BinanceApi::Spot(Spot::UserDataStream), None)
+            .await?;
+        Ok(key.listen_key)
+    }
+
+    /// Extend the validity of a listen key by 60 minutes
+    ///
+    /// Requires an API key, but no signature.
+    pub async fn keepalive_listen_key(&self, listen_key: &str) -> Result<(), Error> {
+        let request: String =
+            build_request(vec![(String::from("listenKey"), listen_key.to_string())]);
+        let _: IgnoredAny = self
+            .send_with_api_key(
+                Method::PUT,
+                BinanceApi::Spot(Spot::UserDataStream),
+                Some(request),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Close a listen key
+    ///
+    /// Requires an API key, but no signature.
+    pub async fn close_listen_key(&self, listen_key: &str) -> Result<(), Error> {
+        let request: String =
+            build_request(vec![(String::from("listenKey"), listen_key.to_string())]);
+        let _: IgnoredAny = self
+            .send_with_api_key(
+                Method::DELETE,
+                BinanceApi::Spot(Spot::UserDataStream),
+                Some(request),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Warn (once) if an order is placed on mainnet without [`BinanceClientBuilder::confirm_live`].
+    ///
+    /// Returns `true` if the warning has been logged.
+    fn warn_unconfirmed_live(&self) -> bool {
+        if !self.live_guard || self.live_warned.swap(true, Ordering::Relaxed) {
+            return false;
+        }
+
+        tracing::warn!(
+            target: TRACING_TARGET,
+            endpoint = %self.host,
+            "placing orders on mainnet: call `BinanceClientBuilder::confirm_live` to silence this warning, or use the testnet endpoint"
+        );
+
+        true
+    }
+
+    /// Place a new order
+    ///
+    /// On mainnet, a warning is logged unless [`BinanceClientBuilder::confirm_live`] is set.
+    pub async fn place_order(&self, req: OrderRequest) -> Result<OrderResponse, Error> {
+        self.warn_unconfirmed_live();
+
+        let request: String = build_signed_request(req.into_parameters()?, self.recv_window)?;
+        self.post_signed(BinanceApi::Spot(Spot::Order), request)
+            .await
+    }
+
+    /// Cancel an existing order and place a new one, in a single request
+    ///
+    /// The symbol of `new_order` must match `symbol`, or [`Error::SymbolMismatch`] is returned.
+    ///
+    /// If one of the two operations fails, [`Error::CancelReplaceFailed`] is returned with the
+    /// outcome of both. In [`CancelReplaceMode::AllowFailure`] mode, the new order may have been
+    /// placed anyway: see [`CancelReplaceFailure::new_order`].
+    ///
+    /// On mainnet, a warning is logged unless [`BinanceClientBuilder::confirm_live`] is set.
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#cancel-an-existing-order-and-send-a-new-order-trade>
+    ///
+    /// [`CancelReplaceFailure::new_order`]: crate::response::CancelReplaceFailure::new_order
+    pub async fn cancel_replace(
+        &self,
+        symbol: &str,
+        cancel_order_id: u64,
+        new_order: OrderRequest,
+        mode: CancelReplaceMode,
+    ) -> Result<CancelReplaceResponse, Error> {
+        if normalize_symbol(&new_order.symbol) != normalize_symbol(symbol) {
+            return Err(Error::SymbolMismatch {
+                expected: symbol.to_string(),
+                found: new_order.symbol,
+            });
+        }
+
+        self.warn_unconfirmed_live();
+
+        let mut parameters: Vec<(String, String)> = new_order.into_parameters()?;
+        parameters.push((String::from("cancelReplaceMode"), mode.as_str().to_string()));
+        parameters.push((String::from("cancelOrderId"), cancel_order_id.to_string()));
+
+        let request: String = build_signed_request(parameters, self.recv_window)?;
+        self.post_signed(BinanceApi::Spot(Spot::OrderCancelReplace), request)
+            .await
+    }
+
+    /// Get the **bitcoin** pairs, fetched once and cached.
+    ///
+    /// Cancel-safe: if the future is dropped during the initialization, the cache is left empty
+    /// and the next call retries it.
     async fn bitcoin_pairs(&self) -> Result<&Vec<Symbol>, Error> {
         self.bitcoin_pairs
             .get_or_try_init(|| async {
@@ -275,20 +838,195 @@ impl BinanceClient {
             .await
     }
 
-    /// Get trades for a specific symbol (i.e., "BTCUSDT")
+    /// Get the metadata of an asset (i.e., `BTC`)
+    ///
+    /// Assets are extracted from the exchange information, fetched once and cached.
+    pub async fn asset_info(&self, code: &str) -> Result<Option<AssetInfo>, Error> {
+        let assets: &HashMap<String, AssetInfo> = self
+            .assets
+            .get_or_try_init(|| async {
+                let info: ExchangeInformation = self.exchange_info().await?;
+                Ok::<_, Error>(assets_from_symbols(&info.symbols))
+            })
+            .await?;
+
+        Ok(assets.get(&normalize_symbol(code)).cloned())
+    }
+
+    /// Get trades for a specific symbol (i.e., "BTCUSDT"), sorted by time
     pub async fn trade_history_for_pair<S>(&self, symbol: S) -> Result<Vec<Trade>, Error>
     where
         S: Into<String>,
     {
-        let mut parameters = BTreeMap::new();
-        parameters.insert(String::from("symbol"), symbol.into());
+        let parameters = vec![(String::from("symbol"), normalize_symbol(&symbol.into()))];
 
         // Build signed request
         let request: String = build_signed_request(parameters, self.recv_window)?;
 
         // Get signed request
-        self.get_signed(BinanceApi::Spot(Spot::MyTrades), Some(request))
-            .await
+        let trades: Vec<Trade> = self
+            .get_signed(BinanceApi::Spot(Spot::MyTrades), Some(request))
+            .await?;
+
+        Ok(sort_and_dedup_trades(trades))
+    }
+
+    /// Get the margin trades of a symbol (i.e., "BTCUSDT"), sorted by time
+    ///
+    /// Set `is_isolated` to get the trades of the isolated margin account of the symbol, instead
+    /// of the cross margin account.
+    ///
+    /// Pages by `fromId` until all the trades are loaded.
+    ///
+    /// <https://developers.binance.com/docs/margin_trading/trade/Query-Margin-Account-Trade-List>
+    pub async fn margin_trade_history<S>(
+        &self,
+        symbol: S,
+        is_isolated: bool,
+    ) -> Result<Vec<MarginTrade>, Error>
+    where
+        S: Into<String>,
+    {
+        let symbol: String = normalize_symbol(&symbol.into());
+
+        self.with_deadline(async {
+            let mut from_id: Option<u64> = None;
+            let mut output: Vec<MarginTrade> = Vec::new();
+
+            loop {
+                let mut parameters = vec![
+                    (String::from("symbol"), symbol.clone()),
+                    (
+                        String::from("isIsolated"),
+                        String::from(if is_isolated { "TRUE" } else { "FALSE" }),
+                    ),
+                    (String::from("limit"), MARGIN_TRADES_MAX_LIMIT.to_string()),
+                ];
+
+                if let Some(from_id) = from_id {
+                    parameters.push((String::from("fromId"), from_id.to_string()));
+                }
+
+                let request: String = build_signed_request(parameters, self.recv_window)?;
+                let batch: Vec<MarginTrade> = self
+                    .get_signed(BinanceApi::Spot(Spot::MarginMyTrades), Some(request))
+                    .await?;
+
+                let batch_len: usize = batch.len();
+                let next_candidate = next_from_id_after_batch(from_id.unwrap_or(0), &batch);
+                output.extend(batch);
+
+                if batch_len < MARGIN_TRADES_MAX_LIMIT {
+                    break;
+                }
+
+                match next_candidate {
+                    Some(next) => from_id = Some(next),
+                    None => break,
+                }
+            }
+
+            Ok(sort_and_dedup_trades(output))
+        })
+        .await
+    }
+
+    /// Get the trades of an order, sorted by time
+    ///
+    /// Pages by `fromId` if the order has more fills than fit in one response.
+    pub async fn trades_for_order<S>(&self, symbol: S, order_id: u64) -> Result<Vec<Trade>, Error>
+    where
+        S: Into<String>,
+    {
+        let symbol: String = normalize_symbol(&symbol.into());
+
+        self.with_deadline(async {
+            let mut from_id: Option<u64> = None;
+            let mut output: Vec<Trade> = Vec::new();
+
+            loop {
+                let mut parameters = vec![
+                    (String::from("symbol"), symbol.clone()),
+                    (String::from("orderId"), order_id.to_string()),
+                    (String::from("limit"), MY_TRADES_MAX_LIMIT.to_string()),
+                ];
+
+                if let Some(from_id) = from_id {
+                    parameters.push((String::from("fromId"), from_id.to_string()));
+                }
+
+                let request: String = build_signed_request(parameters, self.recv_window)?;
+                let batch: Vec<Trade> = self
+                    .get_signed(BinanceApi::Spot(Spot::MyTrades), Some(request))
+                    .await?;
+
+                let batch_len: usize = batch.len();
+                let next_candidate = next_from_id_after_batch(from_id.unwrap_or(0), &batch);
+                output.extend(batch.into_iter().filter(|trade| trade.order_id == order_id));
+
+                if batch_len < MY_TRADES_MAX_LIMIT {
+                    break;
+                }
+
+                match next_candidate {
+                    Some(next) => from_id = Some(next),
+                    None => break,
+                }
+            }
+
+            Ok(sort_and_dedup_trades(output))
+        })
+        .await
+    }
+
+    /// Get all the trades of a symbol (i.e., "BTCUSDT") since `start_time`, sorted by time
+    ///
+    /// The first page is requested by `startTime`, the following ones by `fromId` until no more
+    /// trades are returned. Binance limits a `startTime` query to the following 24 hours: if no
+    /// trades are found there, all the trades are loaded from the first one and filtered by time.
+    pub async fn all_trades_since<S>(
+        &self,
+        symbol: S,
+        start_time: DateTime<Utc>,
+    ) -> Result<Vec<Trade>, Error>
+    where
+        S: Into<String>,
+    {
+        let symbol: String = normalize_symbol(&symbol.into());
+
+        self.with_deadline(async {
+            let parameters = vec![
+                (String::from("symbol"), symbol.clone()),
+                (
+                    String::from("startTime"),
+                    start_time.timestamp_millis().to_string(),
+                ),
+                (String::from("limit"), MY_TRADES_MAX_LIMIT.to_string()),
+            ];
+
+            let request: String = build_signed_request(parameters, self.recv_window)?;
+            let mut trades: Vec<Trade> = self
+                .get_signed(BinanceApi::Spot(Spot::MyTrades), Some(request))
+                .await?;
+
+            let Some(from_id) = next_from_id_after_batch(0, &trades) else {
+                let trades: Vec<Trade> = self
+                    .trade_history_for_pair_from_id_paginated(symbol, 0)
+                    .await?;
+                return Ok(trades
+                    .into_iter()
+                    .filter(|trade| trade.time >= start_time)
+                    .collect());
+            };
+
+            let next: Vec<Trade> = self
+                .trade_history_for_pair_from_id_paginated(symbol, from_id)
+                .await?;
+            trades.extend(next);
+
+            Ok(sort_and_dedup_trades(trades))
+        })
+        .await
     }
 
     async fn trade_history_for_pair_with_options<S>(
@@ -300,10 +1038,11 @@ impl BinanceClient {
     where
         S: Into<String>,
     {
-        let mut parameters = BTreeMap::new();
-        parameters.insert(String::from("symbol"), symbol.into());
-        parameters.insert(String::from("fromId"), from_id.to_string());
-        parameters.insert(String::from("limit"), limit.to_string());
+        let parameters = vec![
+            (String::from("symbol"), normalize_symbol(&symbol.into())),
+            (String::from("fromId"), from_id.to_string()),
+            (String::from("limit"), limit.to_string()),
+        ];
 
         let request: String = build_signed_request(parameters, self.recv_window)?;
         self.get_signed(BinanceApi::Spot(Spot::MyTrades), Some(request))
@@ -334,24 +1073,119 @@ impl BinanceClient {
 
             let batch_len: usize = batch.len();
 
-            if batch_len == 0 {
-                break;
-            }
+            if batch_len == 0 {
+                break;
+            }
+
+            let next_candidate = next_from_id_after_batch(next_from_id, &batch);
+            output.extend(batch);
+
+            if batch_len < MY_TRADES_MAX_LIMIT {
+                break;
+            }
+
+            let Some(next_from_id_candidate) = next_candidate else {
+                break;
+            };
+            next_from_id = next_from_id_candidate;
+        }
+
+        Ok(sort_and_dedup_trades(output))
+    }
+
+    /// Get the orders of a symbol (i.e., "BTCUSDT"), including the canceled and expired ones
+    ///
+    /// Returns a single page: orders from `order_id` onwards if set, otherwise the most recent
+    /// ones (or the ones within the time range). Use [`BinanceClient::all_orders_from_id`] to get
+    /// all the pages.
+    ///
+    /// `limit` defaults to 500 (max 1000).
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/account-endpoints#all-orders-user_data>
+    pub async fn all_orders<S>(
+        &self,
+        symbol: S,
+        order_id: Option<u64>,
+        start_time: Option<DateTime<Utc>>,
+        end_time: Option<DateTime<Utc>>,
+        limit: Option<u16>,
+    ) -> Result<Vec<Order>, Error>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = vec![(String::from("symbol"), normalize_symbol(&symbol.into()))];
+
+        if let Some(order_id) = order_id {
+            parameters.push((String::from("orderId"), order_id.to_string()));
+        }
 
-            let next_candidate = next_from_id_after_batch(next_from_id, &batch);
-            output.extend(batch);
+        if let Some(start_time) = start_time {
+            parameters.push((
+                String::from("startTime"),
+                start_time.timestamp_millis().to_string(),
+            ));
+        }
 
-            if batch_len < MY_TRADES_MAX_LIMIT {
-                break;
-            }
+        if let Some(end_time) = end_time {
+            parameters.push((
+                String::from("endTime"),
+                end_time.timestamp_millis().to_string(),
+            ));
+        }
 
-            let Some(next_from_id_candidate) = next_candidate else {
-                break;
-            };
-            next_from_id = next_from_id_candidate;
+        if let Some(limit) = limit {
+            parameters.push((String::from("limit"), limit.to_string()));
         }
 
-        Ok(output)
+        let request: String = build_signed_request(parameters, self.recv_window)?;
+        self.get_signed(BinanceApi::Spot(Spot::AllOrders), Some(request))
+            .await
+    }
+
+    /// Get all the orders of a symbol (i.e., "BTCUSDT") from `order_id` onwards, sorted by ID
+    ///
+    /// Pages are requested by `orderId` until a partial page is returned. Use `0` to get the
+    /// whole order history.
+    pub async fn all_orders_from_id<S>(&self, symbol: S, order_id: u64) -> Result<Vec<Order>, Error>
+    where
+        S: Into<String>,
+    {
+        let symbol: String = normalize_symbol(&symbol.into());
+
+        self.with_deadline(async {
+            let mut next_order_id: u64 = order_id;
+            let mut output: Vec<Order> = Vec::new();
+
+            loop {
+                let batch: Vec<Order> = self
+                    .all_orders(
+                        symbol.clone(),
+                        Some(next_order_id),
+                        None,
+                        None,
+                        Some(ALL_ORDERS_MAX_LIMIT as u16),
+                    )
+                    .await?;
+
+                let batch_len: usize = batch.len();
+                let max_id: Option<u64> = batch.iter().map(|order| order.order_id).max();
+                output.extend(batch);
+
+                if batch_len < ALL_ORDERS_MAX_LIMIT {
+                    break;
+                }
+
+                match max_id {
+                    Some(max_id) if max_id >= next_order_id => next_order_id = max_id + 1,
+                    _ => break,
+                }
+            }
+
+            output.sort_by_key(|order| order.order_id);
+            output.dedup_by_key(|order| order.order_id);
+            Ok(output)
+        })
+        .await
     }
 
     /// Simple incremental sync for **bitcoin pairs only**.
@@ -365,31 +1199,53 @@ impl BinanceClient {
     ///
     /// Trades for fully closed symbols (now at zero balance) are still synced as long as the
     /// symbol is already present in `cursor`.
+    ///
+    /// Up to [`BinanceClientBuilder::concurrency`] pairs are fetched concurrently. On error, the
+    /// `cursor` is left untouched: the next call fetches the same trades again.
     pub async fn trade_history_bitcoin_incremental(
         &self,
         account: &AccountInformation,
         cursor: &mut HashMap<String, u64>,
     ) -> Result<HashMap<String, Vec<Trade>>, Error> {
-        let btc_pairs: &Vec<Symbol> = self.bitcoin_pairs().await?;
-        let symbols_to_sync: Vec<String> = bitcoin_symbols_to_sync(btc_pairs, account, cursor);
-
-        let mut output = HashMap::with_capacity(symbols_to_sync.len());
-
-        for symbol in symbols_to_sync {
-            let from_id: u64 = cursor.get(&symbol).copied().unwrap_or(0).saturating_add(1);
-
-            let trades: Vec<Trade> = self
-                .trade_history_for_pair_from_id_paginated(symbol.clone(), from_id)
-                .await?;
-
-            if let Some(max_trade_id) = trades.iter().map(|trade| trade.id).max() {
-                cursor.insert(symbol.clone(), max_trade_id);
+        self.with_deadline(async {
+            let btc_pairs: &Vec<Symbol> = self.bitcoin_pairs().await?;
+            let symbols_to_sync: Vec<String> = bitcoin_symbols_to_sync(btc_pairs, account, cursor);
+
+            let requests: Vec<(String, u64)> = symbols_to_sync
+                .into_iter()
+                .map(|symbol| {
+                    let from_id: u64 = cursor.get(&symbol).copied().unwrap_or(0).saturating_add(1);
+                    (symbol, from_id)
+                })
+                .collect();
+
+            let results: Vec<(String, Result<Vec<Trade>, Error>)> = stream::iter(requests)
+                .map(|(symbol, from_id)| async move {
+                    let trades = self
+                        .trade_history_for_pair_from_id_paginated(symbol.clone(), from_id)
+                        .await;
+                    (symbol, trades)
+                })
+                .buffer_unordered(self.concurrency)
+                .collect()
+                .await;
+
+            let mut output = HashMap::with_capacity(results.len());
+
+            for (symbol, trades) in results {
+                output.insert(symbol, trades?);
             }
 
-            output.insert(symbol, trades);
-        }
+            // Only advance the cursor once every pair succeeded, so that no trade is skipped
+            for (symbol, trades) in output.iter() {
+                if let Some(max_trade_id) = trades.iter().map(|trade| trade.id).max() {
+                    cursor.insert(symbol.clone(), max_trade_id);
+                }
+            }
 
-        Ok(output)
+            Ok(output)
+        })
+        .await
     }
 
     /// Get trades for BTC pairs related to assets with non-zero balance.
@@ -406,30 +1262,49 @@ impl BinanceClient {
 }
 
 #[inline]
-fn used_weight_1m(headers: &HeaderMap) -> u32 {
+fn used_weight(headers: &HeaderMap, weight_limit: &WeightLimit) -> u32 {
     headers
-        .get("X-MBX-USED-WEIGHT-1M")
+        .get(weight_limit.header_name())
         .and_then(|v| v.to_str().ok())
         .and_then(|s| s.parse().ok())
         .unwrap_or(0)
 }
 
 #[inline]
-fn throttle_delay(used_weight: u32, request_weight: u32) -> Option<Duration> {
+fn throttle_delay(
+    used_weight: u32,
+    request_weight: u32,
+    weight_limit: &WeightLimit,
+) -> Option<Duration> {
     let required_weight: u32 = used_weight.saturating_add(request_weight);
-    if required_weight <= MAX_WEIGHT_PER_MIN {
+    if required_weight <= weight_limit.limit {
         return None;
     }
 
-    let deficit: u32 = required_weight - MAX_WEIGHT_PER_MIN;
+    let deficit: u32 = required_weight - weight_limit.limit;
 
-    // Example: deficit=100, max=6000 -> sleep for 100/6000 minute
-    let sleep_ms: u64 = (deficit as f64 / MAX_WEIGHT_PER_MIN as f64 * 60_000.0) as u64;
+    // Example: deficit=100, max=6000 per minute -> sleep for 100/6000 minute
+    let window_ms: f64 = weight_limit.window().as_millis() as f64;
+    let sleep_ms: u64 = (deficit as f64 / weight_limit.limit.max(1) as f64 * window_ms) as u64;
     let sleep_ms: u64 = sleep_ms.max(200);
 
     Some(Duration::from_millis(sleep_ms))
 }
 
+/// Drift between the server time and the local time, at the midpoint of the round trip
+#[inline]
+fn clock_drift(sent_at: i64, server_time: i64, received_at: i64) -> i64 {
+    let local_time: i64 = sent_at + (received_at - sent_at) / 2;
+    server_time - local_time
+}
+
+/// Check if the request can be safely retried
+fn is_idempotent(req: &RequestBuilder) -> bool {
+    req.try_clone()
+        .and_then(|req| req.build().ok())
+        .is_some_and(|req| req.method() == Method::GET)
+}
+
 #[inline]
 fn retry_after_ms(headers: &HeaderMap) -> Option<Duration> {
     headers
@@ -459,6 +1334,34 @@ fn filter_btc_pairs_by_assets(btc_pairs: &[Symbol], assets: &HashSet<String>) ->
         .collect()
 }
 
+fn assets_from_symbols(symbols: &[Symbol]) -> HashMap<String, AssetInfo> {
+    let mut assets: HashMap<String, AssetInfo> = HashMap::new();
+
+    for symbol in symbols {
+        for (code, decimals) in [
+            (&symbol.base_asset, symbol.base_asset_precision),
+            (&symbol.quote_asset, symbol.quote_precision),
+        ] {
+            assets.entry(code.clone()).or_insert_with(|| AssetInfo {
+                code: code.clone(),
+                decimals,
+            });
+        }
+    }
+
+    assets
+}
+
+fn non_zero_balances(balances: Vec<Balance>) -> Vec<(String, f64)> {
+    balances
+        .into_iter()
+        .filter_map(|balance| {
+            let total: f64 = balance.total();
+            (total != 0.0).then_some((balance.asset, total))
+        })
+        .collect()
+}
+
 fn bitcoin_symbols_to_sync(
     btc_pairs: &[Symbol],
     account: &AccountInformation,
@@ -484,8 +1387,53 @@ fn bitcoin_symbols_to_sync(
     symbols
 }
 
-fn next_from_id_after_batch(current_from_id: u64, batch: &[Trade]) -> Option<u64> {
-    let max_id = batch.iter().map(|trade| trade.id).max()?;
+/// Trade paged by `fromId`
+trait PagedTrade {
+    fn id(&self) -> u64;
+
+    fn time(&self) -> DateTime<Utc>;
+}
+
+impl PagedTrade for Trade {
+    #[inline]
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    #[inline]
+    fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
+
+impl PagedTrade for MarginTrade {
+    #[inline]
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    #[inline]
+    fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
+
+/// Remove the trades with a duplicated ID and sort them by time (then ID), ascending
+fn sort_and_dedup_trades<T>(mut trades: Vec<T>) -> Vec<T>
+where
+    T: PagedTrade,
+{
+    let mut seen: HashSet<u64> = HashSet::with_capacity(trades.len());
+    trades.retain(|trade| seen.insert(trade.id()));
+    trades.sort_by(|a, b| a.time().cmp(&b.time()).then(a.id().cmp(&b.id())));
+    trades
+}
+
+fn next_from_id_after_batch<T>(current_from_id: u64, batch: &[T]) -> Option<u64>
+where
+    T: PagedTrade,
+{
+    let max_id = batch.iter().map(|trade| trade.id()).max()?;
     if max_id < current_from_id {
         return None;
     }
@@ -496,43 +1444,1145 @@ fn next_from_id_after_batch(current_from_id: u64, batch: &[Trade]) -> Option<u64
 #[cfg(test)]
 mod tests {
     use chrono::DateTime;
+    use common::amount::{Fee, Price, Quantity};
+    use common::cache::CacheConfig;
+    use common::interceptor::ServerError;
     use reqwest::header::{HeaderMap, HeaderValue};
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     use super::*;
-    use crate::response::{Balance, Symbol};
+    use crate::builder::{BinanceEndpoint, BinanceEndpointType, RateLimitInterval};
+    use crate::request::OrderAmount;
+    use crate::response::{
+        Balance, CancelReplaceOutcome, CancelReplaceResult, OrderSide, OrderStatus, OrderType,
+        Symbol,
+    };
+
+    const EXCHANGE_INFO: &str = r#"{
+    "timezone": "UTC",
+    "serverTime": 1565246363776,
+    "rateLimits": [],
+    "symbols": [
+        {
+            "symbol": "ETHBTC",
+            "status": "TRADING",
+            "baseAsset": "ETH",
+            "baseAssetPrecision": 8,
+            "quoteAsset": "BTC",
+            "quotePrecision": 8,
+            "orderTypes": ["LIMIT", "MARKET"],
+            "icebergAllowed": true,
+            "isSpotTradingAllowed": true,
+            "isMarginTradingAllowed": false
+        },
+        {
+            "symbol": "ETHUSDT",
+            "status": "TRADING",
+            "baseAsset": "ETH",
+            "baseAssetPrecision": 8,
+            "quoteAsset": "USDT",
+            "quotePrecision": 8,
+            "orderTypes": ["LIMIT", "MARKET"],
+            "icebergAllowed": true,
+            "isSpotTradingAllowed": true,
+            "isMarginTradingAllowed": false
+        }
+    ]
+}"#;
+
+    async fn mock_client(server: &MockServer) -> BinanceClient {
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_warn_unconfirmed_live_once() {
+        let client = BinanceClient::builder().build().unwrap();
+        assert!(client.warn_unconfirmed_live());
+        assert!(!client.warn_unconfirmed_live());
+
+        // Shared across clones
+        assert!(!client.clone().warn_unconfirmed_live());
+
+        let client = BinanceClient::builder().confirm_live().build().unwrap();
+        assert!(!client.warn_unconfirmed_live());
+
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::from_type(BinanceEndpointType::Testnet))
+            .build()
+            .unwrap();
+        assert!(!client.warn_unconfirmed_live());
+    }
+
+    #[tokio::test]
+    async fn test_asset_info_is_cached() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(EXCHANGE_INFO))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).await;
+
+        let eth = client.asset_info("eth").await.unwrap().unwrap();
+        assert_eq!(eth.code, "ETH");
+        assert_eq!(eth.decimals, 8);
+
+        let usdt = client.asset_info("USDT").await.unwrap().unwrap();
+        assert_eq!(usdt.code, "USDT");
+
+        assert!(client.asset_info("LTC").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exchange_info_cache() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(EXCHANGE_INFO))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .cache(CacheConfig::new().ttl("exchange_info", Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        client.exchange_info().await.unwrap();
+        client.exchange_info().await.unwrap();
+
+        client.clear_cache();
+        client.exchange_info().await.unwrap();
+    }
+
+    #[test]
+    fn test_clock_drift() {
+        // Server ahead
+        assert_eq!(clock_drift(1_000, 1_600, 1_200), 500);
+        // Server behind
+        assert_eq!(clock_drift(1_000, 900, 1_200), -200);
+    }
+
+    #[tokio::test]
+    async fn test_sync_time() {
+        let server = MockServer::start().await;
+
+        // Server one hour ahead
+        let server_time: i64 = Utc::now().timestamp_millis() + 3_600_000;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(format!(r#"{{"serverTime":{server_time}}}"#)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).await;
+        assert_eq!(client.clock_drift(), None);
+
+        let drift: i64 = client.sync_time().await.unwrap();
+        assert!((3_500_000..=3_600_000).contains(&drift));
+        assert_eq!(client.clock_drift(), Some(drift));
+    }
+
+    #[tokio::test]
+    async fn test_overall_deadline_exceeded() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(EXCHANGE_INFO)
+                    .set_delay(Duration::from_secs(2)),
+            )
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .overall_deadline(Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let err = client.exchange_info().await.unwrap_err();
+        assert!(matches!(err, Error::Deadline(deadline) if deadline == Duration::from_millis(100)));
+    }
+
+    #[tokio::test]
+    async fn test_trade_history_bitcoin_keeps_cursor_on_error() {
+        let server = MockServer::start().await;
+
+        let symbol = |symbol: &str, base: &str| -> String {
+            format!(
+                r#"{{"symbol":"{symbol}","status":"TRADING","baseAsset":"{base}","baseAssetPrecision":8,"quoteAsset":"BTC","quotePrecision":8,"orderTypes":["LIMIT"],"icebergAllowed":true,"isSpotTradingAllowed":true,"isMarginTradingAllowed":false}}"#
+            )
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"{{"timezone":"UTC","serverTime":1565246363776,"rateLimits":[],"symbols":[{},{}]}}"#,
+                symbol("ETHBTC", "ETH"),
+                symbol("LTCBTC", "LTC")
+            )))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(query_param("symbol", "ETHBTC"))
+            .and(query_param("fromId", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(my_trades(1..=2)))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(query_param("symbol", "ETHBTC"))
+            .and(query_param("fromId", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(query_param("symbol", "LTCBTC"))
+            .respond_with(
+                ResponseTemplate::new(400)
+                    .set_body_string(r#"{"code":-1121,"msg":"Invalid symbol."}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .endpoint(BinanceEndpoint::new(url))
+            .concurrency(2)
+            .build()
+            .unwrap();
+
+        let account = make_account(vec![
+            Balance {
+                asset: "ETH".to_string(),
+                free: 1.0,
+                locked: 0.0,
+            },
+            Balance {
+                asset: "LTC".to_string(),
+                free: 1.0,
+                locked: 0.0,
+            },
+        ]);
+
+        let mut cursor = HashMap::new();
+        assert!(
+            client
+                .trade_history_bitcoin_incremental(&account, &mut cursor)
+                .await
+                .is_err()
+        );
+        assert!(cursor.is_empty());
+    }
+
+    fn my_trades(ids: std::ops::RangeInclusive<u64>) -> String {
+        let trades: Vec<String> = ids
+            .map(|id| {
+                format!(
+                    r#"{{"symbol":"BTCUSDT","id":{id},"orderId":{id},"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","commission":"10.10000000","commissionAsset":"BNB","time":{},"isBuyer":true,"isMaker":false,"isBestMatch":true}}"#,
+                    1499865549590 + id
+                )
+            })
+            .collect();
+        format!("[{}]", trades.join(","))
+    }
+
+    #[tokio::test]
+    async fn test_all_trades_since_paginates_by_from_id() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .and(query_param("startTime", "1499865549000"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(my_trades(1..=500)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(query_param("fromId", "501"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(my_trades(501..=1000)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(query_param("fromId", "1001"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(my_trades(1001..=1003)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let start_time = DateTime::from_timestamp_millis(1499865549000).unwrap();
+        let trades = client
+            .all_trades_since("btcusdt", start_time)
+            .await
+            .unwrap();
+        assert_eq!(trades.len(), 1003);
+        assert_eq!(trades.first().unwrap().id, 1);
+        assert_eq!(trades.last().unwrap().id, 1003);
+    }
+
+    #[tokio::test]
+    async fn test_margin_trade_history() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/sapi/v1/margin/myTrades"))
+            .and(query_param("symbol", "BNBBTC"))
+            .and(query_param("isIsolated", "TRUE"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[{"commission":"0.00006000","commissionAsset":"BTC","id":35,"isBestMatch":true,"isBuyer":true,"isMaker":false,"orderId":39325,"price":"0.02000000","qty":"1.00000000","symbol":"BNBBTC","isIsolated":true,"time":1561973357172},{"commission":"0.00006000","commissionAsset":"BTC","id":34,"isBestMatch":true,"isBuyer":false,"isMaker":false,"orderId":39324,"price":"0.02000000","qty":"3.00000000","symbol":"BNBBTC","isIsolated":true,"time":1561973357171}]"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let trades = client.margin_trade_history("bnbbtc", true).await.unwrap();
+        let ids: Vec<u64> = trades.iter().map(|trade| trade.id).collect();
+        assert_eq!(ids, vec![34, 35]);
+        assert!(trades.iter().all(|trade| trade.is_isolated));
+    }
+
+    fn margin_trades(ids: std::ops::RangeInclusive<u64>) -> String {
+        let trades: Vec<String> = ids
+            .map(|id| {
+                format!(
+                    r#"{{"commission":"0.00006000","commissionAsset":"BTC","id":{id},"isBestMatch":true,"isBuyer":true,"isMaker":false,"orderId":{id},"price":"0.02000000","qty":"1.00000000","symbol":"BNBBTC","isIsolated":false,"time":{}}}"#,
+                    1561973357000 + id
+                )
+            })
+            .collect();
+        format!("[{}]", trades.join(","))
+    }
+
+    #[tokio::test]
+    async fn test_margin_trade_history_paginates_by_from_id() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/sapi/v1/margin/myTrades"))
+            .and(query_param("symbol", "BNBBTC"))
+            .and(query_param("isIsolated", "FALSE"))
+            .and(query_param("limit", "1000"))
+            .and(|req: &wiremock::Request| !req.url.query_pairs().any(|(k, _)| k == "fromId"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(margin_trades(1..=1000)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/sapi/v1/margin/myTrades"))
+            .and(query_param("fromId", "1001"))
+            .and(query_param("limit", "1000"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(margin_trades(1000..=1002)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let trades = client.margin_trade_history("bnbbtc", false).await.unwrap();
+        assert_eq!(trades.len(), 1002);
+        assert_eq!(trades.first().unwrap().id, 1);
+        assert_eq!(trades.last().unwrap().id, 1002);
+    }
+
+    #[tokio::test]
+    async fn test_trades_for_order() {
+        let server = MockServer::start().await;
+
+        let body: String = my_trades(7..=9).replace(r#""orderId":8"#, r#""orderId":7"#);
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/myTrades"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .and(query_param("orderId", "7"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let trades = client.trades_for_order("btcusdt", 7).await.unwrap();
+        let ids: Vec<u64> = trades.iter().map(|trade| trade.id).collect();
+        assert_eq!(ids, vec![7, 8]);
+        assert!(trades.iter().all(|trade| trade.order_id == 7));
+    }
+
+    fn all_orders(ids: std::ops::RangeInclusive<u64>) -> String {
+        let orders: Vec<String> = ids
+            .map(|id| {
+                format!(
+                    r#"{{"symbol":"BTCUSDT","orderId":{id},"orderListId":-1,"clientOrderId":"c{id}","price":"0.10000000","origQty":"1.00000000","executedQty":"0.00000000","cummulativeQuoteQty":"0.00000000","status":"CANCELED","timeInForce":"GTC","type":"LIMIT","side":"BUY","stopPrice":"0.00000000","icebergQty":"0.00000000","time":1499827319559,"updateTime":1499827319559,"isWorking":true,"origQuoteOrderQty":"0.00000000","workingTime":1499827319559,"selfTradePreventionMode":"NONE"}}"#
+                )
+            })
+            .collect();
+        format!("[{}]", orders.join(","))
+    }
+
+    #[tokio::test]
+    async fn test_all_orders_from_id_paginates_by_order_id() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/allOrders"))
+            .and(query_param("orderId", "1"))
+            .and(query_param("limit", "1000"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(all_orders(1..=1000)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/allOrders"))
+            .and(query_param("orderId", "1001"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(all_orders(1001..=1002)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let orders = client.all_orders_from_id("btcusdt", 1).await.unwrap();
+        assert_eq!(orders.len(), 1002);
+        assert_eq!(orders[0].status, OrderStatus::Canceled);
+        assert_eq!(orders[0].price, Price(0.1));
+        assert_eq!(orders[0].orig_qty, Quantity(1.0));
+        assert_eq!(orders.last().unwrap().order_id, 1002);
+    }
+
+    #[tokio::test]
+    async fn test_ping() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/ping"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).await;
+        client.ping().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_interceptors() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/ping"))
+            .and(header("X-Request-Id", "req-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let responses: Arc<AtomicU32> = Arc::new(AtomicU32::new(0));
+        let counter: Arc<AtomicU32> = responses.clone();
+        let interceptors = Interceptors::new()
+            .on_request(|request| request.header("X-Request-Id", "req-1"))
+            .on_response(move |response| {
+                assert!(response.status().is_success());
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .interceptors(interceptors)
+            .build()
+            .unwrap();
+
+        client.ping().await.unwrap();
+        client.ping().await.unwrap();
+        assert_eq!(responses.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_default_headers() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/ping"))
+            .and(header("X-Gateway-Token", "token"))
+            .and(header("User-Agent", USER_AGENT_NAME))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Gateway-Token", HeaderValue::from_static("token"));
+        headers.insert("User-Agent", HeaderValue::from_static("gateway"));
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        client.ping().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_server_error_retried_for_get() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(EXCHANGE_INFO))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).await;
+        let info = client.exchange_info().await.unwrap();
+        assert_eq!(info.symbols.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_server_error_not_retried_for_post() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .respond_with(ResponseTemplate::new(502).set_body_string("bad gateway"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let req = OrderRequest::market(
+            "BTCUSDT",
+            OrderSide::Buy,
+            OrderAmount::Base(Quantity(0.001)),
+        );
+        let err = client.place_order(req).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ServerError(ServerError { status: 502, ref body }) if body == "bad gateway"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_request_does_not_poison_cache() {
+        let server = MockServer::start().await;
+
+        // First request hangs, the following ones reply immediately
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(EXCHANGE_INFO)
+                    .set_delay(Duration::from_secs(30)),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(EXCHANGE_INFO))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).await;
+
+        // Drop the future mid-flight
+        let res = time::timeout(Duration::from_millis(200), client.bitcoin_pairs()).await;
+        assert!(res.is_err());
+
+        // The next request must succeed
+        let pairs = client.bitcoin_pairs().await.unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].symbol, "ETHBTC");
+    }
+
+    #[tokio::test]
+    async fn test_historical_trades_sends_api_key_without_signature() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/historicalTrades"))
+            .and(header("x-mbx-apikey", "key"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .and(query_param("fromId", "28457"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[{"id":28457,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","time":1499865549590,"isBuyerMaker":true,"isBestMatch":true}]"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let trades = client
+            .historical_trades("BTCUSDT", Some(28457), None)
+            .await
+            .unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].id, 28457);
+
+        let requests = server.received_requests().await.unwrap();
+        let query: &str = requests[0].url.query().unwrap();
+        assert!(!query.contains("signature"));
+        assert!(!query.contains("timestamp"));
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_cache() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(EXCHANGE_INFO))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).await;
+        let cloned = client.clone();
+
+        // Warm the cache from the original client
+        client.bitcoin_pairs().await.unwrap();
+
+        // The clone must hit the cache
+        let pairs = cloned.bitcoin_pairs().await.unwrap();
+        assert_eq!(pairs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_signs_all_parameters() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/sapi/v1/capital/withdraw/apply"))
+            .and(header("x-mbx-apikey", "key"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"id":"7213fea8e94b4a5593d507237e5a555b"}"#),
+            )
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let req = WithdrawRequest::new("BTC", "bc1qaddress", 0.01).network("BTC");
+        let id = client.withdraw(req).await.unwrap();
+        assert_eq!(id, "7213fea8e94b4a5593d507237e5a555b");
+
+        let requests = server.received_requests().await.unwrap();
+        let query: &str = requests[0].url.query().unwrap();
+        let (payload, signature) = query.split_once("&signature=").unwrap();
+
+        assert!(payload.starts_with("coin=BTC&address=bc1qaddress&amount=0.01&network=BTC&"));
+        assert!(payload.contains("&recvWindow="));
+        assert!(payload.contains("&timestamp="));
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(payload.as_bytes());
+        assert_eq!(signature, hex::encode(mac.finalize().into_bytes()));
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_percent_encodes_and_signs_parameters() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/sapi/v1/capital/withdraw/apply"))
+            .and(query_param("addressTag", "memo & tag"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"id":"7213fea8e94b4a5593d507237e5a555b"}"#),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let req = WithdrawRequest::new("XRP", "raddress", 10.0).address_tag("memo & tag");
+        client.withdraw(req).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let query: &str = requests[0].url.query().unwrap();
+        let (payload, signature) = query.split_once("&signature=").unwrap();
+
+        assert!(payload.contains("&addressTag=memo+%26+tag&"));
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(payload.as_bytes());
+        assert_eq!(signature, hex::encode(mac.finalize().into_bytes()));
+    }
+
+    #[tokio::test]
+    async fn test_dust_transfer_repeats_asset_parameter() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/sapi/v1/asset/dust"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+    "totalServiceCharge": "0.02102542",
+    "totalTransfered": "1.05127099",
+    "transferResult": [
+        {
+            "amount": "0.03000000",
+            "fromAsset": "ETH",
+            "operateTime": 1563368549307,
+            "serviceChargeAmount": "0.00500000",
+            "tranId": 2970932918,
+            "transferedAmount": "0.25000000"
+        }
+    ]
+}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let dust = client.dust_transfer(&["ETH", "LTC"]).await.unwrap();
+        assert_eq!(dust.total_transferred, 1.05127099);
+        assert_eq!(dust.transfers.len(), 1);
+        assert_eq!(dust.transfers[0].from_asset, "ETH");
+
+        let requests = server.received_requests().await.unwrap();
+        let query: &str = requests[0].url.query().unwrap();
+        assert!(query.starts_with("asset=ETH&asset=LTC&"));
+    }
+
+    #[tokio::test]
+    async fn test_place_market_order_with_quote_quantity() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order"))
+            .and(query_param("quoteOrderQty", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+    "symbol": "BTCUSDT",
+    "orderId": 28,
+    "orderListId": -1,
+    "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+    "transactTime": 1507725176595,
+    "price": "0.00000000",
+    "origQty": "0.00170000",
+    "executedQty": "0.00170000",
+    "cummulativeQuoteQty": "99.98300000",
+    "status": "FILLED",
+    "timeInForce": "GTC",
+    "type": "MARKET",
+    "side": "BUY"
+}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let req = OrderRequest::market(
+            "BTCUSDT",
+            OrderSide::Buy,
+            OrderAmount::Quote(Quantity(100.0)),
+        );
+        let order = client.place_order(req).await.unwrap();
+        assert_eq!(order.order_id, 28);
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.cumulative_quote_qty, Quantity(99.983));
+
+        let requests = server.received_requests().await.unwrap();
+        let query: &str = requests[0].url.query().unwrap();
+        assert!(!query.contains("quantity="));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_replace() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order/cancelReplace"))
+            .and(query_param("symbol", "BTCUSDT"))
+            .and(query_param("cancelReplaceMode", "STOP_ON_FAILURE"))
+            .and(query_param("cancelOrderId", "9"))
+            .and(query_param("price", "0.02"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{
+    "cancelResult": "SUCCESS",
+    "newOrderResult": "SUCCESS",
+    "cancelResponse": {
+        "symbol": "BTCUSDT",
+        "origClientOrderId": "DnLo3vTAQcjha43lAZhZ0y",
+        "orderId": 9,
+        "orderListId": -1,
+        "clientOrderId": "osxN3JXAtJvKvCqGeMWMVR",
+        "transactTime": 1684804350068,
+        "price": "0.01000000",
+        "origQty": "0.000100",
+        "executedQty": "0.00000000",
+        "cummulativeQuoteQty": "0.00000000",
+        "status": "CANCELED",
+        "timeInForce": "GTC",
+        "type": "LIMIT",
+        "side": "SELL",
+        "selfTradePreventionMode": "NONE"
+    },
+    "newOrderResponse": {
+        "symbol": "BTCUSDT",
+        "orderId": 10,
+        "orderListId": -1,
+        "clientOrderId": "wOceeeOzNORyLiQfw7jd8S",
+        "transactTime": 1652928801803,
+        "price": "0.02000000",
+        "origQty": "0.040000",
+        "executedQty": "0.00000000",
+        "cummulativeQuoteQty": "0.00000000",
+        "status": "NEW",
+        "timeInForce": "GTC",
+        "type": "LIMIT",
+        "side": "BUY",
+        "workingTime": 1669277163808,
+        "fills": [],
+        "selfTradePreventionMode": "NONE"
+    }
+}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let new_order = OrderRequest::limit("BTCUSDT", OrderSide::Buy, Quantity(0.04), Price(0.02));
+        let res = client
+            .cancel_replace("BTCUSDT", 9, new_order, CancelReplaceMode::StopOnFailure)
+            .await
+            .unwrap();
+        assert_eq!(res.cancel_result, CancelReplaceResult::Success);
+        assert_eq!(res.new_order_result, CancelReplaceResult::Success);
+        assert_eq!(res.cancel_response.order_id, 9);
+        assert_eq!(res.cancel_response.status, OrderStatus::Canceled);
+        assert_eq!(res.new_order_response.order_id, 10);
+        assert_eq!(res.new_order_response.status, OrderStatus::New);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_replace_allow_failure() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v3/order/cancelReplace"))
+            .and(query_param("cancelReplaceMode", "ALLOW_FAILURE"))
+            .respond_with(ResponseTemplate::new(409).set_body_string(
+                r#"{
+    "code": -2021,
+    "msg": "Order cancel-replace partially failed.",
+    "data": {
+        "cancelResult": "FAILURE",
+        "newOrderResult": "SUCCESS",
+        "cancelResponse": {
+            "code": -2011,
+            "msg": "Unknown order sent."
+        },
+        "newOrderResponse": {
+            "symbol": "BTCUSDT",
+            "orderId": 11,
+            "orderListId": -1,
+            "clientOrderId": "pfojJMg6IMNDKuJqDxvoxN",
+            "transactTime": 1648540168818,
+            "price": "0.02000000",
+            "origQty": "0.040000",
+            "executedQty": "0.00000000",
+            "cummulativeQuoteQty": "0.00000000",
+            "status": "NEW",
+            "timeInForce": "GTC",
+            "type": "LIMIT",
+            "side": "BUY",
+            "fills": []
+        }
+    }
+}"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .auth(BinanceAuth::ApiKeys {
+                api_key: String::from("key"),
+                secret_key: String::from("secret"),
+            })
+            .build()
+            .unwrap();
+
+        let new_order = OrderRequest::limit("BTCUSDT", OrderSide::Buy, Quantity(0.04), Price(0.02));
+        let err = client
+            .cancel_replace("BTCUSDT", 9, new_order, CancelReplaceMode::AllowFailure)
+            .await
+            .unwrap_err();
+
+        let Error::CancelReplaceFailed {
+            status, code, data, ..
+        } = err
+        else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(status, 409);
+        assert_eq!(code, -2021);
+        assert_eq!(data.cancel_result, CancelReplaceResult::Failure);
+        assert!(matches!(
+            data.cancel_response,
+            CancelReplaceOutcome::Error(ref e) if e.code == -2011
+        ));
+        assert_eq!(data.new_order().map(|order| order.order_id), Some(11));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_replace_symbol_mismatch() {
+        let client = BinanceClient::new(BinanceAuth::None).unwrap();
+
+        let new_order = OrderRequest::limit("ETHUSDT", OrderSide::Buy, Quantity(0.04), Price(0.02));
+        let err = client
+            .cancel_replace("BTCUSDT", 9, new_order, CancelReplaceMode::StopOnFailure)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::SymbolMismatch { ref found, .. } if found == "ETHUSDT"));
+    }
+
+    #[tokio::test]
+    async fn test_quote_quantity_rejected_for_limit_order() {
+        let server = MockServer::start().await;
+        let client = mock_client(&server).await;
+
+        let mut req =
+            OrderRequest::limit("BTCUSDT", OrderSide::Buy, Quantity(0.0017), Price(60000.0));
+        req.amount = OrderAmount::Quote(Quantity(100.0));
+
+        let err = client.place_order(req).await.unwrap_err();
+        assert!(matches!(err, Error::QuoteQuantityRequiresMarketOrder));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
 
     #[test]
     fn test_used_weight_header_parsing() {
         let mut headers = HeaderMap::new();
         headers.insert("X-MBX-USED-WEIGHT-1M", HeaderValue::from_static("1234"));
 
-        assert_eq!(used_weight_1m(&headers), 1234);
+        assert_eq!(used_weight(&headers, &WeightLimit::default()), 1234);
+    }
+
+    #[test]
+    fn test_used_weight_header_of_interval() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-MBX-USED-WEIGHT-1M", HeaderValue::from_static("1234"));
+        headers.insert("X-MBX-USED-WEIGHT-1H", HeaderValue::from_static("5678"));
+
+        let weight_limit = WeightLimit::new(100_000, 1, RateLimitInterval::Hour);
+        assert_eq!(used_weight(&headers, &weight_limit), 5678);
     }
 
     #[test]
     fn test_used_weight_missing_header_defaults_to_zero() {
         let headers = HeaderMap::new();
-        assert_eq!(used_weight_1m(&headers), 0);
+        assert_eq!(used_weight(&headers, &WeightLimit::default()), 0);
     }
 
     #[test]
     fn test_throttle_delay_when_weight_is_available() {
-        let delay = throttle_delay(5_979, 20);
+        let delay = throttle_delay(5_979, 20, &WeightLimit::default());
         assert_eq!(delay, None);
     }
 
     #[test]
     fn test_throttle_delay_when_weight_is_missing() {
-        let delay = throttle_delay(5_990, 20);
+        let delay = throttle_delay(5_990, 20, &WeightLimit::default());
         assert_eq!(delay, Some(Duration::from_millis(200)));
     }
 
     #[test]
     fn test_throttle_delay_when_limit_is_already_exceeded() {
-        let delay = throttle_delay(6_100, 20);
+        let delay = throttle_delay(6_100, 20, &WeightLimit::default());
         assert_eq!(delay, Some(Duration::from_millis(1_200)));
     }
 
+    #[test]
+    fn test_throttle_delay_with_vip_limit() {
+        let weight_limit = WeightLimit::new(12_000, 1, RateLimitInterval::Minute);
+        assert_eq!(throttle_delay(6_100, 20, &weight_limit), None);
+        assert_eq!(
+            throttle_delay(12_100, 20, &weight_limit),
+            Some(Duration::from_millis(600))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_weight_limit_from_exchange_info() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/exchangeInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"timezone":"UTC","serverTime":1565246363776,"rateLimits":[{"rateLimitType":"REQUEST_WEIGHT","interval":"MINUTE","intervalNum":1,"limit":12000},{"rateLimitType":"ORDERS","interval":"SECOND","intervalNum":10,"limit":100}],"symbols":[]}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).await;
+        assert_eq!(client.current_weight_limit().limit, 6_000);
+
+        client.exchange_info().await.unwrap();
+        assert_eq!(client.current_weight_limit().limit, 12_000);
+
+        // An explicit limit wins
+        let url: Url = Url::parse(&server.uri()).unwrap();
+        let client = BinanceClient::builder()
+            .endpoint(BinanceEndpoint::new(url))
+            .weight_limit(WeightLimit::new(3_000, 1, RateLimitInterval::Minute))
+            .build()
+            .unwrap();
+        client.exchange_info().await.unwrap();
+        assert_eq!(client.current_weight_limit().limit, 3_000);
+    }
+
     #[test]
     fn test_retry_after_ms_parsing() {
         let mut headers = HeaderMap::new();
@@ -549,7 +2599,7 @@ mod tests {
             base_asset_precision: 8,
             quote_asset: quote.to_string(),
             quote_precision: 8,
-            order_types: vec!["LIMIT".to_string()],
+            order_types: vec![OrderType::Limit],
             iceberg_allowed: true,
             is_spot_trading_allowed: true,
             is_margin_trading_allowed: false,
@@ -565,7 +2615,10 @@ mod tests {
             can_trade: true,
             can_withdraw: true,
             can_deposit: true,
+            update_time: 0,
+            account_type: String::from("SPOT"),
             balances,
+            permissions: vec![String::from("SPOT")],
         }
     }
 
@@ -616,6 +2669,32 @@ mod tests {
         assert_eq!(symbols[1].symbol, "BTCEUR");
     }
 
+    #[test]
+    fn test_non_zero_balances() {
+        let balances = vec![
+            Balance {
+                asset: String::from("BTC"),
+                free: 0.5,
+                locked: 0.25,
+            },
+            Balance {
+                asset: String::from("LTC"),
+                free: 0.0,
+                locked: 0.0,
+            },
+            Balance {
+                asset: String::from("ETH"),
+                free: 0.0,
+                locked: 1.0,
+            },
+        ];
+
+        assert_eq!(
+            non_zero_balances(balances),
+            vec![(String::from("BTC"), 0.75), (String::from("ETH"), 1.0)]
+        );
+    }
+
     #[test]
     fn test_bitcoin_symbols_to_sync() {
         let btc_pairs = vec![
@@ -646,11 +2725,13 @@ mod tests {
 
     fn make_trade(id: u64) -> Trade {
         Trade {
+            symbol: "ETHBTC".to_string(),
             id,
-            price: 1.0,
-            base_qty: 1.0,
-            quote_qty: 1.0,
-            commission: 0.0,
+            order_id: id,
+            price: Price(1.0),
+            base_qty: Quantity(1.0),
+            quote_qty: Quantity(1.0),
+            commission: Fee(0.0),
             commission_asset: "BNB".to_string(),
             time: DateTime::from_timestamp(0, 0).unwrap(),
             is_buyer: true,
@@ -659,6 +2740,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sort_and_dedup_trades() {
+        let mut late = make_trade(1);
+        late.time = DateTime::from_timestamp(10, 0).unwrap();
+
+        let trades = vec![late, make_trade(3), make_trade(2), make_trade(3)];
+        let ids: Vec<u64> = sort_and_dedup_trades(trades)
+            .into_iter()
+            .map(|trade| trade.id)
+            .collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+    }
+
     #[test]
     fn test_next_from_id_after_batch() {
         let batch = vec![make_trade(100), make_trade(101), make_trade(103)];
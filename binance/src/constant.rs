@@ -4,14 +4,38 @@ pub(crate) const SPOT_MAINNET: &str = "https://api.binance.com";
 pub(crate) const SPOT_MAINNET_US: &str = "https://api.binance.us";
 pub(crate) const SPOT_TESTNET: &str = "https://testnet.binance.vision";
 
+pub(crate) const STREAM_MAINNET: &str = "wss://stream.binance.com:9443";
+pub(crate) const STREAM_MAINNET_US: &str = "wss://stream.binance.us:9443";
+pub(crate) const STREAM_TESTNET: &str = "wss://stream.testnet.binance.vision";
+
 /// User Agent for the client
 pub(crate) const USER_AGENT_NAME: &str =
     concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// Target of the `tracing` events
+pub(crate) const TRACING_TARGET: &str = "exchanges::binance";
+
 pub(crate) const DEFAULT_RECV_WINDOW: u64 = 5000;
 pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(25);
+/// Max number of pairs fetched concurrently
+pub(crate) const DEFAULT_CONCURRENCY: usize = 5;
 
 /// <https://www.binance.com/en/support/announcement/detail/9820396bf54644c39e666b4780622846>
 pub(crate) const MAX_WEIGHT_PER_MIN: u32 = 6000;
 
+/// Max retries of an idempotent request failing with a server error (HTTP 5xx)
+pub(crate) const MAX_SERVER_ERROR_RETRIES: u32 = 3;
+/// Delay before retrying a request failing with a server error, multiplied by the attempt
+pub(crate) const SERVER_ERROR_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Interval between two keepalives of the user data stream listen key (valid for 60 minutes)
+#[cfg(feature = "stream")]
+pub(crate) const LISTEN_KEY_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// Delay before the first reconnection of the user data stream, doubled at every failure
+#[cfg(feature = "stream")]
+pub(crate) const USER_STREAM_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// Max delay between two reconnections of the user data stream
+#[cfg(feature = "stream")]
+pub(crate) const USER_STREAM_MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
 pub(crate) const BTC_TICKER: &str = "BTC";
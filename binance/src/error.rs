@@ -1,20 +1,38 @@
 //! Binance error
 
+use common::interceptor::{RequestError, ServerError};
 use thiserror::Error;
 use url::ParseError;
 
+use crate::response::CancelReplaceFailure;
+
 /// Binance error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Reqwest error
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
+    /// Request error, with the method and path of the request
+    #[error(transparent)]
+    Request(#[from] RequestError),
+    /// Blocking runtime error
+    #[cfg(feature = "blocking")]
+    #[error(transparent)]
+    Runtime(#[from] std::io::Error),
     /// Invalid header
     #[error(transparent)]
     InvalidHeader(#[from] reqwest::header::InvalidHeaderValue),
     /// URL error
     #[error(transparent)]
     Url(#[from] ParseError),
+    /// JSON error
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// WebSocket error
+    #[cfg(feature = "stream")]
+    #[error(transparent)]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
     /// Timestamp error
     #[error(transparent)]
     Timestamp(#[from] std::time::SystemTimeError),
@@ -27,7 +45,50 @@ pub enum Error {
     /// Can't clone the request
     #[error("can't clone the request")]
     CantCloneRequest,
+    /// Server error (HTTP 5xx)
+    #[error(transparent)]
+    ServerError(#[from] ServerError),
+    /// Overall deadline exceeded (see [`BinanceClientBuilder::overall_deadline`])
+    ///
+    /// [`BinanceClientBuilder::overall_deadline`]: crate::builder::BinanceClientBuilder::overall_deadline
+    #[error("deadline of {0:?} exceeded")]
+    Deadline(std::time::Duration),
     /// Missing deposit address in response
     #[error("missing deposit address")]
     MissingDepositAddress,
+    /// Cancel-replace failed (HTTP 4xx)
+    ///
+    /// In [`CancelReplaceMode::AllowFailure`] mode, the new order may have been placed anyway:
+    /// see [`CancelReplaceFailure::new_order`].
+    ///
+    /// [`CancelReplaceMode::AllowFailure`]: crate::request::CancelReplaceMode::AllowFailure
+    #[error("cancel-replace failed (HTTP {status}): {msg} ({code})")]
+    CancelReplaceFailed {
+        /// HTTP status code
+        status: u16,
+        /// Binance error code
+        code: i64,
+        /// Binance error message
+        msg: String,
+        /// Outcome of the two operations
+        data: Box<CancelReplaceFailure>,
+    },
+    /// The symbol of the order doesn't match the expected one
+    #[error("symbol mismatch: expected {expected}, found {found}")]
+    SymbolMismatch {
+        /// Expected symbol
+        expected: String,
+        /// Symbol of the order
+        found: String,
+    },
+    /// Quote order quantity used with a non-market order
+    #[error("quote order quantity is only allowed for market orders")]
+    QuoteQuantityRequiresMarketOrder,
+}
+
+#[cfg(feature = "stream")]
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(e))
+    }
 }
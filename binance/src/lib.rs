@@ -7,10 +7,15 @@
 
 mod api;
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod builder;
 pub mod client;
 mod constant;
 pub mod error;
 pub mod prelude;
+pub mod request;
 pub mod response;
+#[cfg(feature = "stream")]
+mod stream;
 mod util;
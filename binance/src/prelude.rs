@@ -5,11 +5,18 @@
 #![allow(ambiguous_glob_reexports)]
 #![doc(hidden)]
 
+pub use ::common::amount::{Fee, Price, Quantity};
+pub use ::common::cache::CacheConfig;
+pub use ::common::interceptor::{Interceptors, RequestError, ServerError};
+pub use ::reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 pub use ::url::*;
 
 pub use crate::auth::*;
+#[cfg(feature = "blocking")]
+pub use crate::blocking::*;
 pub use crate::builder::*;
 pub use crate::client::*;
 pub use crate::error::*;
+pub use crate::request::*;
 pub use crate::response::*;
 pub use crate::*;
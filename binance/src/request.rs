@@ -0,0 +1,258 @@
+//! Binance requests
+
+use common::amount::{Price, Quantity};
+
+use crate::error::Error;
+use crate::response::{OrderSide, OrderType, TimeInForce};
+use crate::util::normalize_symbol;
+
+/// Withdraw request
+///
+/// <https://developers.binance.com/docs/wallet/capital/withdraw>
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawRequest {
+    /// Coin (i.e., `BTC`)
+    pub coin: String,
+    /// Destination address
+    pub address: String,
+    /// Amount
+    pub amount: f64,
+    /// Network (default: coin's default network)
+    pub network: Option<String>,
+    /// Secondary address identifier (i.e., memo or tag)
+    pub address_tag: Option<String>,
+    /// Client ID for the withdrawal
+    pub withdraw_order_id: Option<String>,
+}
+
+impl WithdrawRequest {
+    /// New withdraw request
+    pub fn new<S, A>(coin: S, address: A, amount: f64) -> Self
+    where
+        S: Into<String>,
+        A: Into<String>,
+    {
+        Self {
+            coin: coin.into(),
+            address: address.into(),
+            amount,
+            network: None,
+            address_tag: None,
+            withdraw_order_id: None,
+        }
+    }
+
+    /// Set network
+    #[inline]
+    pub fn network<S>(mut self, network: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.network = Some(network.into());
+        self
+    }
+
+    /// Set address tag
+    #[inline]
+    pub fn address_tag<S>(mut self, tag: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.address_tag = Some(tag.into());
+        self
+    }
+
+    /// Set client ID for the withdrawal
+    #[inline]
+    pub fn withdraw_order_id<S>(mut self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.withdraw_order_id = Some(id.into());
+        self
+    }
+
+    pub(crate) fn into_parameters(self) -> Vec<(String, String)> {
+        let mut parameters = Vec::new();
+        parameters.push((String::from("coin"), self.coin));
+        parameters.push((String::from("address"), self.address));
+        parameters.push((String::from("amount"), self.amount.to_string()));
+
+        if let Some(network) = self.network {
+            parameters.push((String::from("network"), network));
+        }
+
+        if let Some(address_tag) = self.address_tag {
+            parameters.push((String::from("addressTag"), address_tag));
+        }
+
+        if let Some(withdraw_order_id) = self.withdraw_order_id {
+            parameters.push((String::from("withdrawOrderId"), withdraw_order_id));
+        }
+
+        parameters
+    }
+}
+
+/// Order amount
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderAmount {
+    /// Quantity of the base asset (i.e., buy `0.0017` BTC)
+    Base(Quantity),
+    /// Amount of the quote asset (i.e., spend `100` USDT)
+    ///
+    /// Unlike the other quantities, the [`Quantity`] is in the quote asset. Only allowed for market
+    /// orders.
+    Quote(Quantity),
+}
+
+/// Cancel-replace mode
+///
+/// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#cancel-an-existing-order-and-send-a-new-order-trade>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CancelReplaceMode {
+    /// Don't place the new order if the cancel fails
+    #[default]
+    StopOnFailure,
+    /// Place the new order even if the cancel fails
+    AllowFailure,
+}
+
+impl CancelReplaceMode {
+    /// Get the mode as sent to Binance (i.e., `STOP_ON_FAILURE`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StopOnFailure => "STOP_ON_FAILURE",
+            Self::AllowFailure => "ALLOW_FAILURE",
+        }
+    }
+}
+
+/// Account type of a daily snapshot
+///
+/// Only `SPOT` is supported: the `MARGIN` and `FUTURES` snapshots have a different data layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum SnapshotAccountType {
+    /// Spot account
+    #[default]
+    Spot,
+}
+
+impl SnapshotAccountType {
+    /// Get the account type as sent to Binance (i.e., `SPOT`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Spot => "SPOT",
+        }
+    }
+}
+
+/// New order request
+///
+/// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#new-order-trade>
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    /// Symbol (i.e., `BTCUSDT`)
+    pub symbol: String,
+    /// Side
+    pub side: OrderSide,
+    /// Order type
+    pub order_type: OrderType,
+    /// Amount
+    pub amount: OrderAmount,
+    /// Price
+    pub price: Option<Price>,
+    /// Time in force
+    pub time_in_force: Option<TimeInForce>,
+    /// Client order ID
+    pub new_client_order_id: Option<String>,
+}
+
+impl OrderRequest {
+    /// New market order
+    pub fn market<S>(symbol: S, side: OrderSide, amount: OrderAmount) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Market,
+            amount,
+            price: None,
+            time_in_force: None,
+            new_client_order_id: None,
+        }
+    }
+
+    /// New limit order (good till canceled)
+    pub fn limit<S>(symbol: S, side: OrderSide, quantity: Quantity, price: Price) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Limit,
+            amount: OrderAmount::Base(quantity),
+            price: Some(price),
+            time_in_force: Some(TimeInForce::Gtc),
+            new_client_order_id: None,
+        }
+    }
+
+    /// Set time in force
+    #[inline]
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    /// Set client order ID
+    #[inline]
+    pub fn new_client_order_id<S>(mut self, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.new_client_order_id = Some(id.into());
+        self
+    }
+
+    pub(crate) fn into_parameters(self) -> Result<Vec<(String, String)>, Error> {
+        let mut parameters = Vec::new();
+        parameters.push((String::from("symbol"), normalize_symbol(&self.symbol)));
+        parameters.push((String::from("side"), self.side.as_str().to_string()));
+        parameters.push((String::from("type"), self.order_type.as_str().to_string()));
+
+        if let Some(time_in_force) = self.time_in_force {
+            parameters.push((
+                String::from("timeInForce"),
+                time_in_force.as_str().to_string(),
+            ));
+        }
+
+        match self.amount {
+            OrderAmount::Base(quantity) => {
+                parameters.push((String::from("quantity"), quantity.to_string()));
+            }
+            OrderAmount::Quote(quantity) => {
+                if self.order_type != OrderType::Market {
+                    return Err(Error::QuoteQuantityRequiresMarketOrder);
+                }
+
+                parameters.push((String::from("quoteOrderQty"), quantity.to_string()));
+            }
+        }
+
+        if let Some(price) = self.price {
+            parameters.push((String::from("price"), price.to_string()));
+        }
+
+        if let Some(new_client_order_id) = self.new_client_order_id {
+            parameters.push((String::from("newClientOrderId"), new_client_order_id));
+        }
+
+        Ok(parameters)
+    }
+}
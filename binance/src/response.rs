@@ -4,9 +4,12 @@ use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 
 use chrono::{DateTime, NaiveDateTime, Utc};
+use common::amount::{Fee, Price, Quantity};
 use common::deser::{
     deserialize_string_to_f64, deserialize_unix_timestamp_milliseconds_to_utc_seconds,
 };
+use common::exchange::Exchange;
+use common::trade::{NormalizedTrade, TradeSide};
 use serde::{Deserialize, Deserializer, de};
 
 use crate::constant::BTC_TICKER;
@@ -36,6 +39,14 @@ pub struct ExchangeInformation {
     pub symbols: Vec<Symbol>,
 }
 
+/// Server time
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ServerTime {
+    /// Server time, in milliseconds
+    pub server_time: i64,
+}
+
 /// Rate limit
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -50,6 +61,137 @@ pub struct RateLimit {
     pub limit: u64,
 }
 
+/// Order status
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderStatus {
+    /// The order has been accepted by the engine
+    New,
+    /// The order is pending until the working order of an order list is fully filled
+    PendingNew,
+    /// A part of the order has been filled
+    PartiallyFilled,
+    /// The order has been completed
+    Filled,
+    /// The order has been canceled by the user
+    Canceled,
+    /// Currently unused
+    PendingCancel,
+    /// The order was not accepted by the engine and not processed
+    Rejected,
+    /// The order was canceled according to the order type's rules
+    Expired,
+    /// The order was expired by the exchange due to self-trade prevention
+    ExpiredInMatch,
+    /// Status not known by this library
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl OrderStatus {
+    /// Get the status as sent by Binance (i.e., `FILLED`)
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::New => "NEW",
+            Self::PendingNew => "PENDING_NEW",
+            Self::PartiallyFilled => "PARTIALLY_FILLED",
+            Self::Filled => "FILLED",
+            Self::Canceled => "CANCELED",
+            Self::PendingCancel => "PENDING_CANCEL",
+            Self::Rejected => "REJECTED",
+            Self::Expired => "EXPIRED",
+            Self::ExpiredInMatch => "EXPIRED_IN_MATCH",
+            Self::Other(status) => status,
+        }
+    }
+}
+
+/// Order side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderSide {
+    /// Buy
+    Buy,
+    /// Sell
+    Sell,
+}
+
+impl OrderSide {
+    /// Get the side as sent by Binance (i.e., `BUY`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Buy => "BUY",
+            Self::Sell => "SELL",
+        }
+    }
+}
+
+/// Order type
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderType {
+    /// Limit
+    Limit,
+    /// Market
+    Market,
+    /// Stop loss
+    StopLoss,
+    /// Stop loss limit
+    StopLossLimit,
+    /// Take profit
+    TakeProfit,
+    /// Take profit limit
+    TakeProfitLimit,
+    /// Limit maker
+    LimitMaker,
+    /// Order type not known by this library
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl OrderType {
+    /// Get the order type as sent by Binance (i.e., `LIMIT`)
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Limit => "LIMIT",
+            Self::Market => "MARKET",
+            Self::StopLoss => "STOP_LOSS",
+            Self::StopLossLimit => "STOP_LOSS_LIMIT",
+            Self::TakeProfit => "TAKE_PROFIT",
+            Self::TakeProfitLimit => "TAKE_PROFIT_LIMIT",
+            Self::LimitMaker => "LIMIT_MAKER",
+            Self::Other(r#type) => r#type,
+        }
+    }
+}
+
+/// Time in force
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TimeInForce {
+    /// Good till canceled
+    Gtc,
+    /// Immediate or cancel
+    Ioc,
+    /// Fill or kill
+    Fok,
+    /// Time in force not known by this library
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl TimeInForce {
+    /// Get the time in force as sent by Binance (i.e., `GTC`)
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Gtc => "GTC",
+            Self::Ioc => "IOC",
+            Self::Fok => "FOK",
+            Self::Other(tif) => tif,
+        }
+    }
+}
+
 /// Symbol information
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -67,7 +209,7 @@ pub struct Symbol {
     /// Quote precision
     pub quote_precision: u64,
     /// Order types
-    pub order_types: Vec<String>,
+    pub order_types: Vec<OrderType>,
     /// Iceberg allowed
     pub iceberg_allowed: bool,
     /// Spot trading allowed
@@ -102,17 +244,28 @@ impl Hash for Symbol {
     }
 }
 
+/// Asset metadata
+///
+/// Binance doesn't expose the asset names.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetInfo {
+    /// Asset code (i.e., `BTC`)
+    pub code: String,
+    /// Number of decimals
+    pub decimals: u64,
+}
+
 /// Account information
 #[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountInformation {
-    /// Maker commission rate
+    /// Maker commission rate, in basis points (see [`AccountInformation::commission_rates`])
     pub maker_commission: f32,
-    /// Taker commission rate
+    /// Taker commission rate, in basis points (see [`AccountInformation::commission_rates`])
     pub taker_commission: f32,
-    /// Buyer commission rate
+    /// Buyer commission rate, in basis points (see [`AccountInformation::commission_rates`])
     pub buyer_commission: f32,
-    /// Seller commission rate
+    /// Seller commission rate, in basis points (see [`AccountInformation::commission_rates`])
     pub seller_commission: f32,
     /// Can trade
     pub can_trade: bool,
@@ -120,11 +273,26 @@ pub struct AccountInformation {
     pub can_withdraw: bool,
     /// Can deposit
     pub can_deposit: bool,
+    /// Last update time of the account (milliseconds)
+    pub update_time: u64,
+    /// Account type (i.e., `SPOT`, `MARGIN`)
+    pub account_type: String,
     /// Balances
     pub balances: Vec<Balance>,
+    /// Permissions (i.e., `SPOT`, `MARGIN`)
+    #[serde(default)]
+    pub permissions: Vec<String>,
 }
 
 impl AccountInformation {
+    /// Check if the account has the given permission (i.e., `MARGIN`), case-insensitively
+    #[inline]
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(permission))
+    }
+
     /// Get the balance for the given asset
     #[inline]
     pub fn balance_for_asset(&self, asset: &str) -> Option<&Balance> {
@@ -136,6 +304,65 @@ impl AccountInformation {
     pub fn bitcoin_balance(&self) -> Option<&Balance> {
         self.balance_for_asset(BTC_TICKER)
     }
+
+    /// Get the commission rates
+    #[inline]
+    pub fn commission_rates(&self) -> CommissionRates {
+        CommissionRates {
+            maker_bps: self.maker_commission,
+            taker_bps: self.taker_commission,
+            buyer_bps: self.buyer_commission,
+            seller_bps: self.seller_commission,
+        }
+    }
+}
+
+/// Commission rates
+///
+/// Binance reports the rates in basis points: `15` means 0.15%, so a fee of `0.0015` times the
+/// traded amount. The `*_bps` fields keep the raw values, while the methods return fractions
+/// that can be directly multiplied by an amount.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct CommissionRates {
+    /// Maker commission rate, in basis points
+    pub maker_bps: f32,
+    /// Taker commission rate, in basis points
+    pub taker_bps: f32,
+    /// Buyer commission rate, in basis points
+    pub buyer_bps: f32,
+    /// Seller commission rate, in basis points
+    pub seller_bps: f32,
+}
+
+impl CommissionRates {
+    /// Maker commission rate, as a fraction (i.e., `0.0015` for 0.15%)
+    #[inline]
+    pub fn maker(&self) -> f64 {
+        bps_to_fraction(self.maker_bps)
+    }
+
+    /// Taker commission rate, as a fraction (i.e., `0.0015` for 0.15%)
+    #[inline]
+    pub fn taker(&self) -> f64 {
+        bps_to_fraction(self.taker_bps)
+    }
+
+    /// Buyer commission rate, as a fraction (i.e., `0.0015` for 0.15%)
+    #[inline]
+    pub fn buyer(&self) -> f64 {
+        bps_to_fraction(self.buyer_bps)
+    }
+
+    /// Seller commission rate, as a fraction (i.e., `0.0015` for 0.15%)
+    #[inline]
+    pub fn seller(&self) -> f64 {
+        bps_to_fraction(self.seller_bps)
+    }
+}
+
+#[inline]
+fn bps_to_fraction(bps: f32) -> f64 {
+    f64::from(bps) / 10_000.0
 }
 
 /// Balance
@@ -311,26 +538,346 @@ pub struct WithdrawalTransaction {
     pub network: String,
 }
 
+/// Withdrawal
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct Withdrawal {
+    pub(crate) id: String,
+}
+
+/// Dust transfer result
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DustTransfer {
+    /// Total service charge, in BNB
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub total_service_charge: f64,
+    /// Total amount transferred, in BNB
+    #[serde(rename = "totalTransfered")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub total_transferred: f64,
+    /// Per-asset conversions
+    #[serde(rename = "transferResult")]
+    pub transfers: Vec<DustTransferResult>,
+}
+
+/// Dust transfer of a single asset
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DustTransferResult {
+    /// Transaction ID
+    #[serde(rename = "tranId")]
+    pub id: u64,
+    /// Converted asset
+    pub from_asset: String,
+    /// Converted amount
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub amount: f64,
+    /// Service charge, in BNB
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub service_charge_amount: f64,
+    /// Amount received, in BNB
+    #[serde(rename = "transferedAmount")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub transferred_amount: f64,
+    /// Operation time
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub operate_time: DateTime<Utc>,
+}
+
+/// New order response
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResponse {
+    /// Symbol
+    pub symbol: String,
+    /// Order ID
+    pub order_id: u64,
+    /// Client order ID
+    pub client_order_id: String,
+    /// Transaction time
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub transact_time: DateTime<Utc>,
+    /// Price
+    pub price: Price,
+    /// Original quantity
+    pub orig_qty: Quantity,
+    /// Executed quantity
+    pub executed_qty: Quantity,
+    /// Cumulative quote quantity
+    #[serde(rename = "cummulativeQuoteQty")]
+    pub cumulative_quote_qty: Quantity,
+    /// Status
+    pub status: OrderStatus,
+    /// Time in force
+    pub time_in_force: TimeInForce,
+    /// Order type
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    /// Side
+    pub side: OrderSide,
+    /// Fills (only in `FULL` responses, the default for `MARKET` and `LIMIT` orders)
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+}
+
+impl OrderResponse {
+    /// Average fill price, weighted by quantity
+    ///
+    /// Returns `None` if there are no fills.
+    pub fn average_price(&self) -> Option<Price> {
+        let qty: f64 = self.fills.iter().map(|fill| *fill.qty).sum();
+
+        if qty <= 0.0 {
+            return None;
+        }
+
+        let quote: f64 = self.fills.iter().map(|fill| *fill.price * *fill.qty).sum();
+        Some(Price(quote / qty))
+    }
+
+    /// Total commission paid in an asset (i.e., `BNB`)
+    pub fn total_commission(&self, asset: &str) -> Fee {
+        let commission: f64 = self
+            .fills
+            .iter()
+            .filter(|fill| fill.commission_asset == asset)
+            .map(|fill| *fill.commission)
+            .sum();
+        Fee(commission)
+    }
+}
+
+/// Order, as returned by the order queries
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    /// Symbol
+    pub symbol: String,
+    /// Order ID
+    pub order_id: u64,
+    /// Client order ID
+    pub client_order_id: String,
+    /// Price
+    pub price: Price,
+    /// Original quantity
+    pub orig_qty: Quantity,
+    /// Executed quantity
+    pub executed_qty: Quantity,
+    /// Cumulative quote quantity
+    #[serde(rename = "cummulativeQuoteQty")]
+    pub cumulative_quote_qty: Quantity,
+    /// Status
+    pub status: OrderStatus,
+    /// Time in force
+    pub time_in_force: TimeInForce,
+    /// Order type
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    /// Side
+    pub side: OrderSide,
+    /// Creation time
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub time: DateTime<Utc>,
+    /// Last update time
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub update_time: DateTime<Utc>,
+    /// Whether the order is on the book
+    pub is_working: bool,
+}
+
+/// Result of one of the cancel-replace operations
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CancelReplaceResult {
+    /// Succeeded
+    Success,
+    /// Failed
+    Failure,
+    /// Not attempted, because the cancel failed in `STOP_ON_FAILURE` mode
+    NotAttempted,
+    /// Result not known by this library
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Cancel-replace response
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplaceResponse {
+    /// Cancel result
+    pub cancel_result: CancelReplaceResult,
+    /// New order result
+    pub new_order_result: CancelReplaceResult,
+    /// Canceled order
+    pub cancel_response: OrderResponse,
+    /// New order
+    pub new_order_response: OrderResponse,
+}
+
+/// Binance error message (i.e., `{"code": -2011, "msg": "Unknown order sent."}`)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BinanceErrorMessage {
+    /// Error code
+    pub code: i64,
+    /// Error message
+    pub msg: String,
+}
+
+/// Outcome of one of the cancel-replace operations, when the request failed
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum CancelReplaceOutcome {
+    /// The operation succeeded
+    Order(OrderResponse),
+    /// The operation failed
+    Error(BinanceErrorMessage),
+}
+
+/// Details of a failed cancel-replace (the `data` of the error response)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplaceFailure {
+    /// Cancel result
+    pub cancel_result: CancelReplaceResult,
+    /// New order result
+    pub new_order_result: CancelReplaceResult,
+    /// Cancel outcome
+    pub cancel_response: CancelReplaceOutcome,
+    /// New order outcome (`None` if not attempted)
+    #[serde(default)]
+    pub new_order_response: Option<CancelReplaceOutcome>,
+}
+
+impl CancelReplaceFailure {
+    /// Get the new order, if it has been placed despite the failure
+    pub fn new_order(&self) -> Option<&OrderResponse> {
+        match &self.new_order_response {
+            Some(CancelReplaceOutcome::Order(order)) => Some(order),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CancelReplaceErrorResponse {
+    pub code: i64,
+    pub msg: String,
+    pub data: CancelReplaceFailure,
+}
+
+/// Order fill
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    /// Price
+    pub price: Price,
+    /// Quantity
+    pub qty: Quantity,
+    /// Commission
+    pub commission: Fee,
+    /// Commission asset
+    pub commission_asset: String,
+    /// Trade ID
+    pub trade_id: u64,
+}
+
 /// Binance trade
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Trade {
+    /// Symbol
+    pub symbol: String,
     /// Trade ID
     pub id: u64,
+    /// ID of the order that produced the trade
+    pub order_id: u64,
     /// Price
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub price: f64,
+    pub price: Price,
     /// Quantity
     #[serde(rename = "qty")]
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub base_qty: f64,
+    pub base_qty: Quantity,
     /// Quote quantity
     #[serde(rename = "quoteQty")]
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub quote_qty: f64,
+    pub quote_qty: Quantity,
     /// Commission
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub commission: f64,
+    pub commission: Fee,
+    /// Commission asset
+    pub commission_asset: String,
+    /// Time
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub time: DateTime<Utc>,
+    /// Whether is buyer
+    pub is_buyer: bool,
+    /// Whether is maker
+    pub is_maker: bool,
+    /// Whether is best match
+    pub is_best_match: bool,
+}
+
+impl Trade {
+    /// Get the fee and its currency
+    ///
+    /// The fee is positive when charged (Binance already uses this sign).
+    #[inline]
+    pub fn normalized_fee(&self) -> (Fee, &str) {
+        (self.commission, &self.commission_asset)
+    }
+
+    /// Get the side of the trade
+    #[inline]
+    pub fn side(&self) -> OrderSide {
+        if self.is_buyer {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        }
+    }
+
+    /// Convert to a [`NormalizedTrade`]
+    #[inline]
+    pub fn into_normalized(self) -> NormalizedTrade {
+        NormalizedTrade::from(self)
+    }
+}
+
+impl From<Trade> for NormalizedTrade {
+    fn from(trade: Trade) -> Self {
+        Self {
+            exchange: Exchange::Binance,
+            side: if trade.is_buyer {
+                TradeSide::Buy
+            } else {
+                TradeSide::Sell
+            },
+            symbol: trade.symbol,
+            base_qty: trade.base_qty,
+            quote_qty: trade.quote_qty,
+            price: trade.price,
+            fee: trade.commission,
+            fee_currency: trade.commission_asset,
+            timestamp: trade.time,
+            trade_id: trade.id.to_string(),
+        }
+    }
+}
+
+/// Binance margin trade
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTrade {
+    /// Symbol
+    pub symbol: String,
+    /// Trade ID
+    pub id: u64,
+    /// ID of the order that produced the trade
+    pub order_id: u64,
+    /// Price
+    pub price: Price,
+    /// Quantity
+    #[serde(rename = "qty")]
+    pub base_qty: Quantity,
+    /// Commission
+    pub commission: Fee,
     /// Commission asset
     pub commission_asset: String,
     /// Time
@@ -342,6 +889,281 @@ pub struct Trade {
     pub is_maker: bool,
     /// Whether is best match
     pub is_best_match: bool,
+    /// Whether the trade is from an isolated margin account
+    pub is_isolated: bool,
+}
+
+impl MarginTrade {
+    /// Get the fee and its currency
+    ///
+    /// The fee is positive when charged (Binance already uses this sign).
+    #[inline]
+    pub fn normalized_fee(&self) -> (Fee, &str) {
+        (self.commission, &self.commission_asset)
+    }
+
+    /// Get the side of the trade
+    #[inline]
+    pub fn side(&self) -> OrderSide {
+        if self.is_buyer {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        }
+    }
+
+    /// Get the quote quantity
+    ///
+    /// Not returned by Binance for margin trades: computed from the price and quantity.
+    #[inline]
+    pub fn quote_qty(&self) -> Quantity {
+        Quantity(*self.price * *self.base_qty)
+    }
+
+    /// Convert to a [`NormalizedTrade`]
+    #[inline]
+    pub fn into_normalized(self) -> NormalizedTrade {
+        NormalizedTrade::from(self)
+    }
+}
+
+impl From<MarginTrade> for NormalizedTrade {
+    fn from(trade: MarginTrade) -> Self {
+        Self {
+            exchange: Exchange::Binance,
+            side: if trade.is_buyer {
+                TradeSide::Buy
+            } else {
+                TradeSide::Sell
+            },
+            quote_qty: trade.quote_qty(),
+            symbol: trade.symbol,
+            base_qty: trade.base_qty,
+            price: trade.price,
+            fee: trade.commission,
+            fee_currency: trade.commission_asset,
+            timestamp: trade.time,
+            trade_id: trade.id.to_string(),
+        }
+    }
+}
+
+/// Binance market trade
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTrade {
+    /// Trade ID
+    pub id: u64,
+    /// Price
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub price: f64,
+    /// Quantity
+    #[serde(rename = "qty")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub base_qty: f64,
+    /// Quote quantity
+    #[serde(rename = "quoteQty")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub quote_qty: f64,
+    /// Time
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub time: DateTime<Utc>,
+    /// Whether the buyer was the maker
+    pub is_buyer_maker: bool,
+    /// Whether is best match
+    pub is_best_match: bool,
+}
+
+/// Account snapshots response
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AccountSnapshots {
+    pub snapshot_vos: Vec<AccountSnapshot>,
+}
+
+/// Daily account snapshot
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSnapshot {
+    /// Account type (i.e., `spot`)
+    pub r#type: String,
+    /// Snapshot time
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub update_time: DateTime<Utc>,
+    /// Snapshot data
+    pub data: SnapshotData,
+}
+
+/// Account snapshot data of a `SPOT` account
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotData {
+    /// Balances
+    #[serde(default)]
+    pub balances: Vec<Balance>,
+    /// Total value of the assets, in BTC
+    #[serde(default, deserialize_with = "deserialize_string_to_f64")]
+    pub total_asset_of_btc: f64,
+}
+
+impl AccountSnapshot {
+    /// Get the balance of an asset (i.e., `BTC`), if any
+    pub fn balance(&self, asset: &str) -> Option<&Balance> {
+        self.data.balances.iter().find(|b| b.asset == asset)
+    }
+}
+
+/// User data stream listen key
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKey {
+    /// Listen key
+    pub listen_key: String,
+}
+
+/// User data stream event
+///
+/// <https://developers.binance.com/docs/binance-spot-api-docs/user-data-stream>
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "e")]
+pub enum UserEvent {
+    /// Account balances changed
+    #[serde(rename = "outboundAccountPosition")]
+    AccountPosition(AccountPosition),
+    /// Order update
+    #[serde(rename = "executionReport")]
+    ExecutionReport(Box<ExecutionReport>),
+    /// The listen key expired: a new one must be created
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired,
+    /// Event not known by this library
+    #[serde(other)]
+    Other,
+}
+
+/// Account position update (`outboundAccountPosition`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountPosition {
+    /// Event time
+    #[serde(rename = "E")]
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub event_time: DateTime<Utc>,
+    /// Time of the last account update
+    #[serde(rename = "u")]
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub last_update_time: DateTime<Utc>,
+    /// Changed balances
+    #[serde(rename = "B")]
+    pub balances: Vec<PositionBalance>,
+}
+
+/// Balance of an account position update
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PositionBalance {
+    /// Asset
+    #[serde(rename = "a")]
+    pub asset: String,
+    /// Free amount
+    #[serde(rename = "f")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub free: f64,
+    /// Locked amount
+    #[serde(rename = "l")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub locked: f64,
+}
+
+/// Execution type of an order update
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExecutionType {
+    /// The order has been accepted
+    New,
+    /// The order has been canceled
+    Canceled,
+    /// Currently unused
+    Replaced,
+    /// The order has been rejected
+    Rejected,
+    /// Part of the order or all of the order's quantity has filled
+    Trade,
+    /// The order was canceled according to the order type's rules
+    Expired,
+    /// The order has expired due to self-trade prevention
+    TradePrevention,
+    /// Execution type not known by this library
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Order update (`executionReport`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExecutionReport {
+    /// Event time
+    #[serde(rename = "E")]
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub event_time: DateTime<Utc>,
+    /// Symbol
+    #[serde(rename = "s")]
+    pub symbol: String,
+    /// Client order ID
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    /// Side
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    /// Order type
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    /// Time in force
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    /// Order quantity
+    #[serde(rename = "q")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub quantity: f64,
+    /// Order price
+    #[serde(rename = "p")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub price: f64,
+    /// Current execution type
+    #[serde(rename = "x")]
+    pub execution_type: ExecutionType,
+    /// Current order status
+    #[serde(rename = "X")]
+    pub status: OrderStatus,
+    /// Reject reason (`NONE` if not rejected)
+    #[serde(rename = "r")]
+    pub reject_reason: String,
+    /// Order ID
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    /// Last executed quantity
+    #[serde(rename = "l")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub last_executed_qty: f64,
+    /// Cumulative filled quantity
+    #[serde(rename = "z")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub cumulative_filled_qty: f64,
+    /// Last executed price
+    #[serde(rename = "L")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub last_executed_price: f64,
+    /// Commission amount
+    #[serde(rename = "n")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub commission: f64,
+    /// Commission asset
+    #[serde(rename = "N")]
+    pub commission_asset: Option<String>,
+    /// Transaction time
+    #[serde(rename = "T")]
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub transaction_time: DateTime<Utc>,
+    /// Trade ID (`-1` if not a trade)
+    #[serde(rename = "t")]
+    pub trade_id: i64,
 }
 
 #[cfg(test)]
@@ -385,6 +1207,12 @@ mod tests {
         assert_eq!(account.can_trade, true);
         assert_eq!(account.can_withdraw, true);
         assert_eq!(account.can_deposit, true);
+        assert_eq!(account.update_time, 123456789);
+        assert_eq!(account.account_type, "SPOT");
+        assert_eq!(account.permissions, vec!["SPOT".to_string()]);
+        assert!(account.has_permission("SPOT"));
+        assert!(account.has_permission("spot"));
+        assert!(!account.has_permission("MARGIN"));
         assert_eq!(
             account.balances,
             vec![
@@ -402,6 +1230,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_commission_rates() {
+        let account = AccountInformation {
+            maker_commission: 15.0,
+            taker_commission: 10.0,
+            buyer_commission: 0.0,
+            seller_commission: 0.0,
+            can_trade: true,
+            can_withdraw: true,
+            can_deposit: true,
+            update_time: 0,
+            account_type: String::from("SPOT"),
+            balances: Vec::new(),
+            permissions: Vec::new(),
+        };
+
+        let rates = account.commission_rates();
+        assert_eq!(rates.maker_bps, 15.0);
+        assert_eq!(rates.taker_bps, 10.0);
+        assert_eq!(rates.maker(), 0.0015);
+        assert_eq!(rates.taker(), 0.001);
+        assert_eq!(rates.buyer(), 0.0);
+        assert_eq!(rates.seller(), 0.0);
+    }
+
     #[test]
     fn test_deserialize_deposit_transaction() {
         let json = r#"{
@@ -509,4 +1362,251 @@ mod tests {
         let tx: WithdrawalTransaction = serde_json::from_str(json).unwrap();
         assert_eq!(tx.status, WithdrawStatus::Unknown(99));
     }
+
+    #[test]
+    fn test_deserialize_trade() {
+        let json = r#"{
+    "symbol": "BNBBTC",
+    "id": 28457,
+    "orderId": 100234,
+    "orderListId": -1,
+    "price": "4.00000100",
+    "qty": "12.00000000",
+    "quoteQty": "48.000012",
+    "commission": "10.10000000",
+    "commissionAsset": "BNB",
+    "time": 1499865549590,
+    "isBuyer": true,
+    "isMaker": false,
+    "isBestMatch": true
+}"#;
+
+        let trade: Trade = serde_json::from_str(json).unwrap();
+        assert_eq!(trade.id, 28457);
+        assert_eq!(trade.order_id, 100234);
+        assert_eq!(trade.side(), OrderSide::Buy);
+        assert_eq!(trade.normalized_fee(), (Fee(10.1), "BNB"));
+
+        let trade: NormalizedTrade = trade.into_normalized();
+        assert_eq!(trade.exchange, Exchange::Binance);
+        assert_eq!(trade.symbol, "BNBBTC");
+        assert_eq!(trade.side, TradeSide::Buy);
+        assert_eq!(*trade.base_qty, 12.0);
+        assert_eq!(*trade.quote_qty, 48.000012);
+        assert_eq!(*trade.fee, 10.1);
+        assert_eq!(trade.trade_id, "28457");
+    }
+
+    #[test]
+    fn test_deserialize_margin_trade() {
+        let json = r#"{
+    "commission": "0.00006000",
+    "commissionAsset": "BTC",
+    "id": 34,
+    "isBestMatch": true,
+    "isBuyer": false,
+    "isMaker": false,
+    "orderId": 39324,
+    "price": "0.02000000",
+    "qty": "3.00000000",
+    "symbol": "BNBBTC",
+    "isIsolated": true,
+    "time": 1561973357171
+}"#;
+
+        let trade: MarginTrade = serde_json::from_str(json).unwrap();
+        assert_eq!(trade.order_id, 39324);
+        assert!(trade.is_isolated);
+        assert_eq!(trade.side(), OrderSide::Sell);
+        assert!((*trade.quote_qty() - 0.06).abs() < 1e-12);
+
+        let trade: NormalizedTrade = trade.into_normalized();
+        assert_eq!(trade.side, TradeSide::Sell);
+        assert_eq!(trade.fee_currency, "BTC");
+        assert_eq!(trade.trade_id, "34");
+    }
+
+    #[test]
+    fn test_deserialize_market_trade() {
+        let json = r#"{
+    "id": 28457,
+    "price": "4.00000100",
+    "qty": "12.00000000",
+    "quoteQty": "48.000012",
+    "time": 1499865549590,
+    "isBuyerMaker": true,
+    "isBestMatch": true
+}"#;
+
+        let trade: MarketTrade = serde_json::from_str(json).unwrap();
+        assert_eq!(trade.id, 28457);
+        assert_eq!(trade.price, 4.000001);
+        assert_eq!(trade.base_qty, 12.0);
+        assert_eq!(trade.quote_qty, 48.000012);
+        assert_eq!(trade.time.timestamp(), 1499865549);
+        assert!(trade.is_buyer_maker);
+        assert!(trade.is_best_match);
+    }
+
+    #[test]
+    fn test_deserialize_order_enums() {
+        let status: OrderStatus = serde_json::from_str(r#""PARTIALLY_FILLED""#).unwrap();
+        assert_eq!(status, OrderStatus::PartiallyFilled);
+        let status: OrderStatus = serde_json::from_str(r#""EXPIRED_IN_MATCH""#).unwrap();
+        assert_eq!(status.as_str(), "EXPIRED_IN_MATCH");
+        let status: OrderStatus = serde_json::from_str(r#""NEW_STATUS""#).unwrap();
+        assert_eq!(status, OrderStatus::Other(String::from("NEW_STATUS")));
+
+        let side: OrderSide = serde_json::from_str(r#""SELL""#).unwrap();
+        assert_eq!(side, OrderSide::Sell);
+        assert!(serde_json::from_str::<OrderSide>(r#""HOLD""#).is_err());
+
+        let r#type: OrderType = serde_json::from_str(r#""STOP_LOSS_LIMIT""#).unwrap();
+        assert_eq!(r#type, OrderType::StopLossLimit);
+        assert_eq!(r#type.as_str(), "STOP_LOSS_LIMIT");
+        let r#type: OrderType = serde_json::from_str(r#""OCO""#).unwrap();
+        assert_eq!(r#type, OrderType::Other(String::from("OCO")));
+
+        let tif: TimeInForce = serde_json::from_str(r#""GTC""#).unwrap();
+        assert_eq!(tif, TimeInForce::Gtc);
+        let tif: TimeInForce = serde_json::from_str(r#""GTX""#).unwrap();
+        assert_eq!(tif, TimeInForce::Other(String::from("GTX")));
+    }
+
+    #[test]
+    fn test_deserialize_order_response_fills() {
+        let json = r#"{
+    "symbol": "BTCUSDT",
+    "orderId": 28,
+    "orderListId": -1,
+    "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+    "transactTime": 1507725176595,
+    "price": "0.00000000",
+    "origQty": "10.00000000",
+    "executedQty": "10.00000000",
+    "cummulativeQuoteQty": "10.00000000",
+    "status": "FILLED",
+    "timeInForce": "GTC",
+    "type": "MARKET",
+    "side": "SELL",
+    "fills": [
+        {
+            "price": "4000.00000000",
+            "qty": "1.00000000",
+            "commission": "4.00000000",
+            "commissionAsset": "USDT",
+            "tradeId": 56
+        },
+        {
+            "price": "3999.00000000",
+            "qty": "5.00000000",
+            "commission": "19.99500000",
+            "commissionAsset": "USDT",
+            "tradeId": 57
+        },
+        {
+            "price": "3998.00000000",
+            "qty": "4.00000000",
+            "commission": "0.00100000",
+            "commissionAsset": "BNB",
+            "tradeId": 58
+        }
+    ]
+}"#;
+        let order: OrderResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(order.fills.len(), 3);
+        assert_eq!(
+            order.fills[0],
+            Fill {
+                price: Price(4000.0),
+                qty: Quantity(1.0),
+                commission: Fee(4.0),
+                commission_asset: String::from("USDT"),
+                trade_id: 56,
+            }
+        );
+        assert!((*order.average_price().unwrap() - 3998.7).abs() < 1e-9);
+        assert!((*order.total_commission("USDT") - 23.995).abs() < 1e-9);
+        assert_eq!(order.total_commission("BNB"), Fee(0.001));
+
+        // ACK/RESULT responses have no fills
+        let json = json.split(",\n    \"fills\"").next().unwrap().to_string() + "}";
+        let order: OrderResponse = serde_json::from_str(&json).unwrap();
+        assert!(order.fills.is_empty());
+        assert_eq!(order.average_price(), None);
+    }
+
+    #[test]
+    fn test_deserialize_user_events() {
+        let json = r#"{
+    "e": "outboundAccountPosition",
+    "E": 1564034571105,
+    "u": 1564034571073,
+    "B": [{"a": "ETH", "f": "10000.000000", "l": "0.000000"}]
+}"#;
+        let event: UserEvent = serde_json::from_str(json).unwrap();
+        let UserEvent::AccountPosition(position) = event else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(position.event_time.timestamp(), 1564034571);
+        assert_eq!(
+            position.balances,
+            vec![PositionBalance {
+                asset: String::from("ETH"),
+                free: 10000.0,
+                locked: 0.0,
+            }]
+        );
+
+        let json = r#"{
+    "e": "executionReport",
+    "E": 1499405658658,
+    "s": "ETHBTC",
+    "c": "mUvoqJxFIILMdfAW5iGSOW",
+    "S": "BUY",
+    "o": "LIMIT",
+    "f": "GTC",
+    "q": "1.00000000",
+    "p": "0.10264410",
+    "P": "0.00000000",
+    "F": "0.00000000",
+    "g": -1,
+    "C": "",
+    "x": "TRADE",
+    "X": "PARTIALLY_FILLED",
+    "r": "NONE",
+    "i": 4293153,
+    "l": "0.40000000",
+    "z": "0.40000000",
+    "L": "0.10264410",
+    "n": "0.00040000",
+    "N": "ETH",
+    "T": 1499405658657,
+    "t": 12,
+    "w": false,
+    "m": false,
+    "M": true,
+    "O": 1499405658657
+}"#;
+        let event: UserEvent = serde_json::from_str(json).unwrap();
+        let UserEvent::ExecutionReport(report) = event else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(report.symbol, "ETHBTC");
+        assert_eq!(report.side, OrderSide::Buy);
+        assert_eq!(report.execution_type, ExecutionType::Trade);
+        assert_eq!(report.status, OrderStatus::PartiallyFilled);
+        assert_eq!(report.order_id, 4293153);
+        assert_eq!(report.last_executed_qty, 0.4);
+        assert_eq!(report.commission_asset.as_deref(), Some("ETH"));
+        assert_eq!(report.trade_id, 12);
+
+        let json = r#"{"e": "listenKeyExpired", "E": 1699596037418, "listenKey": "abc"}"#;
+        let event: UserEvent = serde_json::from_str(json).unwrap();
+        assert!(matches!(event, UserEvent::ListenKeyExpired));
+
+        let json = r#"{"e": "balanceUpdate", "E": 1573200697110, "a": "BTC", "d": "100.00000000"}"#;
+        let event: UserEvent = serde_json::from_str(json).unwrap();
+        assert!(matches!(event, UserEvent::Other));
+    }
 }
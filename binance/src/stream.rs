@@ -0,0 +1,199 @@
+//! User data stream
+
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt, stream};
+use tokio::net::TcpStream;
+use tokio::time::{self, Instant, Interval, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use url::Url;
+
+use crate::client::BinanceClient;
+use crate::constant::{
+    LISTEN_KEY_KEEPALIVE_INTERVAL, TRACING_TARGET, USER_STREAM_MAX_RECONNECT_DELAY,
+    USER_STREAM_RECONNECT_DELAY,
+};
+use crate::error::Error;
+use crate::response::UserEvent;
+
+impl BinanceClient {
+    /// Stream the user data events (i.e., balance and order updates)
+    ///
+    /// A listen key is created on the first poll and kept alive every 30 minutes. When the
+    /// connection drops, the stream reconnects with the same listen key. On
+    /// [`UserEvent::ListenKeyExpired`], the event is yielded and a new listen key is created:
+    /// the events sent in between are lost. A superseded listen key is closed before creating
+    /// the new one.
+    ///
+    /// Errors don't end the stream: the next poll reconnects, waiting 1 second after the first
+    /// failure and doubling the delay at every further one (up to 60 seconds). The listen key
+    /// isn't closed when the stream is dropped, it expires after 60 minutes.
+    ///
+    /// Requires the `stream` feature.
+    pub fn user_stream(&self) -> impl Stream<Item = Result<UserEvent, Error>> + '_ {
+        stream::unfold(UserStream::new(), move |mut state| async move {
+            let event = self.next_user_event(&mut state).await;
+            Some((event, state))
+        })
+    }
+
+    async fn next_user_event(&self, state: &mut UserStream) -> Result<UserEvent, Error> {
+        loop {
+            if state.socket.is_none() {
+                state.wait_before_reconnect().await;
+            }
+
+            let listen_key: String = match &state.listen_key {
+                Some(listen_key) => listen_key.clone(),
+                None => {
+                    if let Some(stale) = state.stale_listen_key.take() {
+                        if let Err(e) = self.close_listen_key(&stale).await {
+                            tracing::debug!(target: TRACING_TARGET, error = %e, "can't close the superseded listen key");
+                        }
+                    }
+
+                    let listen_key: String = match self.create_listen_key().await {
+                        Ok(listen_key) => listen_key,
+                        Err(e) => {
+                            state.failed();
+                            return Err(e);
+                        }
+                    };
+                    state.keepalive.reset();
+                    state.listen_key.insert(listen_key).clone()
+                }
+            };
+
+            let socket = match &mut state.socket {
+                Some(socket) => socket,
+                None => {
+                    let url: Url = self.stream_host.join(&format!("ws/{listen_key}"))?;
+                    match tokio_tungstenite::connect_async(url.as_str()).await {
+                        Ok((socket, _)) => state.socket.insert(socket),
+                        Err(e) => {
+                            // The listen key may be gone: create a new one on the next attempt
+                            state.reset();
+                            return Err(e.into());
+                        }
+                    }
+                }
+            };
+
+            let message = tokio::select! {
+                _ = state.keepalive.tick() => None,
+                message = socket.next() => Some(message),
+            };
+
+            match message {
+                // Keepalive
+                None => {
+                    if let Err(e) = self.keepalive_listen_key(&listen_key).await {
+                        state.reset();
+                        return Err(e);
+                    }
+                }
+                Some(Some(Ok(Message::Text(text)))) => {
+                    state.failures = 0;
+
+                    let event: UserEvent = serde_json::from_str(text.as_str())?;
+
+                    if let UserEvent::ListenKeyExpired = event {
+                        state.reset();
+                    }
+
+                    return Ok(event);
+                }
+                // Ping/pong frames are answered while reading
+                Some(Some(Ok(Message::Ping(..) | Message::Pong(..) | Message::Frame(..)))) => {}
+                Some(Some(Ok(Message::Binary(..)))) => {}
+                // Closed by the server (i.e., every 24 hours): reconnect
+                Some(Some(Ok(Message::Close(..))) | None) => state.disconnected(),
+                Some(Some(Err(e))) => {
+                    state.disconnected();
+                    return Err(e.into());
+                }
+            }
+        }
+    }
+}
+
+/// State of [`BinanceClient::user_stream`]
+struct UserStream {
+    listen_key: Option<String>,
+    /// Listen key replaced by a new one, to close
+    stale_listen_key: Option<String>,
+    socket: Option<WebSocketStream<MaybeTlsStream<TcpStream>>>,
+    keepalive: Interval,
+    /// Consecutive failures, reset when an event is received
+    failures: u32,
+}
+
+impl UserStream {
+    fn new() -> Self {
+        let mut keepalive: Interval = time::interval_at(
+            Instant::now() + LISTEN_KEY_KEEPALIVE_INTERVAL,
+            LISTEN_KEY_KEEPALIVE_INTERVAL,
+        );
+        keepalive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        Self {
+            listen_key: None,
+            stale_listen_key: None,
+            socket: None,
+            keepalive,
+            failures: 0,
+        }
+    }
+
+    /// Record a failure: the next reconnection waits longer
+    #[inline]
+    fn failed(&mut self) {
+        self.failures = self.failures.saturating_add(1);
+    }
+
+    /// Drop the connection, keeping the listen key
+    fn disconnected(&mut self) {
+        self.socket = None;
+        self.failed();
+    }
+
+    /// Drop the connection and the listen key
+    fn reset(&mut self) {
+        if let Some(listen_key) = self.listen_key.take() {
+            self.stale_listen_key = Some(listen_key);
+        }
+        self.disconnected();
+    }
+
+    /// Wait before reconnecting, if the previous attempts failed
+    async fn wait_before_reconnect(&self) {
+        if let Some(delay) = reconnect_delay(self.failures) {
+            time::sleep(delay).await;
+        }
+    }
+}
+
+/// Delay before reconnecting after `failures` consecutive failures (capped exponential backoff)
+fn reconnect_delay(failures: u32) -> Option<Duration> {
+    let exponent: u32 = failures.checked_sub(1)?;
+    let delay: Duration = USER_STREAM_RECONNECT_DELAY
+        .checked_mul(2u32.saturating_pow(exponent))
+        .unwrap_or(USER_STREAM_MAX_RECONNECT_DELAY);
+    Some(delay.min(USER_STREAM_MAX_RECONNECT_DELAY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_delay() {
+        assert_eq!(reconnect_delay(0), None);
+        assert_eq!(reconnect_delay(1), Some(Duration::from_secs(1)));
+        assert_eq!(reconnect_delay(2), Some(Duration::from_secs(2)));
+        assert_eq!(reconnect_delay(4), Some(Duration::from_secs(8)));
+        assert_eq!(reconnect_delay(7), Some(Duration::from_secs(60)));
+        assert_eq!(reconnect_delay(u32::MAX), Some(Duration::from_secs(60)));
+    }
+}
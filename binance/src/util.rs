@@ -1,36 +1,43 @@
-use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use url::form_urlencoded::Serializer;
+
 use crate::error::Error;
 
-pub fn build_request(parameters: BTreeMap<String, String>) -> String {
-    let mut request = String::new();
-    for (key, value) in parameters {
-        let param = format!("{}={}&", key, value);
-        request.push_str(param.as_ref());
-    }
-    request.pop();
-    request
+/// Build the percent-encoded query string, preserving the order of the parameters.
+///
+/// Keys can be repeated (i.e., `asset=ETH&asset=LTC`). The signature is computed over this exact
+/// string.
+pub fn build_request(parameters: Vec<(String, String)>) -> String {
+    Serializer::new(String::new())
+        .extend_pairs(parameters)
+        .finish()
+}
+
+/// Normalize a symbol to the format expected by Binance (i.e., ` btcusdt` -> `BTCUSDT`).
+#[inline]
+pub fn normalize_symbol(symbol: &str) -> String {
+    symbol.trim().to_ascii_uppercase()
 }
 
 pub fn build_signed_request(
-    parameters: BTreeMap<String, String>,
+    parameters: Vec<(String, String)>,
     recv_window: u64,
 ) -> Result<String, Error> {
     build_signed_request_custom(parameters, recv_window, SystemTime::now())
 }
 
 pub fn build_signed_request_custom(
-    mut parameters: BTreeMap<String, String>,
+    mut parameters: Vec<(String, String)>,
     recv_window: u64,
     start: SystemTime,
 ) -> Result<String, Error> {
     if recv_window > 0 {
-        parameters.insert("recvWindow".into(), recv_window.to_string());
+        parameters.push(("recvWindow".into(), recv_window.to_string()));
     }
     let timestamp = get_timestamp(start)?;
 
-    parameters.insert("timestamp".into(), timestamp.to_string());
+    parameters.push(("timestamp".into(), timestamp.to_string()));
     Ok(build_request(parameters))
 }
 
@@ -38,3 +45,99 @@ fn get_timestamp(start: SystemTime) -> Result<u64, Error> {
     let since_epoch = start.duration_since(UNIX_EPOCH)?;
     Ok(since_epoch.as_secs() * 1000 + u64::from(since_epoch.subsec_nanos()) / 1_000_000)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    use super::*;
+
+    fn params(parameters: &[(&str, &str)]) -> Vec<(String, String)> {
+        parameters
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize_symbol() {
+        assert_eq!(normalize_symbol("btcusdt"), "BTCUSDT");
+        assert_eq!(normalize_symbol(" EthBtc\n"), "ETHBTC");
+        assert_eq!(normalize_symbol("BTCUSDT"), "BTCUSDT");
+    }
+
+    #[test]
+    fn test_build_request_preserves_order_and_duplicates() {
+        let request = build_request(params(&[
+            ("symbol", "BTCUSDT"),
+            ("asset", "ETH"),
+            ("asset", "LTC"),
+        ]));
+        assert_eq!(request, "symbol=BTCUSDT&asset=ETH&asset=LTC");
+    }
+
+    #[test]
+    fn test_build_request_percent_encodes_values() {
+        let request = build_request(params(&[
+            ("name", "cold wallet & co"),
+            ("symbol", "BTCUSDT"),
+        ]));
+        assert_eq!(request, "name=cold+wallet+%26+co&symbol=BTCUSDT");
+    }
+
+    #[test]
+    fn test_build_signed_request_signs_encoded_query() {
+        let start = UNIX_EPOCH + Duration::from_millis(1499827319559);
+        let request = build_signed_request_custom(params(&[("name", "a&b c")]), 0, start).unwrap();
+        assert_eq!(request, "name=a%26b+c&timestamp=1499827319559");
+
+        // The server decodes the query back to the original value
+        let decoded: Vec<(String, String)> = url::form_urlencoded::parse(request.as_bytes())
+            .into_owned()
+            .collect();
+        assert_eq!(decoded[0], (String::from("name"), String::from("a&b c")));
+    }
+
+    /// Example from the Binance docs
+    ///
+    /// <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/request-security#signed-endpoint-examples-for-post-apiv3order---hmac-keys>
+    #[test]
+    fn test_build_signed_request_matches_binance_example() {
+        let start = UNIX_EPOCH + Duration::from_millis(1499827319559);
+        let request = build_signed_request_custom(
+            params(&[
+                ("symbol", "LTCBTC"),
+                ("side", "BUY"),
+                ("type", "LIMIT"),
+                ("timeInForce", "GTC"),
+                ("quantity", "1"),
+                ("price", "0.1"),
+            ]),
+            5000,
+            start,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559"
+        );
+
+        let secret = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(request.as_bytes());
+        assert_eq!(
+            hex::encode(mac.finalize().into_bytes()),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+
+    #[test]
+    fn test_build_signed_request_without_recv_window() {
+        let start = UNIX_EPOCH + Duration::from_millis(1499827319559);
+        let request = build_signed_request_custom(Vec::new(), 0, start).unwrap();
+        assert_eq!(request, "timestamp=1499827319559");
+    }
+}
@@ -0,0 +1,312 @@
+//! Replay recorded responses through the client
+
+use binance_api::prelude::*;
+use chrono::DateTime;
+#[cfg(feature = "stream")]
+use futures_util::{SinkExt, StreamExt};
+#[cfg(feature = "stream")]
+use tokio::net::TcpListener;
+#[cfg(feature = "stream")]
+use tokio_tungstenite::tungstenite::Message;
+#[cfg(feature = "stream")]
+use tokio_tungstenite::tungstenite::handshake::server::Request;
+use wiremock::matchers::{header, header_exists, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(server: &MockServer) -> BinanceClient {
+    let url: Url = Url::parse(&server.uri()).unwrap();
+    BinanceClient::builder()
+        .endpoint(BinanceEndpoint::new(url))
+        .auth(BinanceAuth::ApiKeys {
+            api_key: String::from("key"),
+            secret_key: String::from("secret"),
+        })
+        .build()
+        .unwrap()
+}
+
+async fn mount(server: &MockServer, http_method: &str, http_path: &str, fixture: &str) {
+    Mock::given(method(http_method))
+        .and(path(http_path))
+        .respond_with(ResponseTemplate::new(200).set_body_string(fixture))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_get_account() {
+    let server = MockServer::start().await;
+    mount(
+        &server,
+        "GET",
+        "/api/v3/account",
+        include_str!("fixtures/account.json"),
+    )
+    .await;
+
+    let account = client(&server).get_account().await.unwrap();
+    assert_eq!(account.balances[0].asset, "BTC");
+    assert_eq!(account.balances[0].free, 0.52);
+
+    let requests = server.received_requests().await.unwrap();
+    let request = &requests[0];
+    assert_eq!(request.headers["x-mbx-apikey"], "key");
+
+    let query: &str = request.url.query().unwrap();
+    assert!(query.contains("timestamp="));
+    assert!(query.contains("&signature="));
+    assert!(!query.contains("omitZeroBalances"));
+}
+
+#[tokio::test]
+async fn test_get_account_omit_zero_balances() {
+    let server = MockServer::start().await;
+    mount(
+        &server,
+        "GET",
+        "/api/v3/account",
+        include_str!("fixtures/account.json"),
+    )
+    .await;
+
+    client(&server)
+        .get_account_with_options(true)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let query: &str = requests[0].url.query().unwrap();
+    assert!(query.starts_with("omitZeroBalances=true&"));
+}
+
+#[tokio::test]
+async fn test_healthcheck_verifies_api_keys() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/api/v3/ping", "{}").await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/account"))
+        .and(query_param("omitZeroBalances", "true"))
+        .and(header("x-mbx-apikey", "key"))
+        .respond_with(ResponseTemplate::new(401).set_body_string(
+            r#"{"code":-2015,"msg":"Invalid API-key, IP, or permissions for action."}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server).healthcheck().await.unwrap_err();
+    assert!(
+        matches!(err, Error::Request(_)),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_bitcoin_deposit_address() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/sapi/v1/capital/deposit/address"))
+        .and(query_param("coin", "BTC"))
+        .and(query_param("network", "BTC"))
+        .and(header("x-mbx-apikey", "key"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/deposit_address.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let address = client(&server).bitcoin_deposit_address().await.unwrap();
+    assert_eq!(address, "1HPn8Rx2y6nNSfagQBKy27GB99Vbzg89wv");
+}
+
+#[tokio::test]
+async fn test_historical_trades() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/historicalTrades"))
+        .and(query_param("symbol", "BTCUSDT"))
+        .and(header_exists("x-mbx-apikey"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/historical_trades.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let trades = client(&server)
+        .historical_trades("BTCUSDT", None, None)
+        .await
+        .unwrap();
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].id, 28457);
+}
+
+#[tokio::test]
+async fn test_http_error_hides_signature() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/account"))
+        .respond_with(
+            ResponseTemplate::new(400).set_body_string(
+                r#"{"code":-1022,"msg":"Signature for this request is not valid."}"#,
+            ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server).get_account().await.unwrap_err();
+    let Error::Request(ref e) = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(e.method().map(|method| method.as_str()), Some("GET"));
+    assert_eq!(e.path(), "/api/v3/account");
+    assert!(err.to_string().starts_with("GET /api/v3/account: "));
+    assert!(!err.to_string().contains("signature"));
+    assert!(!err.to_string().contains("timestamp"));
+}
+
+#[tokio::test]
+async fn test_listen_key_lifecycle() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/userDataStream"))
+        .and(header_exists("x-mbx-apikey"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"listenKey":"pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    for http_method in ["PUT", "DELETE"] {
+        Mock::given(method(http_method))
+            .and(path("/api/v3/userDataStream"))
+            .and(query_param(
+                "listenKey",
+                "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1",
+            ))
+            .and(header_exists("x-mbx-apikey"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = client(&server);
+    let listen_key = client.create_listen_key().await.unwrap();
+    assert_eq!(
+        listen_key,
+        "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"
+    );
+
+    client.keepalive_listen_key(&listen_key).await.unwrap();
+    client.close_listen_key(&listen_key).await.unwrap();
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_user_stream_recreates_expired_listen_key() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/userDataStream"))
+        .and(header_exists("x-mbx-apikey"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"listenKey":"key"}"#))
+        .expect(2)
+        .mount(&server)
+        .await;
+    // The expired listen key is closed before creating the new one
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/userDataStream"))
+        .and(query_param("listenKey", "key"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let stream_url: Url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+
+    let position = r#"{"e":"outboundAccountPosition","E":1564034571105,"u":1564034571073,"B":[{"a":"ETH","f":"10000.000000","l":"0.000000"}]}"#;
+    let expired = r#"{"e":"listenKeyExpired","E":1699596037418,"listenKey":"key"}"#;
+
+    tokio::spawn(async move {
+        for messages in [vec![position, expired], vec![position]] {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_hdr_async(tcp, |req: &Request, res| {
+                assert_eq!(req.uri().path(), "/ws/key");
+                Ok(res)
+            })
+            .await
+            .unwrap();
+
+            for message in messages {
+                socket.send(Message::text(message)).await.unwrap();
+            }
+        }
+    });
+
+    let url: Url = Url::parse(&server.uri()).unwrap();
+    let client = BinanceClient::builder()
+        .endpoint(BinanceEndpoint::new(url))
+        .stream_endpoint(stream_url)
+        .auth(BinanceAuth::ApiKeys {
+            api_key: String::from("key"),
+            secret_key: String::from("secret"),
+        })
+        .build()
+        .unwrap();
+
+    let events: Vec<UserEvent> = client
+        .user_stream()
+        .take(3)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert!(matches!(events[0], UserEvent::AccountPosition(..)));
+    assert!(matches!(events[1], UserEvent::ListenKeyExpired));
+    assert!(matches!(events[2], UserEvent::AccountPosition(..)));
+}
+
+#[tokio::test]
+async fn test_account_snapshots() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/sapi/v1/accountSnapshot"))
+        .and(query_param("type", "SPOT"))
+        .and(query_param("limit", "7"))
+        .and(query_param("startTime", "1576195200000"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/account_snapshot.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let start_time = DateTime::from_timestamp_millis(1576195200000).unwrap();
+    let snapshots = client(&server)
+        .account_snapshots(SnapshotAccountType::Spot, Some(start_time), None, Some(7))
+        .await
+        .unwrap();
+    assert_eq!(snapshots.len(), 1);
+
+    let snapshot = &snapshots[0];
+    assert_eq!(snapshot.r#type, "spot");
+    assert_eq!(snapshot.update_time.timestamp(), 1576281599);
+    assert_eq!(snapshot.data.total_asset_of_btc, 0.099427);
+    assert_eq!(snapshot.balance("BTC").unwrap().free, 0.09905021);
+    assert!(snapshot.balance("ETH").is_none());
+}
@@ -2,16 +2,20 @@
 
 use std::fmt;
 
+use common::config::ApiKeys;
 use hmac::{Hmac, Mac};
-use sha3::Sha3_384;
+use sha2::Sha384;
 
 use crate::error::Error;
 
-type HmacSha384 = Hmac<Sha3_384>;
+type HmacSha384 = Hmac<Sha384>;
 
 /// Bitfinex authentication
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub enum BitfinexAuth {
+    /// No authentication (public endpoints only)
+    #[default]
+    None,
     /// API Keys
     ApiKeys {
         /// API Key
@@ -27,6 +31,15 @@ impl fmt::Debug for BitfinexAuth {
     }
 }
 
+impl From<ApiKeys> for BitfinexAuth {
+    fn from(keys: ApiKeys) -> Self {
+        Self::ApiKeys {
+            api_key: keys.api_key,
+            api_secret: keys.api_secret,
+        }
+    }
+}
+
 impl BitfinexAuth {
     /// Construct API keys credential
     pub fn api_keys<K, S>(api_key: K, api_secret: S) -> Self
@@ -41,6 +54,12 @@ impl BitfinexAuth {
     }
 }
 
+/// Sign the payload (`/api/{path}{nonce}{body}`) with HMAC-SHA384, hex encoded
+///
+/// Bitfinex uses SHA-384 of the SHA-2 family (`crypto.createHmac('sha384', secret)` in the docs),
+/// not SHA3-384.
+///
+/// <https://docs.bitfinex.com/docs/rest-auth>
 pub(crate) fn sign_payload<S, P>(secret: S, payload: P) -> Result<String, Error>
 where
     S: AsRef<[u8]>,
@@ -52,3 +71,18 @@ where
     let signature: String = hex::encode(result.into_bytes());
     Ok(signature)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        let signature: String =
+            sign_payload("secret", "/api/v2/auth/r/wallets1700000000000000{}").unwrap();
+        assert_eq!(
+            signature,
+            "afbf78cd51a89892e09129309adef95416cd1975e061c03e37a7e5b9080a7a68ed081a16f21ef29e55493f0f61388a1e"
+        );
+    }
+}
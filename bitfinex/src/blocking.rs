@@ -0,0 +1,180 @@
+//! Blocking Bitfinex client
+
+use common::blocking::Runtime;
+
+use crate::client::BitfinexClient;
+use crate::error::Error;
+use crate::response::{
+    FundingCredit, FundingOffer, Movement, Order, Ticker, Trade, Wallet, WalletType,
+};
+
+impl BitfinexClient {
+    /// Get a blocking client sharing this client
+    ///
+    /// See [`BlockingBitfinexClient`].
+    pub fn blocking(&self) -> Result<BlockingBitfinexClient, Error> {
+        Ok(BlockingBitfinexClient {
+            client: self.clone(),
+            runtime: Runtime::new()?,
+        })
+    }
+}
+
+/// Blocking Bitfinex client
+///
+/// Thin wrapper that runs the [`BitfinexClient`] requests on an internal current-thread runtime.
+/// Must NOT be used from within an async context.
+#[derive(Debug, Clone)]
+pub struct BlockingBitfinexClient {
+    client: BitfinexClient,
+    runtime: Runtime,
+}
+
+impl BlockingBitfinexClient {
+    /// Get the async client
+    #[inline]
+    pub fn as_async(&self) -> &BitfinexClient {
+        &self.client
+    }
+
+    /// Check if the platform is operative (`true`) or in maintenance (`false`)
+    ///
+    /// See [`BitfinexClient::platform_status`].
+    #[inline]
+    pub fn platform_status(&self) -> Result<bool, Error> {
+        self.runtime.block_on(self.client.platform_status())
+    }
+
+    /// Check that the API is reachable and operative
+    ///
+    /// See [`BitfinexClient::healthcheck`].
+    #[inline]
+    pub fn healthcheck(&self) -> Result<(), Error> {
+        self.runtime.block_on(self.client.healthcheck())
+    }
+
+    /// Get wallets
+    ///
+    /// See [`BitfinexClient::wallets`].
+    #[inline]
+    pub fn wallets(&self) -> Result<Vec<Wallet>, Error> {
+        self.runtime.block_on(self.client.wallets())
+    }
+
+    /// Get exchange wallets
+    ///
+    /// See [`BitfinexClient::exchange_wallets`].
+    #[inline]
+    pub fn exchange_wallets(&self) -> Result<Vec<Wallet>, Error> {
+        self.runtime.block_on(self.client.exchange_wallets())
+    }
+
+    /// Get the wallet of the given type for a currency.
+    ///
+    /// See [`BitfinexClient::wallet`].
+    #[inline]
+    pub fn wallet(
+        &self,
+        wallet_type: &WalletType,
+        currency: &str,
+    ) -> Result<Option<Wallet>, Error> {
+        self.runtime
+            .block_on(self.client.wallet(wallet_type, currency))
+    }
+
+    /// Get the total balance of a currency, summed across all wallet types.
+    ///
+    /// See [`BitfinexClient::total_balance`].
+    #[inline]
+    pub fn total_balance(&self, currency: &str) -> Result<f64, Error> {
+        self.runtime.block_on(self.client.total_balance(currency))
+    }
+
+    /// Get all the non-zero balances, as `(currency, amount)` pairs
+    ///
+    /// See [`BitfinexClient::all_balances`].
+    #[inline]
+    pub fn all_balances(&self) -> Result<Vec<(String, f64)>, Error> {
+        self.runtime.block_on(self.client.all_balances())
+    }
+
+    /// Get a **bitcoin** deposit address.
+    ///
+    /// See [`BitfinexClient::bitcoin_deposit_address`].
+    #[inline]
+    pub fn bitcoin_deposit_address(&self) -> Result<String, Error> {
+        self.runtime.block_on(self.client.bitcoin_deposit_address())
+    }
+
+    /// Get **bitcoin** movements (deposit/withdrawal)
+    ///
+    /// See [`BitfinexClient::movements`].
+    #[inline]
+    pub fn movements(&self) -> Result<Vec<Movement>, Error> {
+        self.runtime.block_on(self.client.movements())
+    }
+
+    /// Get **bitcoin** trades (buy/sell)
+    ///
+    /// See [`BitfinexClient::trades`].
+    #[inline]
+    pub fn trades(&self) -> Result<Vec<Trade>, Error> {
+        self.runtime.block_on(self.client.trades())
+    }
+
+    /// Clear the response cache
+    ///
+    /// See [`BitfinexClient::clear_cache`].
+    #[inline]
+    pub fn clear_cache(&self) {
+        self.client.clear_cache()
+    }
+
+    /// Get the ticker of a trading pair (i.e., `tBTCUSD`)
+    ///
+    /// See [`BitfinexClient::ticker`].
+    #[inline]
+    pub fn ticker(&self, symbol: &str) -> Result<Ticker, Error> {
+        self.runtime.block_on(self.client.ticker(symbol))
+    }
+
+    /// Get the exchange-tradable pairs
+    ///
+    /// See [`BitfinexClient::symbols`].
+    #[inline]
+    pub fn symbols(&self) -> Result<Vec<String>, Error> {
+        self.runtime.block_on(self.client.symbols())
+    }
+
+    /// Get active orders
+    ///
+    /// See [`BitfinexClient::active_orders`].
+    #[inline]
+    pub fn active_orders(&self) -> Result<Vec<Order>, Error> {
+        self.runtime.block_on(self.client.active_orders())
+    }
+
+    /// Get past (inactive) orders, optionally filtered by symbol
+    ///
+    /// See [`BitfinexClient::order_history`].
+    #[inline]
+    pub fn order_history(&self, symbol: Option<&str>) -> Result<Vec<Order>, Error> {
+        self.runtime.block_on(self.client.order_history(symbol))
+    }
+
+    /// Get active funding offers of a funding currency (i.e., `fUSD`)
+    ///
+    /// See [`BitfinexClient::funding_offers`].
+    #[inline]
+    pub fn funding_offers(&self, symbol: &str) -> Result<Vec<FundingOffer>, Error> {
+        self.runtime.block_on(self.client.funding_offers(symbol))
+    }
+
+    /// Get past funding credits of a funding currency (i.e., `fUSD`)
+    ///
+    /// See [`BitfinexClient::funding_credits`].
+    #[inline]
+    pub fn funding_credits(&self, symbol: &str) -> Result<Vec<FundingCredit>, Error> {
+        self.runtime.block_on(self.client.funding_credits(symbol))
+    }
+}
@@ -1,8 +1,13 @@
 //! Bitfinex client
 
 use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
+use common::cache::{CacheConfig, ResponseCache};
+use common::interceptor::{self, Interceptors};
+use common::rate_limit::SlidingWindow;
 use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
 use reqwest::{Client, Method, Response};
 use serde::de::DeserializeOwned;
@@ -14,16 +19,27 @@ use crate::constant::{
 };
 use crate::error::Error;
 use crate::request::DepositAddressRequest;
-use crate::response::{DepositAddress, Movement, Trade, Wallet};
+use crate::response::{
+    DepositAddress, FundingCredit, FundingOffer, Movement, Order, Ticker, Trade, Wallet, WalletType,
+};
 
 const BITCOIN_DEPOSIT_METHOD: &str = "bitcoin";
 const EXCHANGE_WALLET: &str = "exchange";
+const CACHE_TICKER: &str = "ticker";
+const CACHE_SYMBOLS: &str = "symbols";
 
 enum Api {
     DepositAddress,
     Wallets,
     Movements { currency: String },
     Trades,
+    ActiveOrders,
+    OrdersHistory { symbol: Option<String> },
+    FundingOffers { symbol: String },
+    FundingCredits { symbol: String },
+    Ticker { symbol: String },
+    ExchangePairs,
+    PlatformStatus,
 }
 
 impl Api {
@@ -35,6 +51,20 @@ impl Api {
                 Cow::Owned(format!("/v2/auth/r/movements/{currency}/hist"))
             }
             Self::Trades => Cow::Borrowed("/v2/auth/r/trades/hist"),
+            Self::ActiveOrders => Cow::Borrowed("/v2/auth/r/orders"),
+            Self::OrdersHistory { symbol } => match symbol {
+                Some(symbol) => Cow::Owned(format!("/v2/auth/r/orders/{symbol}/hist")),
+                None => Cow::Borrowed("/v2/auth/r/orders/hist"),
+            },
+            Self::FundingOffers { symbol } => {
+                Cow::Owned(format!("/v2/auth/r/funding/offers/{symbol}"))
+            }
+            Self::FundingCredits { symbol } => {
+                Cow::Owned(format!("/v2/auth/r/funding/credits/{symbol}/hist"))
+            }
+            Self::Ticker { symbol } => Cow::Owned(format!("/v2/ticker/{symbol}")),
+            Self::ExchangePairs => Cow::Borrowed("/v2/conf/pub:list:pair:exchange"),
+            Self::PlatformStatus => Cow::Borrowed("/v2/platform/status"),
         }
     }
 
@@ -44,8 +74,23 @@ impl Api {
             Self::Wallets => Method::POST,
             Self::Movements { .. } => Method::POST,
             Self::Trades => Method::POST,
+            Self::ActiveOrders => Method::POST,
+            Self::OrdersHistory { .. } => Method::POST,
+            Self::FundingOffers { .. } => Method::POST,
+            Self::FundingCredits { .. } => Method::POST,
+            Self::Ticker { .. } => Method::GET,
+            Self::ExchangePairs => Method::GET,
+            Self::PlatformStatus => Method::GET,
         }
     }
+
+    /// Public endpoints don't require authentication
+    fn is_public(&self) -> bool {
+        matches!(
+            self,
+            Self::Ticker { .. } | Self::ExchangePairs | Self::PlatformStatus
+        )
+    }
 }
 
 /// Bitfinex client
@@ -57,6 +102,12 @@ pub struct BitfinexClient {
     client: Client,
     /// Authentication
     auth: BitfinexAuth,
+    /// Response cache
+    cache: Arc<ResponseCache>,
+    /// Client-side rate limiter
+    rate_limiter: Option<Arc<SlidingWindow>>,
+    /// Request and response interceptors
+    interceptors: Interceptors,
 }
 
 impl BitfinexClient {
@@ -64,14 +115,70 @@ impl BitfinexClient {
     pub fn new(auth: BitfinexAuth) -> Result<Self, Error> {
         Ok(Self {
             root_url: Url::parse(API_ROOT_URL)?,
-            client: Client::builder()
-                .user_agent(USER_AGENT_NAME)
-                .timeout(Duration::from_secs(25))
-                .build()?,
+            client: build_http_client(HeaderMap::new())?,
             auth,
+            cache: Arc::new(ResponseCache::default()),
+            rate_limiter: None,
+            interceptors: Interceptors::default(),
         })
     }
 
+    /// Set a custom root URL (i.e., a proxy or a mock server)
+    #[inline]
+    pub fn with_root_url(mut self, root_url: Url) -> Self {
+        self.root_url = root_url;
+        self
+    }
+
+    /// Set response cache (default: disabled)
+    ///
+    /// Cacheable endpoints: `ticker`, `symbols`.
+    #[inline]
+    pub fn with_cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Arc::new(ResponseCache::new(cache));
+        self
+    }
+
+    /// Set headers added to every request (default: none)
+    ///
+    /// The client headers (i.e., authentication) win on conflict. Rebuilds the HTTP client.
+    #[inline]
+    pub fn with_default_headers(mut self, headers: HeaderMap) -> Result<Self, Error> {
+        self.client = build_http_client(headers)?;
+        Ok(self)
+    }
+
+    /// Set request and response interceptors (default: none)
+    ///
+    /// The request interceptor runs after the authentication headers are added.
+    #[inline]
+    pub fn with_interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
+    /// Set a client-side rate limiter (default: disabled)
+    ///
+    /// Each request takes a weight of 1. Share the same limiter across clients to enforce a
+    /// common limit (i.e., 90 requests per minute).
+    #[inline]
+    pub fn with_rate_limiter(mut self, limiter: Arc<SlidingWindow>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Clear the response cache
+    #[inline]
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    fn build_public_headers() -> HeaderMap {
+        let mut headers = HeaderMap::with_capacity(1);
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers
+    }
+
     fn build_headers(&self, api: &Api, payload: &str) -> Result<HeaderMap, Error> {
         let nonce: u64 = generate_nonce();
 
@@ -93,6 +200,7 @@ impl BitfinexClient {
         );
 
         match &self.auth {
+            BitfinexAuth::None => return Err(Error::ApiKeysNotAvailable),
             BitfinexAuth::ApiKeys {
                 api_key,
                 api_secret,
@@ -114,7 +222,18 @@ impl BitfinexClient {
         Ok(headers)
     }
 
+    /// Send the request and parse the response.
+    ///
+    /// The inner future is boxed, so the public methods stay small (see `clippy::large_futures`).
+    #[inline]
     async fn call_api<T>(&self, api: Api, payload: Option<String>) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        Box::pin(self.send_and_parse(api, payload)).await
+    }
+
+    async fn send_and_parse<T>(&self, api: Api, payload: Option<String>) -> Result<T, Error>
     where
         T: DeserializeOwned,
     {
@@ -122,8 +241,17 @@ impl BitfinexClient {
         let method: Method = api.http_method();
         let payload: String = payload.unwrap_or_default();
 
-        // Build headers
-        let headers: HeaderMap = self.build_headers(&api, &payload)?;
+        // Wait for the rate limiter before generating the nonce
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire(1).await;
+        }
+
+        // Build headers (public endpoints are not signed)
+        let headers: HeaderMap = if api.is_public() {
+            Self::build_public_headers()
+        } else {
+            self.build_headers(&api, &payload)?
+        };
 
         // Send request
         let mut request = self.client.request(method, url).headers(headers);
@@ -132,13 +260,42 @@ impl BitfinexClient {
             request = request.body(payload);
         }
 
-        let response: Response = request.send().await?;
+        let response: Response = self.interceptors.send(request).await?;
+
+        let response: Response = interceptor::error_for_server_status(response).await?;
 
         // Propagate error if any
-        let response: Response = response.error_for_status()?;
+        let response: Response = interceptor::error_for_status(response)?;
 
         // Deserialize response
-        Ok(response.json().await?)
+        Ok(interceptor::json(response).await?)
+    }
+
+    /// Check if the platform is operative (`true`) or in maintenance (`false`)
+    ///
+    /// Public endpoint: doesn't require API keys.
+    ///
+    /// <https://docs.bitfinex.com/reference/rest-public-platform-status>
+    pub async fn platform_status(&self) -> Result<bool, Error> {
+        let status: Vec<u8> = self.call_api(Api::PlatformStatus, None).await?;
+        Ok(status.first() == Some(&1))
+    }
+
+    /// Check that the API is reachable and operative
+    ///
+    /// Returns [`Error::Maintenance`] if the platform is in maintenance (see
+    /// [`BitfinexClient::platform_status`]).
+    /// If API keys are configured, they are verified by fetching the wallets.
+    pub async fn healthcheck(&self) -> Result<(), Error> {
+        if !self.platform_status().await? {
+            return Err(Error::Maintenance);
+        }
+
+        if let BitfinexAuth::ApiKeys { .. } = self.auth {
+            self.wallets().await?;
+        }
+
+        Ok(())
     }
 
     /// Get wallets
@@ -149,6 +306,38 @@ impl BitfinexClient {
         self.call_api(Api::Wallets, None).await
     }
 
+    /// Get exchange wallets
+    pub async fn exchange_wallets(&self) -> Result<Vec<Wallet>, Error> {
+        let wallets: Vec<Wallet> = self.wallets().await?;
+        Ok(wallets.into_iter().filter(Wallet::is_exchange).collect())
+    }
+
+    /// Get the wallet of the given type for a currency.
+    ///
+    /// Returns `None` if no such wallet exists.
+    pub async fn wallet(
+        &self,
+        wallet_type: &WalletType,
+        currency: &str,
+    ) -> Result<Option<Wallet>, Error> {
+        let wallets: Vec<Wallet> = self.wallets().await?;
+        Ok(find_wallet(wallets, wallet_type, currency))
+    }
+
+    /// Get the total balance of a currency, summed across all wallet types.
+    pub async fn total_balance(&self, currency: &str) -> Result<f64, Error> {
+        let wallets: Vec<Wallet> = self.wallets().await?;
+        Ok(sum_balance(&wallets, currency))
+    }
+
+    /// Get all the non-zero balances, as `(currency, amount)` pairs
+    ///
+    /// Balances are summed across all wallet types.
+    pub async fn all_balances(&self) -> Result<Vec<(String, f64)>, Error> {
+        let wallets: Vec<Wallet> = self.wallets().await?;
+        Ok(sum_balances(&wallets))
+    }
+
     /// Get a **bitcoin** deposit address.
     pub async fn bitcoin_deposit_address(&self) -> Result<String, Error> {
         let payload: String = serde_json::to_string(&DepositAddressRequest {
@@ -178,7 +367,7 @@ impl BitfinexClient {
         .await
     }
 
-    /// Get **bitcoin** trades (buy/sell)
+    /// Get **bitcoin** trades (buy/sell), deduplicated and sorted by timestamp (ascending)
     #[inline]
     pub async fn trades(&self) -> Result<Vec<Trade>, Error> {
         let trades: Vec<Trade> = self.call_api(Api::Trades, None).await?;
@@ -191,8 +380,145 @@ impl BitfinexClient {
             })
             .collect();
 
-        Ok(trades)
+        Ok(sort_and_dedup_trades(trades))
+    }
+
+    /// Get the ticker of a trading pair (i.e., `tBTCUSD`)
+    ///
+    /// Public endpoint: doesn't require API keys. Cached if enabled in
+    /// [`BitfinexClient::with_cache`].
+    ///
+    /// <https://docs.bitfinex.com/reference/rest-public-ticker>
+    pub async fn ticker(&self, symbol: &str) -> Result<Ticker, Error> {
+        if let Some(ticker) = self.cache.get(CACHE_TICKER, symbol) {
+            return Ok(ticker);
+        }
+
+        let ticker: Ticker = self
+            .call_api(
+                Api::Ticker {
+                    symbol: String::from(symbol),
+                },
+                None,
+            )
+            .await?;
+        self.cache.insert(CACHE_TICKER, symbol, &ticker);
+        Ok(ticker)
+    }
+
+    /// Get the exchange-tradable pairs (i.e., `BTCUSD`, `TESTBTC:TESTUSD`)
+    ///
+    /// Pairs are returned without the `t` prefix used by the trading symbols. Public endpoint:
+    /// doesn't require API keys. Cached if enabled in [`BitfinexClient::with_cache`].
+    ///
+    /// <https://docs.bitfinex.com/reference/rest-public-conf>
+    pub async fn symbols(&self) -> Result<Vec<String>, Error> {
+        if let Some(symbols) = self.cache.get(CACHE_SYMBOLS, "") {
+            return Ok(symbols);
+        }
+
+        let configs: Vec<Vec<String>> = self.call_api(Api::ExchangePairs, None).await?;
+        let symbols: Vec<String> = configs.into_iter().next().unwrap_or_default();
+        self.cache.insert(CACHE_SYMBOLS, "", &symbols);
+        Ok(symbols)
+    }
+
+    /// Get active orders
+    ///
+    /// <https://docs.bitfinex.com/reference/rest-auth-retrieve-orders>
+    #[inline]
+    pub async fn active_orders(&self) -> Result<Vec<Order>, Error> {
+        self.call_api(Api::ActiveOrders, None).await
+    }
+
+    /// Get past (inactive) orders, optionally filtered by symbol (i.e., `tBTCUSD`)
+    ///
+    /// <https://docs.bitfinex.com/reference/rest-auth-orders-history>
+    #[inline]
+    pub async fn order_history(&self, symbol: Option<&str>) -> Result<Vec<Order>, Error> {
+        self.call_api(
+            Api::OrdersHistory {
+                symbol: symbol.map(String::from),
+            },
+            None,
+        )
+        .await
+    }
+
+    /// Get active funding offers of a funding currency (i.e., `fUSD`)
+    ///
+    /// <https://docs.bitfinex.com/reference/rest-auth-funding-offers>
+    #[inline]
+    pub async fn funding_offers(&self, symbol: &str) -> Result<Vec<FundingOffer>, Error> {
+        self.call_api(
+            Api::FundingOffers {
+                symbol: String::from(symbol),
+            },
+            None,
+        )
+        .await
+    }
+
+    /// Get past funding credits of a funding currency (i.e., `fUSD`)
+    ///
+    /// <https://docs.bitfinex.com/reference/rest-auth-funding-credits-hist>
+    #[inline]
+    pub async fn funding_credits(&self, symbol: &str) -> Result<Vec<FundingCredit>, Error> {
+        self.call_api(
+            Api::FundingCredits {
+                symbol: String::from(symbol),
+            },
+            None,
+        )
+        .await
+    }
+}
+
+fn build_http_client(default_headers: HeaderMap) -> Result<Client, Error> {
+    Ok(Client::builder()
+        .default_headers(default_headers)
+        .user_agent(USER_AGENT_NAME)
+        .timeout(Duration::from_secs(25))
+        .build()?)
+}
+
+fn find_wallet(wallets: Vec<Wallet>, wallet_type: &WalletType, currency: &str) -> Option<Wallet> {
+    wallets
+        .into_iter()
+        .find(|wallet| &wallet.r#type == wallet_type && wallet.currency == currency)
+}
+
+fn sum_balance(wallets: &[Wallet], currency: &str) -> f64 {
+    wallets
+        .iter()
+        .filter(|wallet| wallet.currency == currency)
+        .map(|wallet| wallet.balance)
+        .sum()
+}
+
+/// Remove the trades with a duplicated ID and sort them by timestamp, ascending
+fn sort_and_dedup_trades(mut trades: Vec<Trade>) -> Vec<Trade> {
+    let mut seen: HashSet<u64> = HashSet::with_capacity(trades.len());
+    trades.retain(|trade| seen.insert(trade.id));
+    trades.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then(a.id.cmp(&b.id)));
+    trades
+}
+
+fn sum_balances(wallets: &[Wallet]) -> Vec<(String, f64)> {
+    let mut balances: Vec<(String, f64)> = Vec::new();
+
+    for wallet in wallets {
+        match balances
+            .iter_mut()
+            .find(|(currency, _)| *currency == wallet.currency)
+        {
+            Some((_, amount)) => *amount += wallet.balance,
+            None => balances.push((wallet.currency.clone(), wallet.balance)),
+        }
     }
+
+    balances.retain(|(_, amount)| *amount != 0.0);
+    balances
 }
 
 fn generate_nonce() -> u64 {
@@ -201,3 +527,139 @@ fn generate_nonce() -> u64 {
         .unwrap_or_default()
         .as_millis() as u64
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Map;
+
+    use super::*;
+
+    /// Max size (bytes) of the futures returned by the public methods
+    const MAX_FUTURE_SIZE: usize = 256;
+
+    fn future_size<F>(_: &F) -> usize
+    where
+        F: Future,
+    {
+        size_of::<F>()
+    }
+
+    #[test]
+    fn test_futures_size() {
+        let client = BitfinexClient::new(BitfinexAuth::ApiKeys {
+            api_key: String::from("key"),
+            api_secret: String::from("secret"),
+        })
+        .unwrap();
+
+        let sizes: [(&str, usize); 12] = [
+            ("wallets", future_size(&client.wallets())),
+            ("exchange_wallets", future_size(&client.exchange_wallets())),
+            (
+                "wallet",
+                future_size(&client.wallet(&WalletType::Exchange, "BTC")),
+            ),
+            ("total_balance", future_size(&client.total_balance("BTC"))),
+            (
+                "bitcoin_deposit_address",
+                future_size(&client.bitcoin_deposit_address()),
+            ),
+            ("movements", future_size(&client.movements())),
+            ("trades", future_size(&client.trades())),
+            ("ticker", future_size(&client.ticker("tBTCUSD"))),
+            ("active_orders", future_size(&client.active_orders())),
+            ("order_history", future_size(&client.order_history(None))),
+            (
+                "funding_offers",
+                future_size(&client.funding_offers("fUSD")),
+            ),
+            (
+                "funding_credits",
+                future_size(&client.funding_credits("fUSD")),
+            ),
+        ];
+
+        for (name, size) in sizes {
+            assert!(
+                size <= MAX_FUTURE_SIZE,
+                "`{name}` future is {size} bytes (max {MAX_FUTURE_SIZE})"
+            );
+        }
+    }
+
+    fn make_wallet(r#type: WalletType, currency: &str, balance: f64) -> Wallet {
+        Wallet {
+            r#type,
+            currency: currency.to_string(),
+            balance,
+            unsettled_interest: 0.0,
+            available_balance: balance,
+            last_change: String::new(),
+            last_change_metadata: Map::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_wallet() {
+        let wallets = vec![
+            make_wallet(WalletType::Exchange, "BTC", 0.5),
+            make_wallet(WalletType::Margin, "BTC", 0.25),
+            make_wallet(WalletType::Exchange, "USD", 100.0),
+        ];
+
+        let wallet = find_wallet(wallets.clone(), &WalletType::Margin, "BTC").unwrap();
+        assert_eq!(wallet.balance, 0.25);
+
+        assert_eq!(find_wallet(wallets, &WalletType::Funding, "BTC"), None);
+    }
+
+    #[test]
+    fn test_sum_balance() {
+        let wallets = vec![
+            make_wallet(WalletType::Exchange, "BTC", 0.5),
+            make_wallet(WalletType::Margin, "BTC", 0.25),
+            make_wallet(WalletType::Funding, "BTC", 0.125),
+            make_wallet(WalletType::Exchange, "USD", 100.0),
+        ];
+
+        assert_eq!(sum_balance(&wallets, "BTC"), 0.875);
+        assert_eq!(sum_balance(&wallets, "ETH"), 0.0);
+    }
+
+    #[test]
+    fn test_sort_and_dedup_trades() {
+        let trade = |id: u64, mts: i64| -> Trade {
+            serde_json::from_str(&format!(
+                r#"[{id},"tBTCUSD",{mts},1,0.1,100,"EXCHANGE MARKET",100,-1,-0.01,"USD",null]"#
+            ))
+            .unwrap()
+        };
+
+        let trades = vec![
+            trade(3, 3000),
+            trade(1, 1000),
+            trade(2, 2000),
+            trade(1, 1000),
+        ];
+        let ids: Vec<u64> = sort_and_dedup_trades(trades)
+            .into_iter()
+            .map(|trade| trade.id)
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_sum_balances() {
+        let wallets = vec![
+            make_wallet(WalletType::Exchange, "BTC", 0.5),
+            make_wallet(WalletType::Exchange, "USD", 100.0),
+            make_wallet(WalletType::Margin, "BTC", 0.25),
+            make_wallet(WalletType::Funding, "ETH", 0.0),
+        ];
+
+        assert_eq!(
+            sum_balances(&wallets),
+            vec![(String::from("BTC"), 0.75), (String::from("USD"), 100.0)]
+        );
+    }
+}
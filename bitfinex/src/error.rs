@@ -1,14 +1,23 @@
 //! Bitfinex error
 
+use common::interceptor::{RequestError, ServerError};
 use reqwest::header::InvalidHeaderValue;
 use thiserror::Error;
 
 /// Bitfinex error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Reqwest error
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
+    /// Request error, with the method and path of the request
+    #[error(transparent)]
+    Request(#[from] RequestError),
+    /// Blocking runtime error
+    #[cfg(feature = "blocking")]
+    #[error(transparent)]
+    Runtime(#[from] std::io::Error),
     /// Invalid header error
     #[error(transparent)]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
@@ -21,6 +30,15 @@ pub enum Error {
     /// Json error
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+    /// API keys not available
+    #[error("API keys not available")]
+    ApiKeysNotAvailable,
+    /// Server error (HTTP 5xx)
+    #[error(transparent)]
+    ServerError(#[from] ServerError),
+    /// Platform in maintenance
+    #[error("platform in maintenance")]
+    Maintenance,
     /// Missing deposit address in response
     #[error("missing deposit address")]
     MissingDepositAddress,
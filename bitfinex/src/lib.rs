@@ -6,6 +6,8 @@
 #![warn(rustdoc::bare_urls)]
 
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
 mod constant;
 pub mod error;
@@ -5,9 +5,16 @@
 #![allow(ambiguous_glob_reexports)]
 #![doc(hidden)]
 
+pub use ::common::amount::{Fee, Price, Quantity};
+pub use ::common::cache::CacheConfig;
+pub use ::common::interceptor::{Interceptors, RequestError, ServerError};
+pub use ::common::rate_limit::SlidingWindow;
+pub use ::reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 pub use ::url::{self, *};
 
 pub use crate::auth::{self, *};
+#[cfg(feature = "blocking")]
+pub use crate::blocking::{self, *};
 pub use crate::client::{self, *};
 pub use crate::error::{self, *};
 pub use crate::response::{self, *};
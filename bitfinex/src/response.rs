@@ -1,7 +1,14 @@
 //! Bitfinex responses
 
-use chrono::{DateTime, Utc};
-use common::deser::deserialize_unix_timestamp_milliseconds_to_utc_seconds;
+use chrono::{DateTime, Duration, Utc};
+use common::amount::{Fee, Price, Quantity};
+use common::deser::{
+    deserialize_string_or_number_to_u64, deserialize_string_to_f64,
+    deserialize_unix_timestamp_milliseconds_to_utc_seconds,
+    unix_timestamp_milliseconds_to_utc_seconds,
+};
+use common::exchange::Exchange;
+use common::trade::{NormalizedTrade, TradeSide};
 use serde::Deserialize;
 use serde_json::{Map, Value};
 
@@ -11,6 +18,21 @@ pub(crate) struct DepositAddress {
     pub(crate) address: String,
 }
 
+/// Bitfinex wallet type
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WalletType {
+    /// Exchange wallet
+    Exchange,
+    /// Margin wallet
+    Margin,
+    /// Funding wallet
+    Funding,
+    /// Wallet type not known by this library
+    #[serde(untagged)]
+    Other(String),
+}
+
 /// Bitfinex wallet
 ///
 /// <https://docs.bitfinex.com/reference/rest-auth-wallets>
@@ -18,7 +40,7 @@ pub(crate) struct DepositAddress {
 #[serde(from = "WalletArray")]
 pub struct Wallet {
     /// Wallet type
-    pub r#type: String,
+    pub r#type: WalletType,
     /// Currency
     pub currency: String,
     /// Balance
@@ -33,6 +55,32 @@ pub struct Wallet {
     pub last_change_metadata: Map<String, Value>,
 }
 
+impl Wallet {
+    /// Check if this is an exchange wallet
+    #[inline]
+    pub fn is_exchange(&self) -> bool {
+        self.r#type == WalletType::Exchange
+    }
+
+    /// Check if this is a margin wallet
+    #[inline]
+    pub fn is_margin(&self) -> bool {
+        self.r#type == WalletType::Margin
+    }
+
+    /// Check if this is a funding wallet
+    #[inline]
+    pub fn is_funding(&self) -> bool {
+        self.r#type == WalletType::Funding
+    }
+
+    /// Get the typed details of the last change, parsed from the metadata
+    #[inline]
+    pub fn last_change_details(&self) -> LastChange {
+        LastChange::from_metadata(&self.last_change_metadata)
+    }
+}
+
 impl From<WalletArray> for Wallet {
     fn from(arr: WalletArray) -> Self {
         Wallet {
@@ -49,7 +97,7 @@ impl From<WalletArray> for Wallet {
 
 #[derive(Deserialize)]
 struct WalletArray(
-    String,             // type
+    WalletType,         // type
     String,             // currency
     f64,                // balance
     f64,                // unsettled_interest
@@ -58,11 +106,70 @@ struct WalletArray(
     Map<String, Value>, // trade_details
 );
 
+const TRADE_REASON: &str = "TRADE";
+
+/// Details of the last wallet change
+#[derive(Debug, Clone, PartialEq)]
+pub enum LastChange {
+    /// Trade
+    Trade {
+        /// Order ID
+        order_id: u64,
+        /// Order ID of the other side of the trade
+        order_id_oppo: Option<u64>,
+        /// Trade price
+        trade_price: f64,
+        /// Trade amount, negative for sells
+        trade_amount: f64,
+        /// Client order ID
+        order_cid: Option<u64>,
+        /// Group order ID
+        order_gid: Option<u64>,
+    },
+    /// Other reasons, or trade details that can't be parsed
+    Other(Map<String, Value>),
+}
+
+impl LastChange {
+    /// Parse the wallet metadata, based on the `reason` key
+    pub fn from_metadata(metadata: &Map<String, Value>) -> Self {
+        if metadata.get("reason").and_then(Value::as_str) == Some(TRADE_REASON) {
+            if let Ok(trade) =
+                serde_json::from_value::<TradeMetadata>(Value::Object(metadata.clone()))
+            {
+                return Self::Trade {
+                    order_id: trade.order_id,
+                    order_id_oppo: trade.order_id_oppo,
+                    trade_price: trade.trade_price,
+                    trade_amount: trade.trade_amount,
+                    order_cid: trade.order_cid,
+                    order_gid: trade.order_gid,
+                };
+            }
+        }
+
+        Self::Other(metadata.clone())
+    }
+}
+
+#[derive(Deserialize)]
+struct TradeMetadata {
+    #[serde(deserialize_with = "deserialize_string_or_number_to_u64")]
+    order_id: u64,
+    order_id_oppo: Option<u64>,
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    trade_price: f64,
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    trade_amount: f64,
+    order_cid: Option<u64>,
+    order_gid: Option<u64>,
+}
+
 /// Bitfinex movement (Deposit/Withdrawal)
 ///
 /// <https://docs.bitfinex.com/reference/rest-auth-movements>
 #[derive(Debug, Clone, PartialEq, Deserialize)]
-#[serde(from = "MovementArray")]
+#[serde(try_from = "Vec<Value>")]
 pub struct Movement {
     /// Movement identifier
     pub id: u64,
@@ -90,6 +197,51 @@ pub struct Movement {
     pub withdraw_transaction_note: Option<String>,
 }
 
+impl Movement {
+    /// Get when the movement started
+    #[inline]
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.mts_started
+    }
+
+    /// Get when the movement was last updated
+    #[inline]
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.mts_updated
+    }
+
+    /// Get the time elapsed between the start and the last update
+    #[inline]
+    pub fn processing_duration(&self) -> Duration {
+        self.mts_updated - self.mts_started
+    }
+}
+
+/// Number of fields in a movement array, placeholders included
+const MOVEMENT_ARRAY_LEN: usize = 22;
+
+impl TryFrom<Vec<Value>> for Movement {
+    type Error = String;
+
+    fn try_from(mut arr: Vec<Value>) -> Result<Self, Self::Error> {
+        // Every field is positional: refuse shorter arrays, whose values would be shifted.
+        // Bitfinex appends new columns at the end, so the extra ones are ignored.
+        if arr.len() < MOVEMENT_ARRAY_LEN {
+            return Err(format!(
+                "unexpected movement array length {}, expected at least {MOVEMENT_ARRAY_LEN}",
+                arr.len()
+            ));
+        }
+
+        arr.truncate(MOVEMENT_ARRAY_LEN);
+
+        let arr: MovementArray = serde_json::from_value(Value::Array(arr))
+            .map_err(|e| format!("invalid movement array: {e}"))?;
+
+        Ok(Self::from(arr))
+    }
+}
+
 impl From<MovementArray> for Movement {
     fn from(arr: MovementArray) -> Self {
         Movement {
@@ -153,23 +305,64 @@ pub struct Trade {
     /// Order id
     pub order_id: u64,
     /// Positive means buy, negative means sell
-    pub amount: f64,
+    pub amount: Quantity,
     /// Execution price
-    pub price: f64,
+    pub price: Price,
     /// Order type
     pub order_type: String,
     /// Order price
-    pub order_price: f64,
+    pub order_price: Price,
     /// Whether the trade was a maker
     pub is_maker: bool,
-    /// Fee
-    pub fee: f64,
+    /// Fee (negative when charged, positive for rebates)
+    pub fee: Fee,
     /// Fee currency
     pub fee_currency: String,
     /// Client Order ID
     pub cid: Option<u64>,
 }
 
+impl Trade {
+    /// Get the fee and its currency
+    ///
+    /// The fee is positive when charged and negative for rebates (Bitfinex uses the opposite
+    /// sign).
+    #[inline]
+    pub fn normalized_fee(&self) -> (Fee, &str) {
+        (Fee(-*self.fee), &self.fee_currency)
+    }
+
+    /// Convert to a [`NormalizedTrade`]
+    #[inline]
+    pub fn into_normalized(self) -> NormalizedTrade {
+        NormalizedTrade::from(self)
+    }
+}
+
+impl From<Trade> for NormalizedTrade {
+    fn from(trade: Trade) -> Self {
+        let base_qty: f64 = trade.amount.abs();
+        let price: f64 = *trade.price;
+
+        Self {
+            exchange: Exchange::Bitfinex,
+            symbol: trade.symbol,
+            side: if *trade.amount < 0.0 {
+                TradeSide::Sell
+            } else {
+                TradeSide::Buy
+            },
+            base_qty: Quantity(base_qty),
+            quote_qty: Quantity(base_qty * price),
+            price: trade.price,
+            fee: Fee(-*trade.fee),
+            fee_currency: trade.fee_currency,
+            timestamp: trade.timestamp,
+            trade_id: trade.id.to_string(),
+        }
+    }
+}
+
 impl From<TradeArray> for Trade {
     fn from(arr: TradeArray) -> Self {
         Trade {
@@ -177,12 +370,12 @@ impl From<TradeArray> for Trade {
             symbol: arr.1,
             timestamp: arr.2,
             order_id: arr.3,
-            amount: arr.4,
-            price: arr.5,
+            amount: Quantity(arr.4),
+            price: Price(arr.5),
             order_type: arr.6,
-            order_price: arr.7,
+            order_price: Price(arr.7),
             is_maker: arr.8 == 1,
-            fee: arr.9,
+            fee: Fee(arr.9),
             fee_currency: arr.10,
             cid: arr.11,
         }
@@ -206,6 +399,312 @@ struct TradeArray(
     Option<u64>, // CID
 );
 
+/// Ticker of a trading pair
+///
+/// <https://docs.bitfinex.com/reference/rest-public-ticker>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "TickerArray")]
+pub struct Ticker {
+    /// Price of last highest bid
+    pub bid: f64,
+    /// Sum of the 25 highest bid sizes
+    pub bid_size: f64,
+    /// Price of last lowest ask
+    pub ask: f64,
+    /// Sum of the 25 lowest ask sizes
+    pub ask_size: f64,
+    /// Amount that the last price has changed since yesterday
+    pub daily_change: f64,
+    /// Relative price change since yesterday (`0.05` means 5%)
+    pub daily_change_relative: f64,
+    /// Price of the last trade
+    pub last_price: f64,
+    /// Daily volume
+    pub volume: f64,
+    /// Daily high
+    pub high: f64,
+    /// Daily low
+    pub low: f64,
+}
+
+impl From<TickerArray> for Ticker {
+    fn from(arr: TickerArray) -> Self {
+        Self {
+            bid: arr.0,
+            bid_size: arr.1,
+            ask: arr.2,
+            ask_size: arr.3,
+            daily_change: arr.4,
+            daily_change_relative: arr.5,
+            last_price: arr.6,
+            volume: arr.7,
+            high: arr.8,
+            low: arr.9,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TickerArray(
+    f64, // BID
+    f64, // BID_SIZE
+    f64, // ASK
+    f64, // ASK_SIZE
+    f64, // DAILY_CHANGE
+    f64, // DAILY_CHANGE_RELATIVE
+    f64, // LAST_PRICE
+    f64, // VOLUME
+    f64, // HIGH
+    f64, // LOW
+);
+
+/// Bitfinex order
+///
+/// <https://docs.bitfinex.com/reference/rest-auth-retrieve-orders>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "OrderArray")]
+pub struct Order {
+    /// Order identifier
+    pub id: u64,
+    /// Group ID
+    pub gid: Option<u64>,
+    /// Client Order ID
+    pub cid: Option<u64>,
+    /// Symbol
+    pub symbol: String,
+    /// Order created at
+    pub mts_create: DateTime<Utc>,
+    /// Order last updated at
+    pub mts_update: DateTime<Utc>,
+    /// Remaining amount (positive means buy, negative means sell)
+    pub amount: f64,
+    /// Original amount
+    pub amount_orig: f64,
+    /// Order type
+    pub order_type: String,
+    /// Order status (i.e., `ACTIVE`, `EXECUTED @ PRICE(AMOUNT)`, `CANCELED`)
+    pub status: String,
+    /// Price
+    pub price: f64,
+    /// Average price
+    pub price_avg: f64,
+}
+
+impl From<OrderArray> for Order {
+    fn from(arr: OrderArray) -> Self {
+        Order {
+            id: arr.0,
+            gid: arr.1,
+            cid: arr.2,
+            symbol: arr.3,
+            mts_create: arr.4,
+            mts_update: arr.5,
+            amount: arr.6,
+            amount_orig: arr.7,
+            order_type: arr.8,
+            status: arr.13,
+            price: arr.16,
+            price_avg: arr.17,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct OrderArray(
+    u64,         // ID
+    Option<u64>, // GID
+    Option<u64>, // CID
+    String,      // SYMBOL
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    DateTime<Utc>, // MTS_CREATE
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    DateTime<Utc>, // MTS_UPDATE
+    f64,         // AMOUNT
+    f64,         // AMOUNT_ORIG
+    String,      // ORDER_TYPE
+    Option<Value>, // TYPE_PREV
+    Option<Value>, // MTS_TIF
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // FLAGS
+    String,      // ORDER_STATUS
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    f64,         // PRICE
+    f64,         // PRICE_AVG
+    Option<Value>, // PRICE_TRAILING
+    Option<Value>, // PRICE_AUX_LIMIT
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // NOTIFY
+    Option<Value>, // HIDDEN
+    Option<Value>, // PLACED_ID
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // ROUTING
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // META
+);
+
+/// Funding offer
+///
+/// <https://docs.bitfinex.com/reference/rest-auth-funding-offers>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "FundingOfferArray")]
+pub struct FundingOffer {
+    /// Offer identifier
+    pub id: u64,
+    /// Funding currency symbol (i.e., `fUSD`)
+    pub symbol: String,
+    /// Offer created at
+    pub mts_created: DateTime<Utc>,
+    /// Offer last updated at
+    pub mts_updated: DateTime<Utc>,
+    /// Remaining amount
+    pub amount: f64,
+    /// Original amount
+    pub amount_orig: f64,
+    /// Offer type (i.e., `LIMIT`)
+    pub offer_type: String,
+    /// Offer status (i.e., `ACTIVE`, `PARTIALLY FILLED`)
+    pub status: String,
+    /// Daily rate
+    pub rate: f64,
+    /// Period, in days
+    pub period: u32,
+}
+
+impl From<FundingOfferArray> for FundingOffer {
+    fn from(arr: FundingOfferArray) -> Self {
+        FundingOffer {
+            id: arr.0,
+            symbol: arr.1,
+            mts_created: arr.2,
+            mts_updated: arr.3,
+            amount: arr.4,
+            amount_orig: arr.5,
+            offer_type: arr.6,
+            status: arr.10,
+            rate: arr.14,
+            period: arr.15,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct FundingOfferArray(
+    u64,    // ID
+    String, // SYMBOL
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    DateTime<Utc>, // MTS_CREATED
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    DateTime<Utc>, // MTS_UPDATED
+    f64,    // AMOUNT
+    f64,    // AMOUNT_ORIG
+    String, // OFFER_TYPE
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // FLAGS
+    String, // OFFER_STATUS
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    f64,    // RATE
+    u32,    // PERIOD
+    Option<Value>, // NOTIFY
+    Option<Value>, // HIDDEN
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // RENEW
+    Option<Value>, // PLACEHOLDER
+);
+
+/// Funding credit (funds used in active positions)
+///
+/// <https://docs.bitfinex.com/reference/rest-auth-funding-credits-hist>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "FundingCreditArray")]
+pub struct FundingCredit {
+    /// Credit identifier
+    pub id: u64,
+    /// Funding currency symbol (i.e., `fUSD`)
+    pub symbol: String,
+    /// Side (`1`: lender, `0`: lender and borrower, `-1`: borrower)
+    pub side: i8,
+    /// Credit created at
+    pub mts_create: DateTime<Utc>,
+    /// Credit last updated at
+    pub mts_update: DateTime<Utc>,
+    /// Amount
+    pub amount: f64,
+    /// Credit status (i.e., `ACTIVE`, `CLOSED`)
+    pub status: String,
+    /// Rate type (`FIXED` or `VAR`)
+    pub rate_type: Option<String>,
+    /// Daily rate
+    pub rate: f64,
+    /// Period, in days
+    pub period: u32,
+    /// Credit opened at
+    pub mts_opening: DateTime<Utc>,
+    /// Last payout at
+    pub mts_last_payout: Option<DateTime<Utc>>,
+    /// Pair of the position funded by the credit (i.e., `tBTCUSD`)
+    pub position_pair: Option<String>,
+}
+
+impl From<FundingCreditArray> for FundingCredit {
+    fn from(arr: FundingCreditArray) -> Self {
+        FundingCredit {
+            id: arr.0,
+            symbol: arr.1,
+            side: arr.2,
+            mts_create: arr.3,
+            mts_update: arr.4,
+            amount: arr.5,
+            status: arr.7,
+            rate_type: arr.8,
+            rate: arr.11,
+            period: arr.12,
+            mts_opening: arr.13,
+            mts_last_payout: arr.14.and_then(unix_timestamp_milliseconds_to_utc_seconds),
+            position_pair: arr.21,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct FundingCreditArray(
+    u64,    // ID
+    String, // SYMBOL
+    i8,     // SIDE
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    DateTime<Utc>, // MTS_CREATE
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    DateTime<Utc>, // MTS_UPDATE
+    f64,    // AMOUNT
+    Option<Value>, // FLAGS
+    String, // STATUS
+    Option<String>, // RATE_TYPE
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // PLACEHOLDER
+    f64,    // RATE
+    u32,    // PERIOD
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    DateTime<Utc>, // MTS_OPENING
+    Option<i64>, // MTS_LAST_PAYOUT
+    Option<Value>, // NOTIFY
+    Option<Value>, // HIDDEN
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // RENEW
+    Option<Value>, // PLACEHOLDER
+    Option<Value>, // NO_CLOSE
+    Option<String>, // POSITION_PAIR
+);
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -252,7 +751,7 @@ mod tests {
         assert_eq!(
             wallet,
             Wallet {
-                r#type: String::from("exchange"),
+                r#type: WalletType::Exchange,
                 currency: String::from("UST"),
                 balance: 19788.6529257,
                 unsettled_interest: 0.0,
@@ -261,6 +760,56 @@ mod tests {
                 last_change_metadata: expected_metadata
             }
         );
+
+        assert_eq!(
+            wallet.last_change_details(),
+            LastChange::Trade {
+                order_id: 1189740779,
+                order_id_oppo: Some(1189785673),
+                trade_price: 11.696,
+                trade_amount: -2.0,
+                order_cid: Some(1598516362757),
+                order_gid: Some(1598516362629),
+            }
+        );
+    }
+
+    #[test]
+    fn test_last_change_other_reason() {
+        let mut metadata = Map::new();
+        metadata.insert("reason".to_string(), json!("DEPOSIT"));
+
+        assert_eq!(
+            LastChange::from_metadata(&metadata),
+            LastChange::Other(metadata.clone())
+        );
+
+        // Unexpected trade shape
+        metadata.insert("reason".to_string(), json!("TRADE"));
+        assert_eq!(
+            LastChange::from_metadata(&metadata),
+            LastChange::Other(metadata.clone())
+        );
+    }
+
+    #[test]
+    fn test_wallet_type_deserialization() {
+        let json = r#"["margin","BTC",0.5,0,0.5,"",{}]"#;
+        let wallet: Wallet = serde_json::from_str(json).unwrap();
+        assert_eq!(wallet.r#type, WalletType::Margin);
+        assert!(wallet.is_margin());
+        assert!(!wallet.is_exchange());
+
+        let json = r#"["funding","BTC",0.5,0,0.5,"",{}]"#;
+        let wallet: Wallet = serde_json::from_str(json).unwrap();
+        assert!(wallet.is_funding());
+
+        let json = r#"["contribution","BTC",0.5,0,0.5,"",{}]"#;
+        let wallet: Wallet = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            wallet.r#type,
+            WalletType::Other(String::from("contribution"))
+        );
     }
 
     #[test]
@@ -309,6 +858,84 @@ mod tests {
                 withdraw_transaction_note: Some(String::from("Purchase of 10000 pizzas")),
             }
         );
+
+        assert_eq!(movement.started_at().timestamp(), 1574175052);
+        assert_eq!(movement.updated_at().timestamp(), 1574181326);
+        assert_eq!(movement.processing_duration(), Duration::seconds(6274));
+    }
+
+    #[test]
+    fn test_movement_deserialization_matrix() {
+        let json = r#"[
+            [13105603,"ETH","ETHEREUM",null,null,1569348774000,1569348774000,null,null,"COMPLETED",null,null,0.26300954,-0.00135,null,null,"DESTINATION_ADDRESS",null,null,null,"TRANSACTION_ID",null],
+            [13293039,"BTC","BITCOIN",null,null,1574175052000,1574181326000,null,null,"CANCELED",null,null,-0.24,-0.00135,null,null,"DESTINATION_ADDRESS",null,null,null,"TRANSACTION_ID","Purchase of 10000 pizzas"],
+            [14021876,"UST","TETHERUSE",null,null,1601032405000,1601032873000,null,null,"PENDING REVIEW",null,null,-500,-1.5,null,null,"0x1234567890abcdef",null,null,null,"",null],
+            [14500012,"XRP","RIPPLE",null,null,1612345678000,1612345999000,null,null,"COMPLETED",null,null,120.5,0,null,null,"rDESTINATION","2349874",null,null,"TRANSACTION_ID",null]
+        ]"#;
+
+        let movements: Vec<Movement> = serde_json::from_str(json).unwrap();
+        assert_eq!(movements.len(), 4);
+
+        // Deposit
+        let deposit = &movements[0];
+        assert_eq!(deposit.currency, "ETH");
+        assert_eq!(deposit.status, "COMPLETED");
+        assert_eq!(deposit.amount, 0.26300954);
+        assert_eq!(deposit.fees, -0.00135);
+        assert_eq!(deposit.processing_duration(), Duration::zero());
+
+        // Canceled withdrawal with a note
+        let withdrawal = &movements[1];
+        assert_eq!(withdrawal.currency, "BTC");
+        assert!(withdrawal.amount < 0.0);
+        assert_eq!(
+            withdrawal.withdraw_transaction_note.as_deref(),
+            Some("Purchase of 10000 pizzas")
+        );
+
+        // Pending withdrawal, integer amount and no transaction id yet
+        let pending = &movements[2];
+        assert_eq!(pending.currency, "UST");
+        assert_eq!(pending.currency_name, "TETHERUSE");
+        assert_eq!(pending.status, "PENDING REVIEW");
+        assert_eq!(pending.amount, -500.0);
+        assert_eq!(pending.fees, -1.5);
+        assert!(pending.transaction_id.is_empty());
+
+        // Deposit with a payment id (destination tag)
+        let tagged = &movements[3];
+        assert_eq!(tagged.currency, "XRP");
+        assert_eq!(tagged.payment_id.as_deref(), Some("2349874"));
+        assert_eq!(tagged.fees, 0.0);
+    }
+
+    #[test]
+    fn test_movement_deserialization_rejects_unexpected_shape() {
+        // One missing column
+        let json = r#"[13105603,"ETH","ETHEREUM",null,null,1569348774000,1569348774000,null,null,"COMPLETED",null,null,0.26300954,-0.00135,null,null,"DESTINATION_ADDRESS",null,null,null,"TRANSACTION_ID"]"#;
+        let err = serde_json::from_str::<Movement>(json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unexpected movement array length 21, expected at least 22"),
+            "{err}"
+        );
+
+        // Right length, wrong type in a typed column
+        let json = r#"[13105603,"ETH","ETHEREUM",null,null,1569348774000,1569348774000,null,null,"COMPLETED",null,null,"0.26300954",-0.00135,null,null,"DESTINATION_ADDRESS",null,null,null,"TRANSACTION_ID",null]"#;
+        let err = serde_json::from_str::<Movement>(json).unwrap_err();
+        assert!(err.to_string().contains("invalid movement array"), "{err}");
+
+        // Not an array at all
+        assert!(serde_json::from_str::<Movement>(r#"{"id":13105603}"#).is_err());
+    }
+
+    #[test]
+    fn test_movement_deserialization_ignores_extra_columns() {
+        let json = r#"[13105603,"ETH","ETHEREUM",null,null,1569348774000,1569348774000,null,null,"COMPLETED",null,null,0.26300954,-0.00135,null,null,"DESTINATION_ADDRESS",null,null,null,"TRANSACTION_ID",null,"NEW_COLUMN",0]"#;
+        let movement: Movement = serde_json::from_str(json).unwrap();
+        assert_eq!(movement.id, 13105603);
+        assert_eq!(movement.transaction_id, "TRANSACTION_ID");
+        assert_eq!(movement.withdraw_transaction_note, None);
     }
 
     #[test]
@@ -337,15 +964,144 @@ mod tests {
                 symbol: String::from("tBTCUST"),
                 timestamp: DateTime::from_timestamp(1574963975, 0).unwrap(),
                 order_id: 34938060782,
-                amount: -0.2,
-                price: 153.57,
+                amount: Quantity(-0.2),
+                price: Price(153.57),
                 order_type: String::from("MARKET"),
-                order_price: 0.0,
+                order_price: Price(0.0),
                 is_maker: false,
-                fee: -0.061668,
+                fee: Fee(-0.061668),
                 fee_currency: String::from("USD"),
                 cid: Some(1234),
             }
         );
+        assert_eq!(trade.normalized_fee(), (Fee(0.061668), "USD"));
+
+        let trade: NormalizedTrade = trade.into_normalized();
+        assert_eq!(trade.exchange, Exchange::Bitfinex);
+        assert_eq!(trade.side, TradeSide::Sell);
+        assert_eq!(*trade.base_qty, 0.2);
+        assert_eq!(*trade.quote_qty, 0.2 * 153.57);
+        assert_eq!(*trade.fee, 0.061668);
+        assert_eq!(trade.trade_id, "402088407");
+    }
+
+    #[test]
+    fn test_ticker_deserialization() {
+        let json = r#"[10645,73.93854271,10647,75.22266119,731.60645389,0.0738,10644.00645389,14480.89849423,10766,9889.1449809]"#;
+
+        let ticker: Ticker = serde_json::from_str(json).unwrap();
+
+        assert_eq!(ticker.bid, 10645.0);
+        assert_eq!(ticker.ask_size, 75.22266119);
+        assert_eq!(ticker.daily_change_relative, 0.0738);
+        assert_eq!(ticker.last_price, 10644.00645389);
+        assert_eq!(ticker.low, 9889.1449809);
+    }
+
+    #[test]
+    fn test_funding_offer_deserialization() {
+        let json = r#"[41237920,"fETH",1573912039000,1573912039000,0.5,0.5,"LIMIT",null,null,0,"ACTIVE",null,null,null,0.0024,2,0,0,null,0,null]"#;
+
+        let offer: FundingOffer = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            offer,
+            FundingOffer {
+                id: 41237920,
+                symbol: String::from("fETH"),
+                mts_created: DateTime::from_timestamp(1573912039, 0).unwrap(),
+                mts_updated: DateTime::from_timestamp(1573912039, 0).unwrap(),
+                amount: 0.5,
+                amount_orig: 0.5,
+                offer_type: String::from("LIMIT"),
+                status: String::from("ACTIVE"),
+                rate: 0.0024,
+                period: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_funding_credit_deserialization() {
+        let json = r#"[26190108,"fUST",-1,1575291961000,1575295850000,350,0,"ACTIVE","FIXED",null,null,0.0024,30,1575291961000,null,0,0,null,0,null,0,"tBTCUST"]"#;
+
+        let credit: FundingCredit = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            credit,
+            FundingCredit {
+                id: 26190108,
+                symbol: String::from("fUST"),
+                side: -1,
+                mts_create: DateTime::from_timestamp(1575291961, 0).unwrap(),
+                mts_update: DateTime::from_timestamp(1575295850, 0).unwrap(),
+                amount: 350.0,
+                status: String::from("ACTIVE"),
+                rate_type: Some(String::from("FIXED")),
+                rate: 0.0024,
+                period: 30,
+                mts_opening: DateTime::from_timestamp(1575291961, 0).unwrap(),
+                mts_last_payout: None,
+                position_pair: Some(String::from("tBTCUST")),
+            }
+        );
+    }
+
+    #[test]
+    fn test_order_deserialization() {
+        let json = r#"[
+                4014781231,
+                null,
+                1600778536023,
+                "tBTCUSD",
+                1600778536030,
+                1600778536033,
+                0,
+                0.0001,
+                "EXCHANGE LIMIT",
+                null,
+                null,
+                null,
+                4096,
+                "EXECUTED @ 15000.0(0.0001)",
+                null,
+                null,
+                15000,
+                15000,
+                0,
+                0,
+                null,
+                null,
+                null,
+                0,
+                0,
+                null,
+                null,
+                null,
+                "API>BFX",
+                null,
+                null,
+                {}
+            ]"#;
+
+        let order: Order = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            order,
+            Order {
+                id: 4014781231,
+                gid: None,
+                cid: Some(1600778536023),
+                symbol: String::from("tBTCUSD"),
+                mts_create: DateTime::from_timestamp(1600778536, 0).unwrap(),
+                mts_update: DateTime::from_timestamp(1600778536, 0).unwrap(),
+                amount: 0.0,
+                amount_orig: 0.0001,
+                order_type: String::from("EXCHANGE LIMIT"),
+                status: String::from("EXECUTED @ 15000.0(0.0001)"),
+                price: 15000.0,
+                price_avg: 15000.0,
+            }
+        );
     }
 }
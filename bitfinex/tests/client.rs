@@ -0,0 +1,275 @@
+//! Replay recorded responses through the client
+
+use bitfinex_api::prelude::*;
+use wiremock::matchers::{header, header_exists, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(server: &MockServer) -> BitfinexClient {
+    let auth = BitfinexAuth::ApiKeys {
+        api_key: String::from("key"),
+        api_secret: String::from("secret"),
+    };
+
+    BitfinexClient::new(auth)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap())
+}
+
+/// Match the authentication headers
+fn authenticated(http_path: &str) -> wiremock::MockBuilder {
+    Mock::given(method("POST"))
+        .and(path(http_path))
+        .and(header("bfx-apikey", "key"))
+        .and(header_exists("bfx-nonce"))
+        .and(header_exists("bfx-signature"))
+}
+
+#[tokio::test]
+async fn test_total_balance() {
+    let server = MockServer::start().await;
+
+    authenticated("/v2/auth/r/wallets")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/wallets.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let balance = client(&server).total_balance("BTC").await.unwrap();
+    assert_eq!(balance, 0.75);
+}
+
+#[tokio::test]
+async fn test_movements() {
+    let server = MockServer::start().await;
+
+    authenticated("/v2/auth/r/movements/BTC/hist")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/movements.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let movements = client(&server).movements().await.unwrap();
+    assert_eq!(movements.len(), 1);
+    assert_eq!(movements[0].id, 13293039);
+    assert_eq!(movements[0].amount, -0.24);
+}
+
+#[tokio::test]
+async fn test_http_error() {
+    let server = MockServer::start().await;
+
+    authenticated("/v2/auth/r/wallets")
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server).wallets().await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ServerError(ServerError { status: 500, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_ticker_without_auth() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/ticker/tBTCUSD"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/ticker.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = BitfinexClient::new(BitfinexAuth::None)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap());
+
+    let ticker = client.ticker("tBTCUSD").await.unwrap();
+    assert_eq!(ticker.last_price, 10644.00645389);
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("bfx-signature"));
+    assert!(!requests[0].headers.contains_key("bfx-nonce"));
+}
+
+#[tokio::test]
+async fn test_ticker_cache() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/ticker/tBTCUSD"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/ticker.json")),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = BitfinexClient::new(BitfinexAuth::None)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap())
+        .with_cache(CacheConfig::new().ttl("ticker", std::time::Duration::from_secs(60)));
+
+    client.ticker("tBTCUSD").await.unwrap();
+    client.ticker("tBTCUSD").await.unwrap();
+
+    client.clear_cache();
+    client.ticker("tBTCUSD").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_symbols() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/conf/pub:list:pair:exchange"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"[["AAVE:USD","BTCEUR","BTCUSD","ETHBTC","TESTBTC:TESTUSD"]]"#),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = BitfinexClient::new(BitfinexAuth::None)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap());
+
+    let symbols = client.symbols().await.unwrap();
+    assert_eq!(symbols.len(), 5);
+    assert!(symbols.contains(&String::from("BTCUSD")));
+    assert!(symbols.contains(&String::from("TESTBTC:TESTUSD")));
+}
+
+#[tokio::test]
+async fn test_healthcheck() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/platform/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[1]"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/platform/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[0]"))
+        .mount(&server)
+        .await;
+
+    let client = BitfinexClient::new(BitfinexAuth::None)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap());
+
+    client.healthcheck().await.unwrap();
+    assert!(matches!(
+        client.healthcheck().await.unwrap_err(),
+        Error::Maintenance
+    ));
+}
+
+#[tokio::test]
+async fn test_healthcheck_verifies_api_keys() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/platform/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[1]"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    authenticated("/v2/auth/r/wallets")
+        .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).healthcheck().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_platform_status() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/platform/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[0]"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/platform/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[1]"))
+        .mount(&server)
+        .await;
+
+    let client = BitfinexClient::new(BitfinexAuth::None)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap());
+
+    assert!(!client.platform_status().await.unwrap());
+    assert!(client.platform_status().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_default_headers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/platform/status"))
+        .and(header("X-Gateway-Token", "token"))
+        .and(header("Accept", "application/json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("[1]"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Gateway-Token", HeaderValue::from_static("token"));
+    headers.insert("Accept", HeaderValue::from_static("text/plain"));
+
+    let client = BitfinexClient::new(BitfinexAuth::None)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap())
+        .with_default_headers(headers)
+        .unwrap();
+
+    assert!(client.platform_status().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_interceptors() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/conf/pub:list:pair:exchange"))
+        .and(header("X-Request-Id", "req-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"[["BTCUSD"]]"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let interceptors =
+        Interceptors::new().on_request(|request| request.header("X-Request-Id", "req-1"));
+
+    let client = BitfinexClient::new(BitfinexAuth::None)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap())
+        .with_interceptors(interceptors);
+
+    assert_eq!(
+        client.symbols().await.unwrap(),
+        vec![String::from("BTCUSD")]
+    );
+}
@@ -0,0 +1,48 @@
+//! Send bitcoin from the Coinbase BTC wallet
+//!
+//! ```sh
+//! COINBASE_API_KEY=.. COINBASE_SECRET_KEY=.. SEND_TO=<address> SEND_AMOUNT=0.0001 \
+//!     ALLOW_WRITES=1 cargo run --example send
+//! ```
+//!
+//! Coinbase has no sandbox for sends: without `ALLOW_WRITES=1` the send is only printed, nothing
+//! is created on the account.
+
+use std::env;
+use std::str::FromStr;
+
+use coinbase_api::prelude::*;
+use rust_decimal::Decimal;
+
+#[tokio::main]
+async fn main() {
+    let auth = CoinbaseAuth::ApiKeys {
+        api_key: env::var("COINBASE_API_KEY").expect("COINBASE_API_KEY not set"),
+        secret_key: env::var("COINBASE_SECRET_KEY").expect("COINBASE_SECRET_KEY not set"),
+    };
+    let client = CoinbaseAppClient::new(auth).unwrap();
+
+    let to: String = env::var("SEND_TO").expect("SEND_TO not set");
+    let amount: String = env::var("SEND_AMOUNT").expect("SEND_AMOUNT not set");
+    let amount: Decimal = Decimal::from_str(&amount).expect("invalid SEND_AMOUNT");
+
+    let accounts = client.accounts().await.unwrap();
+    let account = accounts
+        .iter()
+        .find(|account| account.currency.code == "BTC" && account.r#type == AccountType::Wallet)
+        .expect("BTC wallet not found");
+
+    let req = SendRequest::new(to, amount, "BTC");
+
+    if env::var("ALLOW_WRITES").as_deref() != Ok("1") {
+        println!("Dry run, set ALLOW_WRITES=1 to create and commit the send:");
+        println!("{req:#?}");
+        return;
+    }
+
+    let tx = client.create_send(&account.id, &req).await.unwrap();
+    println!("Uncommitted send: {tx:#?}");
+
+    let tx = client.commit_send(&account.id, &tx.id).await.unwrap();
+    println!("Committed send: {tx:#?}");
+}
@@ -0,0 +1,116 @@
+//! Coinbase Advanced Trade client
+
+use common::pagination::{self, Page};
+use futures_util::TryStreamExt;
+use reqwest::Response;
+
+use super::response::{Account, AccountsResponse};
+use crate::app::agent::SecureHttpClientAgent;
+use crate::app::auth::CoinbaseAuth;
+use crate::app::client::CoinbaseAppClient;
+use crate::app::error::Error;
+
+/// Max page size of the accounts
+const ACCOUNTS_PAGE_LIMIT: usize = 250;
+
+/// Coinbase Advanced Trade client
+#[derive(Debug, Clone)]
+pub struct AdvancedTradeClient {
+    client: SecureHttpClientAgent,
+}
+
+impl AdvancedTradeClient {
+    /// Construct a new Coinbase Advanced Trade client.
+    ///
+    /// Use [`CoinbaseAppClient::advanced_trade`] to customize the client with the
+    /// [`CoinbaseAppClientBuilder`](crate::app::builder::CoinbaseAppClientBuilder).
+    pub fn new(auth: CoinbaseAuth) -> Result<Self, Error> {
+        Ok(CoinbaseAppClient::new(auth)?.advanced_trade())
+    }
+
+    #[inline]
+    pub(crate) fn from_agent(client: SecureHttpClientAgent) -> Self {
+        Self { client }
+    }
+
+    /// Get all the accounts
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/advanced-trade-apis/rest-api#list-accounts>
+    #[inline]
+    pub async fn accounts(&self) -> Result<Vec<Account>, Error> {
+        self.fetch_accounts(None).await
+    }
+
+    /// Get the accounts of a portfolio
+    ///
+    /// Filtered server-side: only the accounts of the portfolio are downloaded.
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/advanced-trade-apis/rest-api#list-accounts>
+    #[inline]
+    pub async fn accounts_in_portfolio(&self, uuid: &str) -> Result<Vec<Account>, Error> {
+        self.fetch_accounts(Some(uuid)).await
+    }
+
+    async fn fetch_accounts(&self, portfolio_uuid: Option<&str>) -> Result<Vec<Account>, Error> {
+        let pages = pagination::paginate(|cursor: Option<String>| async move {
+            self.accounts_page(portfolio_uuid, cursor.as_deref()).await
+        });
+        self.client.with_deadline(pages.try_collect()).await
+    }
+
+    /// Get a page of accounts, optionally scoped to a portfolio
+    ///
+    /// Pass the [`Page::next`] cursor of the previous page to get the next one.
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/advanced-trade-apis/rest-api#list-accounts>
+    pub async fn accounts_page(
+        &self,
+        portfolio_uuid: Option<&str>,
+        cursor: Option<&str>,
+    ) -> Result<Page<Account, String>, Error> {
+        let query: String = accounts_query(portfolio_uuid, cursor);
+        let res: Response = self
+            .client
+            .get("/api/v3/brokerage/accounts", Some(&query))
+            .await?;
+        let res: AccountsResponse = res.json().await?;
+
+        let next: Option<String> = if res.has_next {
+            res.cursor.filter(|cursor| !cursor.is_empty())
+        } else {
+            None
+        };
+
+        Ok(Page::new(res.accounts, next))
+    }
+}
+
+fn accounts_query(portfolio_uuid: Option<&str>, cursor: Option<&str>) -> String {
+    let mut query: String = format!("limit={ACCOUNTS_PAGE_LIMIT}");
+
+    if let Some(cursor) = cursor {
+        query.push_str("&cursor=");
+        query.push_str(cursor);
+    }
+
+    if let Some(uuid) = portfolio_uuid {
+        query.push_str("&retail_portfolio_id=");
+        query.push_str(uuid);
+    }
+
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accounts_query() {
+        assert_eq!(accounts_query(None, None), "limit=250");
+        assert_eq!(
+            accounts_query(Some("b87a2d3f"), Some("789100")),
+            "limit=250&cursor=789100&retail_portfolio_id=b87a2d3f"
+        );
+    }
+}
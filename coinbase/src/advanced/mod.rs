@@ -0,0 +1,10 @@
+//! Coinbase Advanced Trade APIs
+//!
+//! Share the authentication of the App APIs: get a client with [`CoinbaseAppClient::advanced_trade`].
+//!
+//! <https://docs.cdp.coinbase.com/coinbase-app/advanced-trade-apis/overview>
+//!
+//! [`CoinbaseAppClient::advanced_trade`]: crate::app::client::CoinbaseAppClient::advanced_trade
+
+pub mod client;
+pub mod response;
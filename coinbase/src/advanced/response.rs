@@ -0,0 +1,63 @@
+//! Coinbase Advanced Trade APIs responses
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::app::response::Money;
+
+#[derive(Deserialize)]
+pub(super) struct AccountsResponse {
+    pub accounts: Vec<Account>,
+    #[serde(default)]
+    pub has_next: bool,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Advanced Trade account
+///
+/// <https://docs.cdp.coinbase.com/coinbase-app/advanced-trade-apis/rest-api#list-accounts>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Account {
+    /// Account UUID
+    pub uuid: String,
+    /// Account name (i.e., `BTC Wallet`)
+    pub name: String,
+    /// Currency code (i.e., `BTC`)
+    pub currency: String,
+    /// Available balance
+    pub available_balance: Balance,
+    /// Balance on hold
+    #[serde(default)]
+    pub hold: Option<Balance>,
+    /// Whether it's the default account of the currency
+    #[serde(default)]
+    pub default: bool,
+    /// Whether the account is active
+    #[serde(default)]
+    pub active: bool,
+    /// Whether the account is ready to trade
+    #[serde(default)]
+    pub ready: bool,
+    /// Account type (i.e., `ACCOUNT_TYPE_CRYPTO`)
+    #[serde(rename = "type")]
+    pub account_type: String,
+    /// UUID of the portfolio the account belongs to
+    #[serde(default)]
+    pub retail_portfolio_id: Option<String>,
+    /// Created at
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Updated at
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Advanced Trade balance
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize)]
+pub struct Balance {
+    /// Amount
+    pub value: Money,
+    /// Currency
+    pub currency: String,
+}
@@ -1,13 +1,23 @@
+use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
 
+use common::interceptor::{self, Interceptors};
 use reqwest::header::{CONTENT_TYPE, HeaderValue, USER_AGENT};
-use reqwest::{Client, Method, Response};
+use reqwest::{Client, Method, Response, StatusCode};
+use tokio::sync::Mutex;
+use tokio::time;
 use url::Url;
 
 use super::auth::CoinbaseAuth;
-use super::auth::jwt::Jwt;
+use super::auth::jwt::{Jwt, Token};
+use super::bucket::TokenBucket;
+use super::builder::CoinbaseAppClientBuilder;
 use super::constant::{API_ROOT_URL, API_SANDBOX_URL, CB_VERSION, USER_AGENT_NAME};
 use super::error::Error;
+use super::response::{CoinbaseErrorMessage, CoinbaseErrorResponse, TWO_FACTOR_REQUIRED};
+
+const CB_2FA_TOKEN: &str = "CB-2FA-TOKEN";
 
 #[derive(Debug, Clone)]
 struct HttpClientAgent {
@@ -15,24 +25,52 @@ struct HttpClientAgent {
     root_url: Url,
     /// HTTP client.
     client: Client,
+    /// Rate limiter, shared across clones.
+    bucket: Arc<Mutex<TokenBucket>>,
+    /// Upper bound of a whole operation.
+    overall_deadline: Option<Duration>,
+    /// Request and response interceptors.
+    interceptors: Interceptors,
 }
 
 impl HttpClientAgent {
-    fn new(sandbox: bool, timeout: Duration) -> Result<Self, Error> {
-        let root_url: &str = if sandbox {
-            API_SANDBOX_URL
-        } else {
-            API_ROOT_URL
+    fn new(builder: &CoinbaseAppClientBuilder) -> Result<Self, Error> {
+        let root_url: Url = match &builder.root_url {
+            Some(root_url) => root_url.clone(),
+            None if builder.sandbox => Url::parse(API_SANDBOX_URL)?,
+            None => Url::parse(API_ROOT_URL)?,
         };
 
-        let client = Client::builder().timeout(timeout).build()?;
+        let mut client = Client::builder()
+            .default_headers(builder.default_headers.clone())
+            .timeout(builder.timeout);
+
+        if let Some(connect_timeout) = builder.connect_timeout {
+            client = client.connect_timeout(connect_timeout);
+        }
 
         Ok(Self {
-            root_url: Url::parse(root_url)?,
-            client,
+            root_url,
+            client: client.build()?,
+            bucket: Arc::new(Mutex::new(TokenBucket::new(builder.requests_per_second))),
+            overall_deadline: builder.overall_deadline,
+            interceptors: builder.interceptors.clone(),
         })
     }
 
+    /// Run an operation (i.e., including pagination) within the overall deadline
+    pub(crate) async fn with_deadline<F, T>(&self, operation: F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        match self.overall_deadline {
+            Some(deadline) => time::timeout(deadline, operation)
+                .await
+                .map_err(|_| Error::Deadline(deadline))?,
+            None => operation.await,
+        }
+    }
+
     /// Constructs a URL for the request being made.
     fn build_url(&self, resource: &str, query: Option<&str>) -> Result<Url, Error> {
         let mut url = self.root_url.join(resource)?;
@@ -41,28 +79,71 @@ impl HttpClientAgent {
     }
 
     /// Handles the response from the API.
-    async fn handle_response(&self, response: Response) -> Result<Response, Error> {
-        Ok(response.error_for_status()?)
-
-        // if response.status().is_success() {
-        //             Ok(response)
-        //         } else {
-        //             let res: CoinbaseErrorMessage = response.json().await?;
-        //             Err(Error::Coinbase(res))
-        //         }
+    ///
+    /// A 401 answered to a JWT-authenticated request reports the token validity window.
+    async fn handle_response(
+        &self,
+        response: Response,
+        token: Option<&Token>,
+    ) -> Result<Response, Error> {
+        let status: u16 = response.status().as_u16();
+
+        let response: Response = interceptor::error_for_server_status(response).await?;
+
+        if !response.status().is_success() {
+            let response_status: StatusCode = response.status();
+            let body: String = response.text().await.unwrap_or_default();
+
+            if let Some(error) = two_factor_required(&body) {
+                return Err(Error::TwoFactorRequired { status, error });
+            }
+
+            if let (StatusCode::UNAUTHORIZED, Some(token)) = (response_status, token) {
+                let (not_before, expires_at) = token.window();
+                return Err(Error::Unauthorized {
+                    not_before,
+                    expires_at,
+                    body,
+                });
+            }
+
+            return Err(Error::Http { status, body });
+        }
+
+        Ok(response)
     }
 
+    #[inline]
     pub(crate) async fn execute_request(
         &self,
         method: Method,
         url: Url,
         body: Option<String>,
-        token: Option<String>,
+        token: Option<Token>,
+        two_factor_token: Option<&str>,
+    ) -> Result<Response, Error> {
+        self.with_deadline(self.execute_request_unbounded(
+            method,
+            url,
+            body,
+            token,
+            two_factor_token,
+        ))
+        .await
+    }
+
+    async fn execute_request_unbounded(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<String>,
+        token: Option<Token>,
+        two_factor_token: Option<&str>,
     ) -> Result<Response, Error> {
-        // {
-        //     let mut locked_bucket = self.bucket.lock().await;
-        //     locked_bucket.wait_on().await;
-        // }
+        {
+            let mut locked_bucket = self.bucket.lock().await;
+            locked_bucket.wait_on().await;
+        }
 
         let mut request = self
             .client
@@ -71,17 +152,21 @@ impl HttpClientAgent {
             .header(USER_AGENT, USER_AGENT_NAME)
             .header("CB-VERSION", HeaderValue::from_static(CB_VERSION));
 
-        if let Some(token) = token {
-            request = request.bearer_auth(token);
+        if let Some(token) = &token {
+            request = request.bearer_auth(&token.value);
+        }
+
+        if let Some(two_factor_token) = two_factor_token {
+            request = request.header(CB_2FA_TOKEN, two_factor_token);
         }
 
         if let Some(body) = body {
             request = request.body(body);
         }
 
-        let response = request.send().await?;
+        let response = self.interceptors.send(request).await?;
 
-        self.handle_response(response).await
+        self.handle_response(response, token.as_ref()).await
     }
 }
 
@@ -94,15 +179,15 @@ pub struct SecureHttpClientAgent {
 }
 
 impl SecureHttpClientAgent {
-    pub(super) fn new(auth: CoinbaseAuth, sandbox: bool, timeout: Duration) -> Result<Self, Error> {
-        let jwt: Option<Jwt> = match auth {
+    pub(super) fn new(builder: &CoinbaseAppClientBuilder) -> Result<Self, Error> {
+        let jwt: Option<Jwt> = match builder.auth.clone() {
             CoinbaseAuth::None => None,
             CoinbaseAuth::ApiKeys {
                 api_key,
                 secret_key,
             } => {
                 // Do not generate JWT in sandbox mode.
-                if sandbox {
+                if builder.sandbox {
                     None
                 } else {
                     Some(Jwt::new(api_key, secret_key)?)
@@ -112,14 +197,29 @@ impl SecureHttpClientAgent {
 
         Ok(Self {
             jwt,
-            base: HttpClientAgent::new(sandbox, timeout)?,
+            base: HttpClientAgent::new(builder)?,
         })
     }
 
+    /// Check if the requests are authenticated (i.e., API keys configured and not in sandbox mode)
+    #[inline]
+    pub(crate) fn is_authenticated(&self) -> bool {
+        self.jwt.is_some()
+    }
+
+    /// Run an operation (i.e., including pagination) within the overall deadline
+    #[inline]
+    pub(crate) async fn with_deadline<F, T>(&self, operation: F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        self.base.with_deadline(operation).await
+    }
+
     /// Builds a token for the request.
     ///
     /// If JWT is not enabled, returns `None`.
-    fn build_token(&self, method: &Method, path: &str) -> Result<Option<String>, Error> {
+    fn build_token(&self, method: &Method, path: &str) -> Result<Option<Token>, Error> {
         match &self.jwt {
             Some(jwt) => {
                 let url: Url = self.base.root_url.join(path)?;
@@ -130,23 +230,36 @@ impl SecureHttpClientAgent {
         }
     }
 
-    pub(super) async fn get(&self, resource: &str, query: Option<&str>) -> Result<Response, Error> {
+    pub(crate) async fn get(&self, resource: &str, query: Option<&str>) -> Result<Response, Error> {
         const METHOD: Method = Method::GET;
 
         // Build URL
         let url: Url = self.base.build_url(resource, query)?;
 
         // Build token
-        let token: Option<String> = self.build_token(&METHOD, resource)?;
+        let token: Option<Token> = self.build_token(&METHOD, resource)?;
 
         // Execute request
-        self.base.execute_request(METHOD, url, None, token).await
+        self.base
+            .execute_request(METHOD, url, None, token, None)
+            .await
     }
 
+    #[inline]
     pub(super) async fn post(
         &self,
         resource: &str,
         body: Option<String>,
+    ) -> Result<Response, Error> {
+        self.post_with_2fa(resource, body, None).await
+    }
+
+    /// Send a POST request, confirmed with a 2FA token if provided
+    pub(super) async fn post_with_2fa(
+        &self,
+        resource: &str,
+        body: Option<String>,
+        two_factor_token: Option<&str>,
     ) -> Result<Response, Error> {
         const METHOD: Method = Method::POST;
 
@@ -154,9 +267,19 @@ impl SecureHttpClientAgent {
         let url: Url = self.base.build_url(resource, None)?;
 
         // Build token
-        let token: Option<String> = self.build_token(&METHOD, resource)?;
+        let token: Option<Token> = self.build_token(&METHOD, resource)?;
 
         // Execute request
-        self.base.execute_request(METHOD, url, body, token).await
+        self.base
+            .execute_request(METHOD, url, body, token, two_factor_token)
+            .await
     }
 }
+
+/// Get the `two_factor_required` error from a response body, if any
+fn two_factor_required(body: &str) -> Option<CoinbaseErrorMessage> {
+    let res: CoinbaseErrorResponse = serde_json::from_str(body).ok()?;
+    res.errors
+        .into_iter()
+        .find(|error| error.id == TWO_FACTOR_REQUIRED)
+}
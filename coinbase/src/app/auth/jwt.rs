@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use base64::Engine;
 use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
 use p256::SecretKey;
 use p256::pkcs8::{self, DecodePrivateKey, EncodePrivateKey};
 use reqwest::Method;
@@ -109,14 +110,16 @@ impl Jwt {
     ///
     /// # Returns
     ///
-    /// A `Result<String>` with the JWT token if successful; otherwise, an error.
-    pub(crate) fn encode(&self, uri: Option<String>) -> Result<String, Error> {
+    /// A `Result<Token>` with the JWT token and its validity window if successful; otherwise, an error.
+    pub(crate) fn encode(&self, uri: Option<String>) -> Result<Token, Error> {
         // Build header and encode to base64
         let header: Header = self.build_header()?;
         let header: String = base64_encode(&header)?;
 
         // Build payload and encode to base64
         let payload: Payload = self.build_payload(uri);
+        let not_before: u64 = payload.nbf;
+        let expires_at: u64 = payload.exp;
         let payload: String = base64_encode(&payload)?;
 
         // Estimate capacity: header + payload + signature + 2 dots
@@ -131,7 +134,29 @@ impl Jwt {
         message.push('.');
         message.push_str(&signature);
 
-        Ok(message)
+        Ok(Token {
+            value: message,
+            not_before,
+            expires_at,
+        })
+    }
+}
+
+/// Signed JWT token, with its validity window
+#[derive(Debug, Clone)]
+pub(crate) struct Token {
+    /// Encoded token.
+    pub(crate) value: String,
+    /// `nbf` claim, in UNIX seconds.
+    pub(crate) not_before: u64,
+    /// `exp` claim, in UNIX seconds.
+    pub(crate) expires_at: u64,
+}
+
+impl Token {
+    /// Get the validity window of the token.
+    pub(crate) fn window(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        (to_datetime(self.not_before), to_datetime(self.expires_at))
     }
 }
 
@@ -238,6 +263,11 @@ fn parse_key(api_secret: &str) -> Result<Vec<u8>, Error> {
         .map_err(|why| Error::InvalidPrivateKey(why.to_string()))
 }
 
+#[inline]
+fn to_datetime(timestamp: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default()
+}
+
 #[inline]
 fn to_base64(input: &[u8]) -> String {
     URL_SAFE_NO_PAD.encode(input)
@@ -4,6 +4,8 @@
 
 use std::fmt;
 
+use common::config::ApiKeys;
+
 pub(super) mod jwt;
 
 /// Coinbase authentication
@@ -26,3 +28,12 @@ impl fmt::Debug for CoinbaseAuth {
         f.debug_struct("CoinbaseAuth").finish()
     }
 }
+
+impl From<ApiKeys> for CoinbaseAuth {
+    fn from(keys: ApiKeys) -> Self {
+        Self::ApiKeys {
+            api_key: keys.api_key,
+            secret_key: keys.api_secret,
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Blocking Coinbase App client
+
+use common::blocking::Runtime;
+
+use super::client::CoinbaseAppClient;
+use super::error::Error;
+use super::request::SendRequest;
+use super::response::{Account, Address, AssetInfo, Page, SpotPrice, Transaction, TransactionType};
+
+impl CoinbaseAppClient {
+    /// Get a blocking client sharing this client
+    ///
+    /// See [`BlockingCoinbaseAppClient`].
+    pub fn blocking(&self) -> Result<BlockingCoinbaseAppClient, Error> {
+        Ok(BlockingCoinbaseAppClient {
+            client: self.clone(),
+            runtime: Runtime::new()?,
+        })
+    }
+}
+
+/// Blocking Coinbase App client
+///
+/// Thin wrapper that runs the [`CoinbaseAppClient`] requests on an internal current-thread runtime.
+/// Must NOT be used from within an async context.
+#[derive(Debug, Clone)]
+pub struct BlockingCoinbaseAppClient {
+    client: CoinbaseAppClient,
+    runtime: Runtime,
+}
+
+impl BlockingCoinbaseAppClient {
+    /// Get the async client
+    #[inline]
+    pub fn as_async(&self) -> &CoinbaseAppClient {
+        &self.client
+    }
+
+    /// Clear the response cache
+    ///
+    /// See [`CoinbaseAppClient::clear_cache`].
+    #[inline]
+    pub fn clear_cache(&self) {
+        self.client.clear_cache()
+    }
+
+    /// Check that the API is reachable
+    ///
+    /// See [`CoinbaseAppClient::healthcheck`].
+    #[inline]
+    pub fn healthcheck(&self) -> Result<(), Error> {
+        self.runtime.block_on(self.client.healthcheck())
+    }
+
+    /// Get accounts
+    ///
+    /// See [`CoinbaseAppClient::accounts`].
+    #[inline]
+    pub fn accounts(&self) -> Result<Vec<Account>, Error> {
+        self.runtime.block_on(self.client.accounts())
+    }
+
+    /// Get all the non-zero balances, as `(currency, amount)` pairs
+    ///
+    /// See [`CoinbaseAppClient::all_balances`].
+    #[inline]
+    pub fn all_balances(&self) -> Result<Vec<(String, f64)>, Error> {
+        self.runtime.block_on(self.client.all_balances())
+    }
+
+    /// Get the metadata of a currency (i.e., `BTC`)
+    ///
+    /// See [`CoinbaseAppClient::asset_info`].
+    #[inline]
+    pub fn asset_info(&self, code: &str) -> Result<Option<AssetInfo>, Error> {
+        self.runtime.block_on(self.client.asset_info(code))
+    }
+
+    /// Get a page of accounts
+    ///
+    /// See [`CoinbaseAppClient::accounts_page`].
+    #[inline]
+    pub fn accounts_page(&self, starting_after: Option<&str>) -> Result<Page<Account>, Error> {
+        self.runtime
+            .block_on(self.client.accounts_page(starting_after))
+    }
+
+    /// Get account by ID
+    ///
+    /// See [`CoinbaseAppClient::account`].
+    #[inline]
+    pub fn account(&self, id: &str) -> Result<Account, Error> {
+        self.runtime.block_on(self.client.account(id))
+    }
+
+    /// Get the spot price of a currency in a fiat currency
+    ///
+    /// See [`CoinbaseAppClient::spot_price`].
+    #[inline]
+    pub fn spot_price(&self, currency: &str, fiat: &str) -> Result<SpotPrice, Error> {
+        self.runtime
+            .block_on(self.client.spot_price(currency, fiat))
+    }
+
+    /// Get the value of an account balance in a fiat currency
+    ///
+    /// See [`CoinbaseAppClient::account_native_value`].
+    #[inline]
+    pub fn account_native_value(&self, account_id: &str, fiat: &str) -> Result<f64, Error> {
+        self.runtime
+            .block_on(self.client.account_native_value(account_id, fiat))
+    }
+
+    /// Create a new **bitcoin** deposit address.
+    ///
+    /// See [`CoinbaseAppClient::bitcoin_deposit_address`].
+    #[inline]
+    pub fn bitcoin_deposit_address(&self) -> Result<String, Error> {
+        self.runtime.block_on(self.client.bitcoin_deposit_address())
+    }
+
+    /// Get the addresses of an account
+    ///
+    /// See [`CoinbaseAppClient::addresses`].
+    #[inline]
+    pub fn addresses(&self, account_id: &str) -> Result<Vec<Address>, Error> {
+        self.runtime.block_on(self.client.addresses(account_id))
+    }
+
+    /// Get a page of addresses of an account
+    ///
+    /// See [`CoinbaseAppClient::addresses_page`].
+    #[inline]
+    pub fn addresses_page(
+        &self,
+        account_id: &str,
+        starting_after: Option<&str>,
+    ) -> Result<Page<Address>, Error> {
+        self.runtime
+            .block_on(self.client.addresses_page(account_id, starting_after))
+    }
+
+    /// Create a new address for an account, on its default network
+    ///
+    /// See [`CoinbaseAppClient::create_address`].
+    #[inline]
+    pub fn create_address(&self, account_id: &str, name: Option<&str>) -> Result<Address, Error> {
+        self.runtime
+            .block_on(self.client.create_address(account_id, name))
+    }
+
+    /// Create an uncommitted send from an account
+    ///
+    /// See [`CoinbaseAppClient::create_send`].
+    #[inline]
+    pub fn create_send(&self, account_id: &str, req: &SendRequest) -> Result<Transaction, Error> {
+        self.runtime
+            .block_on(self.client.create_send(account_id, req))
+    }
+
+    /// Create an uncommitted send from an account, confirmed with a 2FA token
+    ///
+    /// See [`CoinbaseAppClient::send_money_with_2fa`].
+    #[inline]
+    pub fn send_money_with_2fa(
+        &self,
+        account_id: &str,
+        req: &SendRequest,
+        token: &str,
+    ) -> Result<Transaction, Error> {
+        self.runtime
+            .block_on(self.client.send_money_with_2fa(account_id, req, token))
+    }
+
+    /// Commit a send
+    ///
+    /// See [`CoinbaseAppClient::commit_send`].
+    #[inline]
+    pub fn commit_send(
+        &self,
+        account_id: &str,
+        transaction_id: &str,
+    ) -> Result<Transaction, Error> {
+        self.runtime
+            .block_on(self.client.commit_send(account_id, transaction_id))
+    }
+
+    /// Get transactions by account ID
+    ///
+    /// See [`CoinbaseAppClient::transactions`].
+    #[inline]
+    pub fn transactions(&self, account_id: &str) -> Result<Vec<Transaction>, Error> {
+        self.runtime.block_on(self.client.transactions(account_id))
+    }
+
+    /// Get transactions by account ID, keeping only the given types
+    ///
+    /// See [`CoinbaseAppClient::transactions_filtered`].
+    #[inline]
+    pub fn transactions_filtered(
+        &self,
+        account_id: &str,
+        types: &[TransactionType],
+    ) -> Result<Vec<Transaction>, Error> {
+        self.runtime
+            .block_on(self.client.transactions_filtered(account_id, types))
+    }
+
+    /// Get a page of transactions by account ID
+    ///
+    /// See [`CoinbaseAppClient::transactions_page`].
+    #[inline]
+    pub fn transactions_page(
+        &self,
+        account_id: &str,
+        starting_after: Option<&str>,
+    ) -> Result<Page<Transaction>, Error> {
+        self.runtime
+            .block_on(self.client.transactions_page(account_id, starting_after))
+    }
+}
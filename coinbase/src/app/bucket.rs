@@ -0,0 +1,101 @@
+//! Token bucket rate limiter
+
+use std::time::Duration;
+
+use tokio::time::{self, Instant};
+
+/// Token bucket used to throttle outgoing requests.
+///
+/// The bucket starts full, holds at most `requests_per_second` tokens and refills continuously.
+#[derive(Debug)]
+pub(super) struct TokenBucket {
+    /// Max number of tokens
+    capacity: f64,
+    /// Available tokens
+    tokens: f64,
+    /// Tokens added per second
+    refill_rate: f64,
+    /// Last refill
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub(super) fn new(requests_per_second: u32) -> Self {
+        let capacity: f64 = f64::from(requests_per_second.max(1));
+
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_rate: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now: Instant = Instant::now();
+        let elapsed: f64 = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Wait until a token is available and consume it.
+    pub(super) async fn wait_on(&mut self) {
+        self.refill();
+
+        if self.tokens < 1.0 {
+            let missing: f64 = 1.0 - self.tokens;
+            time::sleep(Duration::from_secs_f64(missing / self.refill_rate)).await;
+            self.refill();
+        }
+
+        self.tokens = (self.tokens - 1.0).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_bucket_allows_burst_up_to_capacity() {
+        let mut bucket = TokenBucket::new(3);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            bucket.wait_on().await;
+        }
+
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_bucket_waits_when_empty() {
+        let mut bucket = TokenBucket::new(2);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            bucket.wait_on().await;
+        }
+
+        assert!(start.elapsed() >= Duration::from_millis(500));
+        assert!(start.elapsed() < Duration::from_millis(600));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_bucket_cancelled_wait_does_not_consume_token() {
+        let mut bucket = TokenBucket::new(1);
+        bucket.wait_on().await;
+
+        // Drop the wait before a token is available
+        let res = time::timeout(Duration::from_millis(100), bucket.wait_on()).await;
+        assert!(res.is_err());
+
+        let start = Instant::now();
+        bucket.wait_on().await;
+        assert!(start.elapsed() <= Duration::from_millis(900));
+
+        // The token has been consumed
+        let res = time::timeout(Duration::from_millis(100), bucket.wait_on()).await;
+        assert!(res.is_err());
+    }
+}
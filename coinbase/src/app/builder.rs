@@ -2,6 +2,11 @@
 
 use std::time::Duration;
 
+use common::cache::CacheConfig;
+use common::interceptor::Interceptors;
+use reqwest::header::HeaderMap;
+use url::Url;
+
 use super::auth::CoinbaseAuth;
 use super::client::CoinbaseAppClient;
 use super::error::Error;
@@ -13,8 +18,22 @@ pub struct CoinbaseAppClientBuilder {
     pub auth: CoinbaseAuth,
     /// Use sandbox APIs
     pub sandbox: bool,
-    /// Requests timeout
+    /// Custom root URL, overriding the mainnet and sandbox ones
+    pub root_url: Option<Url>,
+    /// Requests timeout, including the connection phase
     pub timeout: Duration,
+    /// Connection timeout
+    pub connect_timeout: Option<Duration>,
+    /// Upper bound of a whole operation, including the rate limiter wait and pagination
+    pub overall_deadline: Option<Duration>,
+    /// Max number of requests per second
+    pub requests_per_second: u32,
+    /// Response cache
+    pub cache: CacheConfig,
+    /// Request and response interceptors
+    pub interceptors: Interceptors,
+    /// Headers added to every request
+    pub default_headers: HeaderMap,
 }
 
 impl Default for CoinbaseAppClientBuilder {
@@ -22,7 +41,14 @@ impl Default for CoinbaseAppClientBuilder {
         Self {
             auth: CoinbaseAuth::default(),
             sandbox: false,
+            root_url: None,
             timeout: Duration::from_secs(20),
+            connect_timeout: None,
+            overall_deadline: None,
+            requests_per_second: 10,
+            cache: CacheConfig::default(),
+            interceptors: Interceptors::default(),
+            default_headers: HeaderMap::new(),
         }
     }
 }
@@ -42,6 +68,15 @@ impl CoinbaseAppClientBuilder {
         self
     }
 
+    /// Set a custom root URL (i.e., a proxy or a mock server)
+    ///
+    /// Requests are still authenticated, unless [`CoinbaseAppClientBuilder::sandbox`] is enabled.
+    #[inline]
+    pub fn root_url(mut self, root_url: Url) -> Self {
+        self.root_url = Some(root_url);
+        self
+    }
+
     /// Set timeout (default: 20 secs)
     #[inline]
     pub fn timeout(mut self, timeout: Duration) -> Self {
@@ -49,6 +84,62 @@ impl CoinbaseAppClientBuilder {
         self
     }
 
+    /// Set connection timeout
+    #[inline]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the overall deadline of an operation (default: none)
+    ///
+    /// Unlike [`CoinbaseAppClientBuilder::timeout`], it covers the rate limiter wait and the
+    /// pagination (i.e., [`CoinbaseAppClient::transactions`]). [`Error::Deadline`] is returned if
+    /// exceeded.
+    #[inline]
+    pub fn overall_deadline(mut self, deadline: Duration) -> Self {
+        self.overall_deadline = Some(deadline);
+        self
+    }
+
+    /// Set max number of requests per second (default: 10)
+    ///
+    /// Requests exceeding the limit wait for the rate limiter before being sent.
+    #[inline]
+    pub fn requests_per_second(mut self, requests_per_second: u32) -> Self {
+        self.requests_per_second = requests_per_second;
+        self
+    }
+
+    /// Set response cache (default: disabled)
+    ///
+    /// Cacheable endpoints: `accounts`.
+    #[inline]
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Set request and response interceptors (default: none)
+    ///
+    /// The request interceptor runs after the versioning and authentication headers are added.
+    /// Shared with [`CoinbaseAppClient::advanced_trade`].
+    #[inline]
+    pub fn interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
+    /// Set headers added to every request (default: none)
+    ///
+    /// The Coinbase headers (i.e., `CB-VERSION` and the authentication) win on conflict. Shared
+    /// with [`CoinbaseAppClient::advanced_trade`].
+    #[inline]
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
     /// Build client
     #[inline]
     pub fn build(self) -> Result<CoinbaseAppClient, Error> {
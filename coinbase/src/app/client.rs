@@ -1,27 +1,50 @@
 //! Coinbase App client
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common::cache::ResponseCache;
+use common::pagination;
+use futures_util::{Stream, TryStreamExt, future};
 use reqwest::Response;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use serde::Serialize;
+use serde::de::IgnoredAny;
+use tokio::sync::OnceCell;
+use url::Url;
 
 use super::agent::SecureHttpClientAgent;
 use super::auth::CoinbaseAuth;
+use super::constant::API_ROOT_URL;
 use super::error::Error;
-use super::response::{Account, Address, CoinbaseResponse, Transaction};
+use super::request::SendRequest;
+use super::response::{
+    Account, AccountType, Address, AssetInfo, CoinbaseResponse, Money, Page, Pagination, SpotPrice,
+    Transaction, TransactionType,
+};
+use crate::advanced::client::AdvancedTradeClient;
 use crate::app::builder::CoinbaseAppClientBuilder;
 
 const BITCOIN_NETWORK: &str = "bitcoin";
 const BTC_CURRENCY_CODE: &str = "BTC";
-const WALLET_ACCOUNT_TYPE: &str = "wallet";
+const PAGE_LIMIT: usize = 100;
+const CACHE_ACCOUNTS: &str = "accounts";
 
 #[derive(Debug, Serialize)]
 struct CreateAddressRequest<'a> {
-    network: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network: Option<&'a str>,
 }
 
 /// Coinbase App client
 #[derive(Debug, Clone)]
 pub struct CoinbaseAppClient {
     client: SecureHttpClientAgent,
+    assets: Arc<OnceCell<HashMap<String, AssetInfo>>>,
+    cache: Arc<ResponseCache>,
 }
 
 impl CoinbaseAppClient {
@@ -39,46 +62,112 @@ impl CoinbaseAppClient {
     #[inline]
     pub(super) fn from_builder(builder: CoinbaseAppClientBuilder) -> Result<Self, Error> {
         Ok(Self {
-            client: SecureHttpClientAgent::new(builder.auth, builder.sandbox, builder.timeout)?,
+            client: SecureHttpClientAgent::new(&builder)?,
+            assets: Arc::new(OnceCell::new()),
+            cache: Arc::new(ResponseCache::new(builder.cache)),
         })
     }
 
-    /// Get accounts
+    /// Get an Advanced Trade client sharing this client
     ///
-    /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/accounts#list-accounts>
-    pub async fn accounts(&self) -> Result<Vec<Account>, Error> {
-        let mut accounts = Vec::new();
+    /// The authentication, rate limiter and deadline are shared.
+    #[inline]
+    pub fn advanced_trade(&self) -> AdvancedTradeClient {
+        AdvancedTradeClient::from_agent(self.client.clone())
+    }
+
+    /// Clear the response cache
+    #[inline]
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
 
-        let mut next_uri: Option<String> = None;
+    /// Check that the API is reachable
+    ///
+    /// If the credentials are configured, they are verified by fetching the current user.
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/time>
+    /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/users#show-current-user>
+    pub async fn healthcheck(&self) -> Result<(), Error> {
+        let res: Response = self.client.get("/v2/time", None).await?;
+        let _: CoinbaseResponse<IgnoredAny> = res.json().await?;
+
+        if self.client.is_authenticated() {
+            let res: Response = self.client.get("/v2/user", None).await?;
+            let _: CoinbaseResponse<IgnoredAny> = res.json().await?;
+        }
 
-        loop {
-            let uri: &str = match &next_uri {
-                Some(next_uri) => next_uri.as_str(),
-                None => "/v2/accounts",
-            };
+        Ok(())
+    }
 
-            let res: Response = self.client.get(uri, Some("limit=100")).await?;
+    /// Get accounts
+    ///
+    /// Cached if enabled in [`CoinbaseAppClientBuilder::cache`].
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/accounts#list-accounts>
+    pub async fn accounts(&self) -> Result<Vec<Account>, Error> {
+        if let Some(accounts) = self.cache.get(CACHE_ACCOUNTS, "") {
+            return Ok(accounts);
+        }
 
-            let res: CoinbaseResponse<Vec<Account>> = res.json().await?;
+        let accounts: Vec<Account> = self.fetch_accounts().await?;
+        self.cache.insert(CACHE_ACCOUNTS, "", &accounts);
+        Ok(accounts)
+    }
 
-            accounts.extend(res.data);
+    /// Get all the accounts, skipping the cache
+    async fn fetch_accounts(&self) -> Result<Vec<Account>, Error> {
+        let pages = pagination::paginate(|cursor: Option<String>| async move {
+            self.accounts_page(cursor.as_deref()).await.map(next_page)
+        });
+        self.client.with_deadline(pages.try_collect()).await
+    }
 
-            // Check if there is another page
-            if let Some(pagination) = res.pagination {
-                if let Some(next) = pagination.next_uri {
-                    next_uri = Some(next);
-                    continue;
-                }
-            }
+    /// Get a page of accounts
+    ///
+    /// Pass the [`Pagination::next_starting_after`] cursor of the previous page to get the next
+    /// one.
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/accounts#list-accounts>
+    pub async fn accounts_page(
+        &self,
+        starting_after: Option<&str>,
+    ) -> Result<Page<Account>, Error> {
+        let query: String = page_query(starting_after);
+        let res: Response = self.client.get("/v2/accounts", Some(&query)).await?;
+        let res: CoinbaseResponse<Vec<Account>> = res.json().await?;
+        Ok(Page::from(res))
+    }
 
-            break;
-        }
+    /// Get all the non-zero balances, as `(currency, amount)` pairs
+    ///
+    /// Balances of accounts with the same currency (i.e., wallet and vault) are summed.
+    pub async fn all_balances(&self) -> Result<Vec<(String, f64)>, Error> {
+        // Balances are never served from the cache
+        let accounts: Vec<Account> = self.fetch_accounts().await?;
+        Ok(sum_balances(&accounts))
+    }
 
-        Ok(accounts)
+    /// Get the metadata of a currency (i.e., `BTC`)
+    ///
+    /// Currencies are extracted from the accounts, fetched once and cached.
+    pub async fn asset_info(&self, code: &str) -> Result<Option<AssetInfo>, Error> {
+        let assets: &HashMap<String, AssetInfo> = self
+            .assets
+            .get_or_try_init(|| async {
+                let accounts: Vec<Account> = self.accounts().await?;
+                Ok::<_, Error>(assets_from_accounts(accounts))
+            })
+            .await?;
+
+        Ok(assets.get(&code.to_ascii_uppercase()).cloned())
     }
 
     /// Get account by ID
     ///
+    /// The ID is either a UUID or a currency code (i.e., `BTC`), as returned in
+    /// [`Account::id`]. Use [`Account::refresh`] to re-fetch an already listed account.
+    ///
     /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/accounts#show-account>
     pub async fn account(&self, id: &str) -> Result<Account, Error> {
         let endpoint: String = format!("/v2/accounts/{id}");
@@ -87,6 +176,25 @@ impl CoinbaseAppClient {
         Ok(res.data)
     }
 
+    /// Get the spot price of a currency (i.e., `BTC`) in a fiat currency (i.e., `USD`)
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/prices#get-spot-price>
+    pub async fn spot_price(&self, currency: &str, fiat: &str) -> Result<SpotPrice, Error> {
+        let endpoint: String = format!("/v2/prices/{currency}-{fiat}/spot");
+        let res: Response = self.client.get(&endpoint, None).await?;
+        let res: CoinbaseResponse<SpotPrice> = res.json().await?;
+        Ok(res.data)
+    }
+
+    /// Get the value of an account balance in a fiat currency (i.e., `USD`)
+    ///
+    /// The balance is valued at the current spot price.
+    pub async fn account_native_value(&self, account_id: &str, fiat: &str) -> Result<f64, Error> {
+        let account: Account = self.account(account_id).await?;
+        let price: SpotPrice = self.spot_price(&account.balance.currency, fiat).await?;
+        Ok(native_value(&account.balance.amount, &price.amount))
+    }
+
     /// Create a new **bitcoin** deposit address.
     ///
     /// <https://docs.cdp.coinbase.com/coinbase-app/transfer-apis/onchain-addresses#create-address>
@@ -95,59 +203,285 @@ impl CoinbaseAppClient {
         let account_id: &str =
             find_bitcoin_wallet_account_id(&accounts).ok_or(Error::BitcoinWalletAccountNotFound)?;
 
+        let address: Address = self
+            .post_address(
+                account_id,
+                &CreateAddressRequest {
+                    name: None,
+                    network: Some(BITCOIN_NETWORK),
+                },
+            )
+            .await?;
+
+        if address.address.is_empty() {
+            return Err(Error::MissingDepositAddress);
+        }
+
+        Ok(address.address)
+    }
+
+    /// Get the addresses of an account
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/transfer-apis/onchain-addresses#list-addresses>
+    pub async fn addresses(&self, account_id: &str) -> Result<Vec<Address>, Error> {
+        let pages = pagination::paginate(|cursor: Option<String>| async move {
+            self.addresses_page(account_id, cursor.as_deref())
+                .await
+                .map(next_page)
+        });
+        self.client.with_deadline(pages.try_collect()).await
+    }
+
+    /// Get a page of addresses of an account
+    ///
+    /// Pass the [`Pagination::next_starting_after`] cursor of the previous page to get the next
+    /// one.
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/transfer-apis/onchain-addresses#list-addresses>
+    pub async fn addresses_page(
+        &self,
+        account_id: &str,
+        starting_after: Option<&str>,
+    ) -> Result<Page<Address>, Error> {
         let endpoint: String = format!("/v2/accounts/{account_id}/addresses");
-        let body: String = serde_json::to_string(&CreateAddressRequest {
-            network: BITCOIN_NETWORK,
-        })?;
+        let query: String = page_query(starting_after);
+        let res: Response = self.client.get(&endpoint, Some(&query)).await?;
+        let res: CoinbaseResponse<Vec<Address>> = res.json().await?;
+        Ok(Page::from(res))
+    }
+
+    /// Create a new address for an account, on its default network
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/transfer-apis/onchain-addresses#create-address>
+    pub async fn create_address(
+        &self,
+        account_id: &str,
+        name: Option<&str>,
+    ) -> Result<Address, Error> {
+        self.post_address(
+            account_id,
+            &CreateAddressRequest {
+                name,
+                network: None,
+            },
+        )
+        .await
+    }
 
+    async fn post_address(
+        &self,
+        account_id: &str,
+        request: &CreateAddressRequest<'_>,
+    ) -> Result<Address, Error> {
+        let endpoint: String = format!("/v2/accounts/{account_id}/addresses");
+        let body: String = serde_json::to_string(request)?;
         let res: Response = self.client.post(&endpoint, Some(body)).await?;
         let res: CoinbaseResponse<Address> = res.json().await?;
+        Ok(res.data)
+    }
 
-        if res.data.address.is_empty() {
-            return Err(Error::MissingDepositAddress);
-        }
+    /// Create an uncommitted send from an account
+    ///
+    /// The returned transaction includes the network fee estimate (see
+    /// [`Transaction::network_fee`]). Funds are moved only after [`CoinbaseAppClient::commit_send`].
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/transfer-apis/send-crypto>
+    pub async fn create_send(
+        &self,
+        account_id: &str,
+        req: &SendRequest,
+    ) -> Result<Transaction, Error> {
+        self.send(account_id, req, None).await
+    }
+
+    /// Create an uncommitted send from an account, confirmed with a 2FA token
+    ///
+    /// Use it to resubmit a [`CoinbaseAppClient::create_send`] that failed with
+    /// [`Error::TwoFactorRequired`]: the token is sent in the `CB-2FA-TOKEN` header. Pass the same
+    /// `req`, so that its `idem` token prevents a duplicated send if the first attempt went through.
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/transfer-apis/send-crypto>
+    pub async fn send_money_with_2fa(
+        &self,
+        account_id: &str,
+        req: &SendRequest,
+        token: &str,
+    ) -> Result<Transaction, Error> {
+        self.send(account_id, req, Some(token)).await
+    }
 
-        Ok(res.data.address)
+    async fn send(
+        &self,
+        account_id: &str,
+        req: &SendRequest,
+        two_factor_token: Option<&str>,
+    ) -> Result<Transaction, Error> {
+        let endpoint: String = format!("/v2/accounts/{account_id}/transactions");
+        let body: String = serde_json::to_string(req)?;
+        let res: Response = self
+            .client
+            .post_with_2fa(&endpoint, Some(body), two_factor_token)
+            .await?;
+        let res: CoinbaseResponse<Transaction> = res.json().await?;
+        Ok(res.data)
+    }
+
+    /// Commit a send created with [`CoinbaseAppClient::create_send`]
+    pub async fn commit_send(
+        &self,
+        account_id: &str,
+        transaction_id: &str,
+    ) -> Result<Transaction, Error> {
+        let endpoint: String =
+            format!("/v2/accounts/{account_id}/transactions/{transaction_id}/commit");
+        let res: Response = self.client.post(&endpoint, None).await?;
+        let res: CoinbaseResponse<Transaction> = res.json().await?;
+        Ok(res.data)
     }
 
     /// Get transactions by account ID
     ///
+    /// Loads all the pages: use [`CoinbaseAppClient::transactions_stream`] to process them lazily.
+    ///
     /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/transactions#list-transactions>
+    #[inline]
     pub async fn transactions(&self, account_id: &str) -> Result<Vec<Transaction>, Error> {
-        let mut transactions = Vec::new();
+        self.client
+            .with_deadline(self.transactions_stream(account_id).try_collect())
+            .await
+    }
+
+    /// Get transactions by account ID, keeping only the given types
+    ///
+    /// The filter is applied client-side, after loading all the pages. An empty `types` slice
+    /// keeps all the transactions.
+    pub async fn transactions_filtered(
+        &self,
+        account_id: &str,
+        types: &[TransactionType],
+    ) -> Result<Vec<Transaction>, Error> {
+        self.client
+            .with_deadline(
+                self.transactions_stream(account_id)
+                    .try_filter(|transaction| {
+                        future::ready(type_matches(transaction.r#type, types))
+                    })
+                    .try_collect(),
+            )
+            .await
+    }
+
+    /// Get a page of transactions by account ID
+    ///
+    /// Pass the [`Pagination::next_starting_after`] cursor of the previous page to get the next
+    /// one.
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/transactions#list-transactions>
+    pub async fn transactions_page(
+        &self,
+        account_id: &str,
+        starting_after: Option<&str>,
+    ) -> Result<Page<Transaction>, Error> {
+        let endpoint: String = format!("/v2/accounts/{account_id}/transactions");
+        let query: String = page_query(starting_after);
+        let res: Response = self.client.get(&endpoint, Some(&query)).await?;
+        let res: CoinbaseResponse<Vec<Transaction>> = res.json().await?;
+        Ok(Page::from(res))
+    }
+
+    /// Stream transactions by account ID
+    ///
+    /// Pages are fetched as the stream is consumed.
+    ///
+    /// <https://docs.cdp.coinbase.com/coinbase-app/track-apis/transactions#list-transactions>
+    pub fn transactions_stream<'a>(
+        &'a self,
+        account_id: &'a str,
+    ) -> impl Stream<Item = Result<Transaction, Error>> + 'a {
+        pagination::paginate(move |cursor: Option<String>| async move {
+            self.transactions_page(account_id, cursor.as_deref())
+                .await
+                .map(next_page)
+        })
+    }
+}
 
-        let mut next_uri: Option<String> = None;
+fn page_query(starting_after: Option<&str>) -> String {
+    match starting_after {
+        Some(starting_after) => format!("limit={PAGE_LIMIT}&starting_after={starting_after}"),
+        None => format!("limit={PAGE_LIMIT}"),
+    }
+}
 
-        loop {
-            let uri: String =
-                next_uri.unwrap_or_else(|| format!("/v2/accounts/{account_id}/transactions"));
+/// Convert a Coinbase page into a generic page
+fn next_page<T>(page: Page<T>) -> pagination::Page<T, String> {
+    pagination::Page::new(page.data, next_page_cursor(page.pagination))
+}
 
-            let res: Response = self.client.get(&uri, Some("limit=100")).await?;
+fn next_page_cursor(pagination: Option<Pagination>) -> Option<String> {
+    let pagination: Pagination = pagination?;
 
-            let res: CoinbaseResponse<Vec<Transaction>> = res.json().await?;
+    if let Some(cursor) = pagination.next_starting_after {
+        return Some(cursor);
+    }
 
-            transactions.extend(res.data);
+    // Fall back to the cursor embedded in the next page URI
+    let next_uri: Url = Url::parse(API_ROOT_URL)
+        .ok()?
+        .join(&pagination.next_uri?)
+        .ok()?;
+    next_uri
+        .query_pairs()
+        .find(|(key, _)| key == "starting_after")
+        .map(|(_, value)| value.into_owned())
+}
 
-            // Check if there is another page
-            if let Some(pagination) = res.pagination {
-                if let Some(next) = pagination.next_uri {
-                    next_uri = Some(next);
-                    continue;
-                }
-            }
+fn native_value(balance: &Money, price: &Money) -> f64 {
+    let value: Decimal = balance.value() * price.value();
+    value.to_f64().unwrap_or_default()
+}
 
-            break;
-        }
+fn assets_from_accounts(accounts: Vec<Account>) -> HashMap<String, AssetInfo> {
+    accounts
+        .into_iter()
+        .map(|account| {
+            (
+                account.currency.code.clone(),
+                AssetInfo::from(account.currency),
+            )
+        })
+        .collect()
+}
+
+fn sum_balances(accounts: &[Account]) -> Vec<(String, f64)> {
+    let mut balances: Vec<(String, Decimal)> = Vec::new();
+
+    for account in accounts {
+        let currency: &str = &account.balance.currency;
+        let amount: Decimal = account.balance.amount.value();
 
-        Ok(transactions)
+        match balances.iter_mut().find(|(c, _)| c == currency) {
+            Some((_, total)) => *total += amount,
+            None => balances.push((currency.to_string(), amount)),
+        }
     }
+
+    balances
+        .into_iter()
+        .filter(|(_, total)| !total.is_zero())
+        .map(|(currency, total)| (currency, total.to_f64().unwrap_or_default()))
+        .collect()
+}
+
+fn type_matches(r#type: TransactionType, types: &[TransactionType]) -> bool {
+    types.is_empty() || types.contains(&r#type)
 }
 
 fn find_bitcoin_wallet_account_id(accounts: &[Account]) -> Option<&str> {
     accounts
         .iter()
         .find(|account| {
-            account.currency.code == BTC_CURRENCY_CODE && account.r#type == WALLET_ACCOUNT_TYPE
+            account.currency.code == BTC_CURRENCY_CODE && account.r#type == AccountType::Wallet
         })
         .map(|account| account.id.as_str())
 }
@@ -155,25 +489,29 @@ fn find_bitcoin_wallet_account_id(accounts: &[Account]) -> Option<&str> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::app::response::{Balance, Currency};
+    use crate::app::response::{Balance, Currency, Money};
 
     fn make_account(id: &str, account_type: &str, currency_code: &str) -> Account {
         Account {
             id: id.to_string(),
             name: "test".to_string(),
             primary: false,
-            r#type: account_type.to_string(),
+            r#type: AccountType::from(account_type),
             currency: Currency {
                 asset_id: "asset".to_string(),
                 code: currency_code.to_string(),
                 name: currency_code.to_string(),
+                exponent: Some(8),
             },
             balance: Balance {
-                amount: 0.0,
+                amount: Money::from(Decimal::ZERO),
                 currency: currency_code.to_string(),
             },
+            native_balance: None,
             created_at: None,
             updated_at: None,
+            resource: String::from("account"),
+            resource_path: format!("/v2/accounts/{id}"),
         }
     }
 
@@ -191,6 +529,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_assets_from_accounts() {
+        let accounts = vec![
+            make_account("btc-wallet", "wallet", "BTC"),
+            make_account("btc-vault", "vault", "BTC"),
+            make_account("eth-wallet", "wallet", "ETH"),
+        ];
+
+        let assets = assets_from_accounts(accounts);
+        assert_eq!(assets.len(), 2);
+        assert_eq!(
+            assets.get("BTC"),
+            Some(&AssetInfo {
+                code: String::from("BTC"),
+                name: String::from("BTC"),
+                decimals: Some(8),
+            })
+        );
+    }
+
+    #[test]
+    fn test_sum_balances() {
+        let mut wallet = make_account("btc-wallet", "wallet", "BTC");
+        wallet.balance.amount = "0.5".parse().unwrap();
+        let mut vault = make_account("btc-vault", "vault", "BTC");
+        vault.balance.amount = "0.25".parse().unwrap();
+        let eth = make_account("eth-wallet", "wallet", "ETH");
+
+        assert_eq!(
+            sum_balances(&[wallet, eth, vault]),
+            vec![(String::from("BTC"), 0.75)]
+        );
+    }
+
+    #[test]
+    fn test_page_query() {
+        assert_eq!(page_query(None), "limit=100");
+        assert_eq!(
+            page_query(Some("a1b2c3")),
+            "limit=100&starting_after=a1b2c3"
+        );
+    }
+
+    #[test]
+    fn test_next_page_cursor() {
+        let json = r#"{"limit":25,"order":"desc","next_starting_after":"abc","next_uri":"/v2/accounts?starting_after=abc"}"#;
+        let pagination: Pagination = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            next_page_cursor(Some(pagination)),
+            Some(String::from("abc"))
+        );
+
+        let json =
+            r#"{"limit":25,"order":"desc","next_uri":"/v2/accounts?limit=25&starting_after=def"}"#;
+        let pagination: Pagination = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            next_page_cursor(Some(pagination)),
+            Some(String::from("def"))
+        );
+
+        let json = r#"{"limit":25,"order":"desc","next_uri":null}"#;
+        let pagination: Pagination = serde_json::from_str(json).unwrap();
+        assert_eq!(next_page_cursor(Some(pagination)), None);
+        assert_eq!(next_page_cursor(None), None);
+    }
+
+    #[test]
+    fn test_native_value() {
+        let balance: Money = "0.5".parse().unwrap();
+        let price: Money = "1020.25".parse().unwrap();
+        assert_eq!(native_value(&balance, &price), 510.125);
+    }
+
+    #[test]
+    fn test_type_matches() {
+        let types = [
+            TransactionType::Buy,
+            TransactionType::Sell,
+            TransactionType::Trade,
+        ];
+        assert!(type_matches(TransactionType::Buy, &types));
+        assert!(type_matches(TransactionType::Trade, &types));
+        assert!(!type_matches(TransactionType::Send, &types));
+
+        // No filter
+        assert!(type_matches(TransactionType::Send, &[]));
+    }
+
     #[test]
     fn test_find_bitcoin_wallet_account_id_missing() {
         let accounts = vec![
@@ -1,15 +1,25 @@
 //! Coinbase App error
 
+use chrono::{DateTime, Utc};
+use common::interceptor::{RequestError, ServerError};
 use thiserror::Error;
 
 use super::response::CoinbaseErrorMessage;
 
 /// Coinbase App error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Reqwest error
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
+    /// Request error, with the method and path of the request
+    #[error(transparent)]
+    Request(#[from] RequestError),
+    /// Blocking runtime error
+    #[cfg(feature = "blocking")]
+    #[error(transparent)]
+    Runtime(#[from] std::io::Error),
     /// Url error
     #[error(transparent)]
     Url(#[from] url::ParseError),
@@ -19,6 +29,18 @@ pub enum Error {
     /// Coinbase response error
     #[error("coinbase: {0}")]
     Coinbase(CoinbaseErrorMessage),
+    /// The request must be resubmitted with a 2FA token (usually HTTP 402)
+    ///
+    /// See [`CoinbaseAppClient::send_money_with_2fa`].
+    ///
+    /// [`CoinbaseAppClient::send_money_with_2fa`]: crate::app::client::CoinbaseAppClient::send_money_with_2fa
+    #[error("two-factor authentication required (HTTP {status}): {error}")]
+    TwoFactorRequired {
+        /// HTTP status code
+        status: u16,
+        /// Coinbase error
+        error: CoinbaseErrorMessage,
+    },
     /// Invalid private key
     #[error("invalid private key: {0}")]
     InvalidPrivateKey(String),
@@ -31,7 +53,54 @@ pub enum Error {
     /// Bitcoin wallet account not found
     #[error("bitcoin wallet account not found")]
     BitcoinWalletAccountNotFound,
+    /// Non-success HTTP response (i.e., 404 unknown account, 429 rate limit)
+    #[error("HTTP {status}: {body}")]
+    Http {
+        /// HTTP status code
+        status: u16,
+        /// Response body
+        body: String,
+    },
+    /// JWT rejected (HTTP 401)
+    ///
+    /// Reports the validity window of the rejected token: a window not covering the server
+    /// time points at a skewed local clock rather than a bad key.
+    #[error(
+        "unauthorized: token valid {}–{} UTC, server rejected: {body}",
+        .not_before.format("%H:%M:%S"),
+        .expires_at.format("%H:%M:%S")
+    )]
+    Unauthorized {
+        /// `nbf` claim of the token
+        not_before: DateTime<Utc>,
+        /// `exp` claim of the token
+        expires_at: DateTime<Utc>,
+        /// Response body
+        body: String,
+    },
+    /// Server error (HTTP 5xx)
+    #[error(transparent)]
+    ServerError(#[from] ServerError),
+    /// Overall deadline exceeded (see [`CoinbaseAppClientBuilder::overall_deadline`])
+    ///
+    /// [`CoinbaseAppClientBuilder::overall_deadline`]: crate::app::builder::CoinbaseAppClientBuilder::overall_deadline
+    #[error("deadline of {0:?} exceeded")]
+    Deadline(std::time::Duration),
     /// Missing deposit address in response
     #[error("missing deposit address")]
     MissingDepositAddress,
 }
+
+impl Error {
+    /// Get the HTTP status code, if a response was received
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Http { status, .. } | Self::TwoFactorRequired { status, .. } => Some(*status),
+            Self::ServerError(e) => Some(e.status),
+            Self::Unauthorized { .. } => Some(401),
+            Self::Reqwest(e) => e.status().map(|status| status.as_u16()),
+            Self::Request(e) => e.inner().status().map(|status| status.as_u16()),
+            _ => None,
+        }
+    }
+}
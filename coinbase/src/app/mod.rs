@@ -2,10 +2,14 @@
 //!
 //! <https://docs.cdp.coinbase.com/coinbase-app/introduction/welcome>
 
-mod agent;
+pub(crate) mod agent;
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod bucket;
 pub mod builder;
 pub mod client;
 mod constant;
 pub mod error;
+pub mod request;
 pub mod response;
@@ -0,0 +1,125 @@
+//! Coinbase App APIs requests
+
+use rust_decimal::Decimal;
+use serde::{Serialize, Serializer};
+
+use crate::util::uuid;
+
+fn serialize_decimal_as_string<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&value.to_string())
+}
+
+/// Send request
+///
+/// The transaction is created uncommitted: check its network fee, then confirm it with
+/// [`CoinbaseAppClient::commit_send`](super::client::CoinbaseAppClient::commit_send).
+///
+/// <https://docs.cdp.coinbase.com/coinbase-app/transfer-apis/send-crypto>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SendRequest {
+    r#type: &'static str,
+    /// Destination address or email
+    pub to: String,
+    /// Amount to send
+    #[serde(serialize_with = "serialize_decimal_as_string")]
+    pub amount: Decimal,
+    /// Currency of the amount (i.e., `BTC`)
+    pub currency: String,
+    /// Description, visible to the recipient
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Idempotency token, to avoid duplicated sends (default: random UUID)
+    ///
+    /// Resubmitting the same request (i.e., after a timeout) can't create a second send.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idem: Option<String>,
+    commit: bool,
+}
+
+impl SendRequest {
+    /// New send request
+    pub fn new<T, C>(to: T, amount: Decimal, currency: C) -> Self
+    where
+        T: Into<String>,
+        C: Into<String>,
+    {
+        Self {
+            r#type: "send",
+            to: to.into(),
+            amount,
+            currency: currency.into(),
+            description: None,
+            idem: Some(uuid::new_v4()),
+            commit: false,
+        }
+    }
+
+    /// Set description
+    #[inline]
+    pub fn description<S>(mut self, description: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Override the idempotency token
+    #[inline]
+    pub fn idem<S>(mut self, idem: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.idem = Some(idem.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_send_request() {
+        let req = SendRequest::new(
+            "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+            Decimal::new(1, 3),
+            "BTC",
+        )
+        .idem("b1a7c2f0");
+
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({
+                "type": "send",
+                "to": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+                "amount": "0.001",
+                "currency": "BTC",
+                "idem": "b1a7c2f0",
+                "commit": false
+            })
+        );
+    }
+
+    #[test]
+    fn test_send_request_default_idem() {
+        let req = SendRequest::new(
+            "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+            Decimal::ONE,
+            "BTC",
+        );
+        let other = req.clone();
+        assert_eq!(req.idem.as_deref().map(str::len), Some(36));
+        assert_eq!(req.idem, other.idem);
+
+        let new = SendRequest::new(
+            "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+            Decimal::ONE,
+            "BTC",
+        );
+        assert_ne!(req.idem, new.idem);
+    }
+}
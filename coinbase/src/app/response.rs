@@ -2,11 +2,17 @@
 //!
 //! <https://docs.cdp.coinbase.com/coinbase-app/introduction/welcome>
 
+use std::cmp::Ordering;
 use std::fmt;
+use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
-use common::deser::deserialize_string_to_f64;
-use serde::{Deserialize, Serialize};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Deserializer, Serialize, de};
+
+use super::client::CoinbaseAppClient;
+use super::error::Error;
 
 /// Coinbase App error message
 ///
@@ -25,34 +31,77 @@ impl fmt::Display for CoinbaseErrorMessage {
     }
 }
 
+/// Error ID returned when a send must be confirmed with a 2FA token
+pub(super) const TWO_FACTOR_REQUIRED: &str = "two_factor_required";
+
+#[derive(Deserialize)]
+pub(super) struct CoinbaseErrorResponse {
+    pub errors: Vec<CoinbaseErrorMessage>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct CoinbaseResponse<T> {
     pub pagination: Option<Pagination>,
     pub data: T,
 }
 
-// #[derive(Deserialize)]
-// pub(super) enum Order {
-//     #[serde(rename = "asc")]
-//     Ascending,
-//     #[serde(rename = "desc")]
-//     Descending,
-// }
+/// Sort order of a paginated list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+pub enum SortOrder {
+    /// Ascending
+    #[serde(rename = "asc")]
+    Ascending,
+    /// Descending
+    #[default]
+    #[serde(rename = "desc")]
+    Descending,
+}
 
-#[derive(Deserialize)]
-pub(super) struct Pagination {
-    // pub ending_before: Option<String>,
-    // pub starting_after: Option<String>,
-    // pub previous_ending_before: Option<String>,
-    // pub next_starting_after: Option<String>,
-    // pub limit: usize,
-    // pub order: Order,
-    // pub previous_uri: Option<String>,
+/// Pagination
+///
+/// <https://docs.cdp.coinbase.com/coinbase-app/api-architecture/pagination>
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Pagination {
+    /// Cursor used to request the current page, going backward
+    pub ending_before: Option<String>,
+    /// Cursor used to request the current page, going forward
+    pub starting_after: Option<String>,
+    /// Cursor to request the previous page
+    pub previous_ending_before: Option<String>,
+    /// Cursor to request the next page
+    pub next_starting_after: Option<String>,
+    /// Number of results per page
+    #[serde(default)]
+    pub limit: usize,
+    /// Sort order
+    #[serde(default)]
+    pub order: SortOrder,
+    /// URI of the previous page
+    pub previous_uri: Option<String>,
+    /// URI of the next page
     pub next_uri: Option<String>,
 }
 
+/// Page of a paginated list
+#[derive(Debug)]
+pub struct Page<T> {
+    /// Items
+    pub data: Vec<T>,
+    /// Pagination
+    pub pagination: Option<Pagination>,
+}
+
+impl<T> From<CoinbaseResponse<Vec<T>>> for Page<T> {
+    fn from(res: CoinbaseResponse<Vec<T>>) -> Self {
+        Self {
+            data: res.data,
+            pagination: res.pagination,
+        }
+    }
+}
+
 /// Account
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Account {
     // NOTE: the ID appears to be either a UUID or a token name e.g: "BTC"
     /// Account ID
@@ -61,18 +110,87 @@ pub struct Account {
     pub name: String,
     /// Primary account (or not)
     pub primary: bool,
-    /// Account’s type.
-    ///
-    /// Valid values: `wallet`, `fiat`, `vault`
-    pub r#type: String,
+    /// Account’s type
+    pub r#type: AccountType,
     /// Account’s currency
     pub currency: Currency,
     /// Account balance
     pub balance: Balance,
+    /// Fiat value of the balance, in the user native currency
+    ///
+    /// Omitted by some API versions.
+    #[serde(default)]
+    pub native_balance: Option<Balance>,
     /// Created at
     pub created_at: Option<DateTime<Utc>>,
     /// Updated at
     pub updated_at: Option<DateTime<Utc>>,
+    /// Resource name (i.e., `account`)
+    pub resource: String,
+    /// Canonical API path of the account (i.e., `/v2/accounts/<id>`)
+    pub resource_path: String,
+}
+
+impl Account {
+    /// Check if it's a vault account
+    ///
+    /// Withdrawals from vaults are delayed.
+    #[inline]
+    pub fn is_vault(&self) -> bool {
+        self.r#type == AccountType::Vault
+    }
+
+    /// Check if it's a fiat account
+    #[inline]
+    pub fn is_fiat(&self) -> bool {
+        self.r#type == AccountType::Fiat
+    }
+
+    /// Re-fetch the account (i.e., to read the balance after a send)
+    ///
+    /// See [`CoinbaseAppClient::account`].
+    #[inline]
+    pub async fn refresh(&self, client: &CoinbaseAppClient) -> Result<Self, Error> {
+        client.account(&self.id).await
+    }
+}
+
+/// Account type
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountType {
+    /// Crypto wallet
+    Wallet,
+    /// Fiat account
+    Fiat,
+    /// Vault (withdrawals are delayed)
+    Vault,
+    /// Account type not known by this library
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl AccountType {
+    /// Get the account type as sent by Coinbase (i.e., `wallet`)
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Wallet => "wallet",
+            Self::Fiat => "fiat",
+            Self::Vault => "vault",
+            Self::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for AccountType {
+    fn from(r#type: &str) -> Self {
+        match r#type {
+            "wallet" => Self::Wallet,
+            "fiat" => Self::Fiat,
+            "vault" => Self::Vault,
+            other => Self::Other(other.to_string()),
+        }
+    }
 }
 
 /// On-chain address.
@@ -86,22 +204,113 @@ pub struct Address {
     pub name: Option<String>,
     /// Blockchain network.
     pub network: String,
+    /// URI scheme (i.e., `bitcoin`).
+    #[serde(default)]
+    pub uri_scheme: Option<String>,
     /// Created at.
     pub created_at: DateTime<Utc>,
     /// Updated at.
     pub updated_at: DateTime<Utc>,
 }
 
+/// Spot price
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SpotPrice {
+    /// Price of one unit of the base currency
+    pub amount: Money,
+    /// Base currency (i.e., BTC)
+    pub base: String,
+    /// Quote currency (i.e., USD)
+    pub currency: String,
+}
+
 /// Account balance
 #[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize)]
 pub struct Balance {
     /// Amount
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub amount: f64,
+    pub amount: Money,
     /// Currency
     pub currency: String,
 }
 
+/// Monetary amount
+///
+/// Keeps the exact string sent by Coinbase along with its parsed decimal value,
+/// so amounts can be round-tripped without losing precision.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    raw: String,
+    value: Decimal,
+}
+
+impl Money {
+    /// Get the amount as sent by Coinbase (i.e., `39.59000000`)
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Get the decimal value
+    #[inline]
+    pub fn value(&self) -> Decimal {
+        self.value
+    }
+
+    /// Convert to `f64`
+    ///
+    /// The conversion may lose precision: use [`Money::value`] for exact arithmetic.
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        self.value.to_f64().unwrap_or_default()
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(value: Decimal) -> Self {
+        Self {
+            raw: value.to_string(),
+            value,
+        }
+    }
+}
+
+impl FromStr for Money {
+    type Err = rust_decimal::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            value: Decimal::from_str_exact(s)?,
+            raw: s.to_string(),
+        })
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(
+            self.value
+                .cmp(&other.value)
+                .then_with(|| self.raw.cmp(&other.raw)),
+        )
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw: String = String::deserialize(deserializer)?;
+        Self::from_str(&raw).map_err(de::Error::custom)
+    }
+}
+
 /// Currency
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
 pub struct Currency {
@@ -111,6 +320,30 @@ pub struct Currency {
     pub code: String,
     /// Currency name (i.e., Bitcoin)
     pub name: String,
+    /// Number of decimals (i.e., `8` for BTC)
+    #[serde(default)]
+    pub exponent: Option<u32>,
+}
+
+/// Asset metadata
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetInfo {
+    /// Currency code (i.e., `BTC`)
+    pub code: String,
+    /// Currency name (i.e., `Bitcoin`)
+    pub name: String,
+    /// Number of decimals, if provided
+    pub decimals: Option<u32>,
+}
+
+impl From<Currency> for AssetInfo {
+    fn from(currency: Currency) -> Self {
+        Self {
+            code: currency.code,
+            name: currency.name,
+            decimals: currency.exponent,
+        }
+    }
 }
 
 /// Transaction type
@@ -253,6 +486,34 @@ pub struct Transaction {
     pub description: Option<String>,
     /// Created at
     pub created_at: DateTime<Utc>,
+    /// Network details, for crypto sends and receives
+    #[serde(default)]
+    pub network: Option<TransactionNetwork>,
+    /// Resource name (i.e., `transaction`)
+    pub resource: String,
+    /// Canonical API path of the transaction (i.e., `/v2/accounts/<id>/transactions/<id>`)
+    pub resource_path: String,
+}
+
+impl Transaction {
+    /// Get the network fee, if any (i.e., the estimate of an uncommitted send)
+    #[inline]
+    pub fn network_fee(&self) -> Option<&Balance> {
+        self.network.as_ref()?.transaction_fee.as_ref()
+    }
+}
+
+/// Network details of a transaction
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize)]
+pub struct TransactionNetwork {
+    /// Network status (i.e., `unconfirmed`)
+    pub status: Option<String>,
+    /// Network name (i.e., `bitcoin`)
+    pub network_name: Option<String>,
+    /// On-chain transaction hash
+    pub hash: Option<String>,
+    /// Network fee
+    pub transaction_fee: Option<Balance>,
 }
 
 #[cfg(test)]
@@ -297,7 +558,9 @@ mod tests {
         assert_eq!(account.id, "2bbf394c-193b-5b2a-9155-3b4732659ede");
         assert_eq!(account.name, "My Wallet");
         assert_eq!(account.primary, true);
-        assert_eq!(account.r#type, "wallet");
+        assert_eq!(account.r#type, AccountType::Wallet);
+        assert!(!account.is_vault());
+        assert!(!account.is_fiat());
 
         // Verify currency fields
         assert_eq!(
@@ -306,14 +569,35 @@ mod tests {
         );
         assert_eq!(account.currency.code, "BTC");
         assert_eq!(account.currency.name, "Bitcoin");
+        assert_eq!(account.currency.exponent, Some(8));
 
-        // Verify balance fields - this is the key test for string-to-f64 deserialization
-        assert_eq!(account.balance.amount, 39.59);
+        // Verify balance fields - the original string must be preserved
+        assert_eq!(account.balance.amount.as_str(), "39.59000000");
+        assert_eq!(account.balance.amount.value(), Decimal::new(3959, 2));
+        assert_eq!(account.balance.amount.to_f64(), 39.59);
         assert_eq!(account.balance.currency, "BTC");
 
         // Verify optional fields
         assert_eq!(account.created_at.map(|t| t.timestamp()), Some(1706734142));
         assert_eq!(account.updated_at.map(|t| t.timestamp()), Some(1706734142));
+        assert_eq!(account.resource, "account");
+        assert_eq!(
+            account.resource_path,
+            "/v2/accounts/2bbf394c-193b-5b2a-9155-3b4732659ede"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_account_type() {
+        let r#type: AccountType = serde_json::from_str(r#""vault""#).unwrap();
+        assert_eq!(r#type, AccountType::Vault);
+
+        let r#type: AccountType = serde_json::from_str(r#""staking""#).unwrap();
+        assert_eq!(r#type, AccountType::Other(String::from("staking")));
+        assert_eq!(r#type.as_str(), "staking");
+
+        assert_eq!(AccountType::from("fiat"), AccountType::Fiat);
+        assert_eq!(AccountType::Wallet.as_str(), "wallet");
     }
 
     #[test]
@@ -440,7 +724,15 @@ mod tests {
 "##;
 
         let response: CoinbaseResponse<Vec<Transaction>> = serde_json::from_str(json).unwrap();
-        let transactions = response.data;
+        let page: Page<Transaction> = Page::from(response);
+
+        let pagination = page.pagination.unwrap();
+        assert_eq!(pagination.limit, 25);
+        assert_eq!(pagination.order, SortOrder::Descending);
+        assert_eq!(pagination.next_starting_after, None);
+        assert_eq!(pagination.next_uri, None);
+
+        let transactions = page.data;
 
         assert_eq!(transactions.len(), 4);
 
@@ -449,16 +741,34 @@ mod tests {
         assert_eq!(tx1.id, "4117f7d6-5694-5b36-bc8f-847509850ea4");
         assert_eq!(tx1.r#type, TransactionType::Buy);
         assert_eq!(tx1.status, TransactionStatus::Pending);
-        assert_eq!(tx1.amount.amount, 486.34313725);
-        assert_eq!(tx1.native_amount.amount, 4863.43);
+        assert_eq!(tx1.resource, "transaction");
+        assert_eq!(
+            tx1.resource_path,
+            "/v2/accounts/2bbf394c-193b-5b2a-9155-3b4732659ede/transactions/4117f7d6-5694-5b36-bc8f-847509850ea4"
+        );
+        assert_eq!(tx1.amount.amount.as_str(), "486.34313725");
+        assert_eq!(tx1.amount.amount.value(), Decimal::new(48634313725, 8));
+        assert_eq!(tx1.amount.amount.to_f64(), 486.34313725);
+        assert_eq!(tx1.native_amount.amount.to_f64(), 4863.43);
 
         // Third tx
         let tx3 = &transactions[2];
         assert_eq!(tx3.id, "ff01bbc6-c4ad-59e1-9601-e87b5b709458");
         assert_eq!(tx3.r#type, TransactionType::Transfer);
         assert_eq!(tx3.status, TransactionStatus::Completed);
-        assert_eq!(tx3.amount.amount, -5.0);
-        assert_eq!(tx3.native_amount.amount, -50.0);
+        assert_eq!(tx3.amount.amount.as_str(), "-5.00000000");
+        assert_eq!(tx3.amount.amount.to_f64(), -5.0);
+        assert_eq!(tx3.native_amount.amount.to_f64(), -50.0);
+    }
+
+    #[test]
+    fn test_money_is_lossless() {
+        let money: Money = serde_json::from_str(r#""0.123456789012345678""#).unwrap();
+        assert_eq!(money.as_str(), "0.123456789012345678");
+        assert_eq!(money.value(), Decimal::new(123456789012345678, 18));
+        assert_eq!(money.to_string(), "0.123456789012345678");
+
+        assert!(serde_json::from_str::<Money>(r#""abc""#).is_err());
     }
 
     #[test]
@@ -472,6 +782,7 @@ mod tests {
             "created_at": "2015-01-31T20:49:02Z",
             "updated_at": "2015-03-31T17:25:29-07:00",
             "network": "bitcoin",
+            "uri_scheme": "bitcoin",
             "resource": "address",
             "resource_path": "/v2/accounts/2bbf394c-193b-5b2a-9155-3b4732659ede/addresses/dd3183eb-af1d-5f5d-a90d-cbff946435ff"
           }
@@ -484,6 +795,19 @@ mod tests {
         assert_eq!(address.address, "mswUGcPHp1YnkLCgF1TtoryqSc5E9Q8xFa");
         assert_eq!(address.name, None);
         assert_eq!(address.network, "bitcoin");
+        assert_eq!(address.uri_scheme.as_deref(), Some("bitcoin"));
         assert_eq!(address.created_at.timestamp(), 1422737342);
     }
+
+    #[test]
+    fn test_deserialize_spot_price() {
+        let json = r#"{"data": {"amount": "1020.25", "base": "BTC", "currency": "USD"}}"#;
+
+        let response: CoinbaseResponse<SpotPrice> = serde_json::from_str(json).unwrap();
+        let price = response.data;
+
+        assert_eq!(price.amount.as_str(), "1020.25");
+        assert_eq!(price.base, "BTC");
+        assert_eq!(price.currency, "USD");
+    }
 }
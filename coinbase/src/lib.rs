@@ -5,6 +5,7 @@
 #![warn(clippy::large_futures)]
 #![warn(rustdoc::bare_urls)]
 
+pub mod advanced;
 pub mod app;
 pub mod prelude;
 mod util;
@@ -5,11 +5,19 @@
 #![allow(ambiguous_glob_reexports)]
 #![doc(hidden)]
 
+pub use ::common::cache::CacheConfig;
+pub use ::common::interceptor::{Interceptors, RequestError, ServerError};
+pub use ::reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+pub use ::rust_decimal::Decimal;
 pub use ::url::*;
 
+pub use crate::advanced::client::AdvancedTradeClient;
 pub use crate::app::auth::*;
+#[cfg(feature = "blocking")]
+pub use crate::app::blocking::*;
 pub use crate::app::builder::*;
 pub use crate::app::client::*;
 pub use crate::app::error::*;
+pub use crate::app::request::*;
 pub use crate::app::response::*;
 pub use crate::*;
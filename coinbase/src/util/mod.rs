@@ -1 +1,2 @@
 pub(super) mod time;
+pub(super) mod uuid;
@@ -0,0 +1,414 @@
+//! Replay recorded responses through the client
+
+use coinbase_api::prelude::*;
+use wiremock::matchers::{
+    body_json, body_partial_json, header, header_regex, method, path, query_param,
+};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ACCOUNT_ID: &str = "2bbf394c-193b-5b2a-9155-3b4732659ede";
+
+fn client(server: &MockServer) -> CoinbaseAppClient {
+    let auth = CoinbaseAuth::ApiKeys {
+        api_key: String::from("organizations/org/apiKeys/key"),
+        // Throwaway key, generated for these tests
+        secret_key: String::from(include_str!("fixtures/ec_key.pem")),
+    };
+
+    CoinbaseAppClient::builder()
+        .auth(auth)
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .build()
+        .unwrap()
+}
+
+/// Match the versioning and authentication headers
+fn authenticated(http_method: &str, http_path: &str) -> wiremock::MockBuilder {
+    Mock::given(method(http_method))
+        .and(path(http_path))
+        .and(header("CB-VERSION", "2022-01-06"))
+        .and(header_regex(
+            "Authorization",
+            r"^Bearer [\w-]+\.[\w-]+\.[\w-]+$",
+        ))
+}
+
+#[tokio::test]
+async fn test_accounts() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/v2/accounts")
+        .and(query_param("limit", "100"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/accounts.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let accounts = client(&server).accounts().await.unwrap();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].id, ACCOUNT_ID);
+    assert_eq!(accounts[0].currency.code, "BTC");
+    assert!(accounts[0].native_balance.is_none());
+}
+
+#[tokio::test]
+async fn test_account_by_currency_code() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/v2/accounts/BTC")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/account.json")),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+
+    let account = client.account("BTC").await.unwrap();
+    assert_eq!(account.id, "BTC");
+    assert_eq!(account.resource_path, "/v2/accounts/BTC");
+
+    let native_balance = account.native_balance.as_ref().unwrap();
+    assert_eq!(native_balance.amount.value(), Decimal::new(2742656, 2));
+    assert_eq!(native_balance.currency, "USD");
+
+    let account = account.refresh(&client).await.unwrap();
+    assert_eq!(account.balance.amount.value(), Decimal::new(4, 1));
+}
+
+#[tokio::test]
+async fn test_accounts_cache() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/v2/accounts")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/accounts.json")),
+        )
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = CoinbaseAppClient::builder()
+        .auth(CoinbaseAuth::ApiKeys {
+            api_key: String::from("organizations/org/apiKeys/key"),
+            secret_key: String::from(include_str!("fixtures/ec_key.pem")),
+        })
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .cache(CacheConfig::new().ttl("accounts", std::time::Duration::from_secs(60)))
+        .build()
+        .unwrap();
+
+    client.accounts().await.unwrap();
+    client.accounts().await.unwrap();
+
+    // Balances are never cached
+    client.all_balances().await.unwrap();
+
+    client.clear_cache();
+    client.accounts().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_spot_price() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/v2/prices/BTC-USD/spot")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/spot_price.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let price = client(&server).spot_price("BTC", "USD").await.unwrap();
+    assert_eq!(price.base, "BTC");
+    assert_eq!(price.amount.value(), Decimal::new(60000, 0));
+}
+
+#[tokio::test]
+async fn test_create_address() {
+    let server = MockServer::start().await;
+
+    authenticated("POST", &format!("/v2/accounts/{ACCOUNT_ID}/addresses"))
+        .and(body_json(serde_json::json!({ "name": "Deposits" })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/address.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let address = client(&server)
+        .create_address(ACCOUNT_ID, Some("Deposits"))
+        .await
+        .unwrap();
+    assert_eq!(
+        address.address,
+        "bc1q7l6gd6kvc3kd4lw4yeqsn8jedquawz2hzqpssp"
+    );
+    assert_eq!(address.uri_scheme.as_deref(), Some("bitcoin"));
+}
+
+#[tokio::test]
+async fn test_http_error() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", &format!("/v2/accounts/{ACCOUNT_ID}"))
+        .respond_with(ResponseTemplate::new(401).set_body_string(
+            r#"{"errors":[{"id":"authentication_error","message":"invalid api key"}]}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server).account(ACCOUNT_ID).await.unwrap_err();
+    assert_eq!(err.status(), Some(401));
+    assert!(
+        matches!(err, Error::Unauthorized { ref body, .. } if body.contains("invalid api key"))
+    );
+
+    let Error::Unauthorized {
+        not_before,
+        expires_at,
+        ..
+    } = err
+    else {
+        unreachable!()
+    };
+    assert_eq!((expires_at - not_before).num_seconds(), 120);
+    assert!(err.to_string().starts_with("unauthorized: token valid "));
+
+    authenticated("GET", "/v2/accounts/unknown")
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server).account("unknown").await.unwrap_err();
+    assert_eq!(err.status(), Some(404));
+}
+
+#[tokio::test]
+async fn test_overall_deadline() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/v2/accounts")
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/accounts.json"))
+                .set_delay(std::time::Duration::from_secs(2)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = CoinbaseAppClient::builder()
+        .auth(CoinbaseAuth::ApiKeys {
+            api_key: String::from("organizations/org/apiKeys/key"),
+            secret_key: String::from(include_str!("fixtures/ec_key.pem")),
+        })
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .overall_deadline(std::time::Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    let err = client.accounts().await.unwrap_err();
+    assert!(matches!(err, Error::Deadline(..)));
+}
+
+#[tokio::test]
+async fn test_create_and_commit_send() {
+    let server = MockServer::start().await;
+
+    let transaction_id: &str = "3c04e35e-8e5a-5ff1-9155-00675db4ac02";
+
+    let req = SendRequest::new(
+        "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+        Decimal::new(1, 3),
+        "BTC",
+    );
+
+    authenticated("POST", &format!("/v2/accounts/{ACCOUNT_ID}/transactions"))
+        .and(body_json(serde_json::json!({
+            "type": "send",
+            "to": "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+            "amount": "0.001",
+            "currency": "BTC",
+            "idem": req.idem,
+            "commit": false
+        })))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_string(include_str!("fixtures/send.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    authenticated(
+        "POST",
+        &format!("/v2/accounts/{ACCOUNT_ID}/transactions/{transaction_id}/commit"),
+    )
+    .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("fixtures/send.json")))
+    .expect(1)
+    .mount(&server)
+    .await;
+
+    let client = client(&server);
+    let transaction = client.create_send(ACCOUNT_ID, &req).await.unwrap();
+    assert_eq!(transaction.id, transaction_id);
+
+    let fee = transaction.network_fee().unwrap();
+    assert_eq!(fee.amount.value(), Decimal::new(21, 6));
+    assert_eq!(fee.currency, "BTC");
+
+    let transaction = client
+        .commit_send(ACCOUNT_ID, &transaction.id)
+        .await
+        .unwrap();
+    assert_eq!(transaction.id, transaction_id);
+}
+
+#[tokio::test]
+async fn test_send_money_with_2fa() {
+    let server = MockServer::start().await;
+
+    let endpoint: String = format!("/v2/accounts/{ACCOUNT_ID}/transactions");
+
+    let req = SendRequest::new(
+        "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh",
+        Decimal::new(1, 3),
+        "BTC",
+    );
+
+    // Both attempts must carry the same idempotency token
+    let idem = serde_json::json!({ "idem": req.idem });
+
+    authenticated("POST", &endpoint)
+        .and(header("CB-2FA-TOKEN", "123456"))
+        .and(body_partial_json(&idem))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_string(include_str!("fixtures/send.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    authenticated("POST", &endpoint)
+        .and(body_partial_json(&idem))
+        .respond_with(ResponseTemplate::new(402).set_body_string(
+            r#"{"errors":[{"id":"two_factor_required","message":"That code was invalid"}]}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+
+    let err = client.create_send(ACCOUNT_ID, &req).await.unwrap_err();
+    assert_eq!(err.status(), Some(402));
+    assert!(
+        matches!(err, Error::TwoFactorRequired { status: 402, ref error } if error.id == "two_factor_required")
+    );
+
+    let transaction = client
+        .send_money_with_2fa(ACCOUNT_ID, &req, "123456")
+        .await
+        .unwrap();
+    assert_eq!(transaction.id, "3c04e35e-8e5a-5ff1-9155-00675db4ac02");
+}
+
+#[tokio::test]
+async fn test_advanced_trade_accounts_in_portfolio() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v3/brokerage/accounts")
+        .and(query_param("limit", "250"))
+        .and(query_param(
+            "retail_portfolio_id",
+            "b87a2d3f-8a1e-49b3-a4ea-402d8c389aca",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/advanced_accounts.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let accounts = client(&server)
+        .advanced_trade()
+        .accounts_in_portfolio("b87a2d3f-8a1e-49b3-a4ea-402d8c389aca")
+        .await
+        .unwrap();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].currency, "BTC");
+    assert_eq!(accounts[0].available_balance.value.as_str(), "1.23000000");
+    assert_eq!(
+        accounts[0].retail_portfolio_id.as_deref(),
+        Some("b87a2d3f-8a1e-49b3-a4ea-402d8c389aca")
+    );
+}
+
+#[tokio::test]
+async fn test_healthcheck() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v2/time"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"data":{"iso":"2015-06-23T18:02:51Z","epoch":1435082571}}"#),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    authenticated("GET", "/v2/user")
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"data":{"id":"9da7a204-544e-5fd1-9a12-61176c5d4cd8","name":"User One","resource":"user","resource_path":"/v2/user"}}"#),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).healthcheck().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_default_headers() {
+    let server = MockServer::start().await;
+
+    for http_path in ["/v2/time", "/v2/user"] {
+        authenticated("GET", http_path)
+            .and(header("X-Gateway-Token", "token"))
+            .and(header_regex("User-Agent", "^coinbase-api/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data":{}}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    // Conflicting headers: the Coinbase ones must win
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Gateway-Token", HeaderValue::from_static("token"));
+    headers.insert("CB-VERSION", HeaderValue::from_static("2015-04-08"));
+    headers.insert("Authorization", HeaderValue::from_static("Basic other"));
+    headers.insert("User-Agent", HeaderValue::from_static("gateway"));
+
+    let auth = CoinbaseAuth::ApiKeys {
+        api_key: String::from("organizations/org/apiKeys/key"),
+        secret_key: String::from(include_str!("fixtures/ec_key.pem")),
+    };
+
+    let client = CoinbaseAppClient::builder()
+        .auth(auth)
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    client.healthcheck().await.unwrap();
+}
@@ -0,0 +1,123 @@
+//! Typed amounts
+//!
+//! Lightweight wrappers around `f64` that keep prices, quantities and fees apart in function
+//! signatures. They dereference to `f64`, so reading the value doesn't need any conversion.
+
+use std::fmt;
+use std::ops::Deref;
+
+use serde::{Deserialize, Deserializer, Serialize, de};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrFloat {
+    String(String),
+    Float(f64),
+}
+
+macro_rules! amount {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        ///
+        /// Deserialized from either a number or a numeric string (i.e., `"0.5"`).
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize)]
+        #[serde(transparent)]
+        pub struct $name(pub f64);
+
+        impl $name {
+            /// Construct a new amount
+            #[inline]
+            pub const fn new(value: f64) -> Self {
+                Self(value)
+            }
+
+            /// Get the raw value
+            #[inline]
+            pub const fn value(&self) -> f64 {
+                self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = f64;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl From<f64> for $name {
+            #[inline]
+            fn from(value: f64) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for f64 {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                match StringOrFloat::deserialize(deserializer)? {
+                    StringOrFloat::String(value) => value.parse().map(Self).map_err(de::Error::custom),
+                    StringOrFloat::Float(value) => Ok(Self(value)),
+                }
+            }
+        }
+    };
+}
+
+amount!(
+    /// Price, in the quote asset
+    Price
+);
+
+amount!(
+    /// Quantity, in the base asset unless the field or variant holding it states otherwise (i.e.,
+    /// a quote quantity)
+    Quantity
+);
+
+amount!(
+    /// Fee, in the fee asset
+    Fee
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_amount() {
+        let price: Price = serde_json::from_str("\"60000.5\"").unwrap();
+        assert_eq!(price, Price(60000.5));
+
+        let qty: Quantity = serde_json::from_str("0.0017").unwrap();
+        assert_eq!(qty, Quantity(0.0017));
+
+        assert!(serde_json::from_str::<Fee>("\"abc\"").is_err());
+    }
+
+    #[test]
+    fn test_amount_deref_and_display() {
+        let price = Price::new(2.0);
+        let qty = Quantity::from(1.5);
+        assert_eq!(*price * *qty, 3.0);
+        assert_eq!(price.to_string(), "2");
+        assert_eq!(serde_json::to_string(&qty).unwrap(), "1.5");
+    }
+}
@@ -0,0 +1,37 @@
+//! Blocking utilities
+
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+
+use tokio::runtime::Builder;
+
+/// Runtime used by the blocking clients
+///
+/// Wraps a current-thread tokio runtime. Cheap to clone: clones share the same runtime.
+///
+/// Must NOT be used from within an async context: [`Runtime::block_on`] panics if called
+/// from a thread that is already driving a tokio runtime.
+#[derive(Debug, Clone)]
+pub struct Runtime {
+    inner: Arc<tokio::runtime::Runtime>,
+}
+
+impl Runtime {
+    /// Build a new current-thread runtime
+    pub fn new() -> io::Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self {
+            inner: Arc::new(runtime),
+        })
+    }
+
+    /// Run a future to completion
+    #[inline]
+    pub fn block_on<F>(&self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        self.inner.block_on(future)
+    }
+}
@@ -0,0 +1,158 @@
+//! In-memory response cache
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Cache configuration
+///
+/// Caching is opt-in: only the endpoints with a TTL are cached. The cacheable endpoints are
+/// listed by each client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheConfig {
+    ttls: HashMap<String, Duration>,
+}
+
+impl CacheConfig {
+    /// Construct an empty configuration (nothing is cached)
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache the responses of an endpoint (i.e., `exchange_info`) for `ttl`
+    #[inline]
+    pub fn ttl<S>(mut self, endpoint: S, ttl: Duration) -> Self
+    where
+        S: Into<String>,
+    {
+        self.ttls.insert(endpoint.into(), ttl);
+        self
+    }
+
+    /// Get the TTL of an endpoint, if cached
+    #[inline]
+    pub fn get_ttl(&self, endpoint: &str) -> Option<Duration> {
+        self.ttls.get(endpoint).copied()
+    }
+}
+
+struct Entry {
+    expires_at: Instant,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+/// In-memory response cache
+///
+/// Values are stored per endpoint and key (i.e., the symbol), and expire after the endpoint TTL.
+pub struct ResponseCache {
+    config: CacheConfig,
+    entries: Mutex<HashMap<(String, String), Entry>>,
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(CacheConfig::default())
+    }
+}
+
+impl ResponseCache {
+    /// Construct a new cache
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get a cached value, if not expired
+    pub fn get<T>(&self, endpoint: &str, key: &str) -> Option<T>
+    where
+        T: Clone + 'static,
+    {
+        // Skip the lock if the endpoint is not cached
+        self.config.get_ttl(endpoint)?;
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let id: (String, String) = (endpoint.to_string(), key.to_string());
+
+        match entries.get(&id) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                entry.value.downcast_ref::<T>().cloned()
+            }
+            Some(..) => {
+                entries.remove(&id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache a value, if the endpoint has a TTL
+    pub fn insert<T>(&self, endpoint: &str, key: &str, value: &T)
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let Some(ttl) = self.config.get_ttl(endpoint) else {
+            return;
+        };
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(
+            (endpoint.to_string(), key.to_string()),
+            Entry {
+                expires_at: Instant::now() + ttl,
+                value: Arc::new(value.clone()),
+            },
+        );
+    }
+
+    /// Remove all the cached values
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_only_configured_endpoints() {
+        let config = CacheConfig::new().ttl("ticker", Duration::from_secs(60));
+        let cache = ResponseCache::new(config);
+
+        cache.insert("ticker", "tBTCUSD", &1.5_f64);
+        cache.insert("wallets", "", &2.5_f64);
+
+        assert_eq!(cache.get::<f64>("ticker", "tBTCUSD"), Some(1.5));
+        assert_eq!(cache.get::<f64>("ticker", "tETHUSD"), None);
+        assert_eq!(cache.get::<f64>("wallets", ""), None);
+
+        // Wrong type
+        assert_eq!(cache.get::<u64>("ticker", "tBTCUSD"), None);
+
+        cache.clear();
+        assert_eq!(cache.get::<f64>("ticker", "tBTCUSD"), None);
+    }
+
+    #[test]
+    fn test_cache_expiration() {
+        let config = CacheConfig::new().ttl("ticker", Duration::ZERO);
+        let cache = ResponseCache::new(config);
+
+        cache.insert("ticker", "tBTCUSD", &1.5_f64);
+        assert_eq!(cache.get::<f64>("ticker", "tBTCUSD"), None);
+    }
+}
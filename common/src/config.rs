@@ -0,0 +1,257 @@
+//! Exchange credentials config
+//!
+//! Load the API keys of every exchange from a single TOML or JSON file:
+//!
+//! ```toml
+//! [binance]
+//! api_key = "..."
+//! api_secret = "..."
+//!
+//! [okx]
+//! api_key = "..."
+//! api_secret = "..."
+//! passphrase = "..."
+//!
+//! [strike]
+//! api_key = "..."
+//! ```
+//!
+//! Each exchange crate converts its section into the native auth type (i.e., `BinanceAuth::from(keys)`).
+//! Loading the file requires the `config` feature.
+
+use std::fmt;
+#[cfg(feature = "config")]
+use std::path::Path;
+#[cfg(feature = "config")]
+use std::{fs, io};
+
+use serde::Deserialize;
+
+/// Config error
+#[cfg(feature = "config")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// I/O error
+    Io(io::Error),
+    /// Invalid TOML or unexpected config structure
+    Toml(toml::de::Error),
+    /// Invalid JSON
+    Json(serde_json::Error),
+    /// Unsupported file extension
+    UnsupportedFormat(String),
+}
+
+#[cfg(feature = "config")]
+impl std::error::Error for Error {}
+
+#[cfg(feature = "config")]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "io: {e}"),
+            Self::Toml(e) => write!(f, "toml: {e}"),
+            Self::Json(e) => write!(f, "json: {e}"),
+            Self::UnsupportedFormat(ext) => {
+                write!(
+                    f,
+                    "unsupported config format: '{ext}' (expected toml or json)"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Self::Toml(e)
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// API key and secret
+///
+/// Used by Binance, Bitfinex, Coinbase and Kraken.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct ApiKeys {
+    /// API Key
+    pub api_key: String,
+    /// API Secret
+    #[serde(alias = "secret_key")]
+    pub api_secret: String,
+}
+
+impl fmt::Debug for ApiKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeys").finish()
+    }
+}
+
+/// API key, secret and passphrase
+///
+/// Used by OKX.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct PassphraseApiKeys {
+    /// API Key
+    pub api_key: String,
+    /// API Secret
+    #[serde(alias = "secret_key")]
+    pub api_secret: String,
+    /// API Passphrase
+    pub passphrase: String,
+}
+
+impl fmt::Debug for PassphraseApiKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PassphraseApiKeys").finish()
+    }
+}
+
+/// Single API key
+///
+/// Used by Strike.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct ApiKey {
+    /// API Key
+    pub api_key: String,
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKey").finish()
+    }
+}
+
+/// Credentials of all the exchanges
+///
+/// Every section is optional: only the configured exchanges are set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Credentials {
+    /// Binance
+    #[serde(default)]
+    pub binance: Option<ApiKeys>,
+    /// Bitfinex
+    #[serde(default)]
+    pub bitfinex: Option<ApiKeys>,
+    /// Coinbase
+    #[serde(default)]
+    pub coinbase: Option<ApiKeys>,
+    /// Kraken
+    #[serde(default)]
+    pub kraken: Option<ApiKeys>,
+    /// OKX
+    #[serde(default)]
+    pub okx: Option<PassphraseApiKeys>,
+    /// Strike
+    #[serde(default)]
+    pub strike: Option<ApiKey>,
+}
+
+#[cfg(feature = "config")]
+impl Credentials {
+    /// Parse credentials from a TOML string
+    #[inline]
+    pub fn from_toml(s: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Parse credentials from a JSON string
+    #[inline]
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+/// Load credentials from a config file
+///
+/// The format is selected by the file extension (`.toml` or `.json`).
+#[cfg(feature = "config")]
+pub fn load_from_path<P>(path: P) -> Result<Credentials, Error>
+where
+    P: AsRef<Path>,
+{
+    let path: &Path = path.as_ref();
+    let ext: &str = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+
+    match ext.to_ascii_lowercase().as_str() {
+        "toml" => Credentials::from_toml(&fs::read_to_string(path)?),
+        "json" => Credentials::from_json(&fs::read_to_string(path)?),
+        _ => Err(Error::UnsupportedFormat(ext.to_string())),
+    }
+}
+
+#[cfg(all(test, feature = "config"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials_from_toml() {
+        let toml = r#"
+strike = { api_key = "strike-key" }
+
+[binance]
+api_key = "binance-key"
+api_secret = "binance-secret"
+
+[okx]
+api_key = "okx-key"
+api_secret = "okx-secret"
+passphrase = "okx-pass"
+"#;
+        let credentials = Credentials::from_toml(toml).unwrap();
+
+        let binance = credentials.binance.unwrap();
+        assert_eq!(binance.api_key, "binance-key");
+        assert_eq!(binance.api_secret, "binance-secret");
+
+        let okx = credentials.okx.unwrap();
+        assert_eq!(okx.passphrase, "okx-pass");
+
+        assert_eq!(credentials.strike.unwrap().api_key, "strike-key");
+        assert!(credentials.kraken.is_none());
+    }
+
+    #[test]
+    fn test_credentials_from_json() {
+        let json = r#"{"coinbase":{"api_key":"cb-key","secret_key":"cb-secret"}}"#;
+        let credentials = Credentials::from_json(json).unwrap();
+
+        let coinbase = credentials.coinbase.unwrap();
+        assert_eq!(coinbase.api_key, "cb-key");
+        assert_eq!(coinbase.api_secret, "cb-secret");
+        assert!(credentials.binance.is_none());
+    }
+
+    #[test]
+    fn test_credentials_missing_field() {
+        let toml = "[okx]\napi_key = \"key\"\napi_secret = \"secret\"\n";
+        let err = Credentials::from_toml(toml).unwrap_err();
+        assert!(matches!(err, Error::Toml(..)));
+    }
+
+    #[test]
+    fn test_load_unsupported_format() {
+        let err = load_from_path("keys.yaml").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported config format: 'yaml' (expected toml or json)"
+        );
+    }
+}
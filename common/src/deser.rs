@@ -19,6 +19,20 @@ where
     s.parse().map_err(de::Error::custom)
 }
 
+/// Deserialize a string as f64, with an empty string as `None`
+///
+/// Use it together with `#[serde(default)]` to also accept a missing field.
+pub fn deserialize_optional_string_to_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s.as_deref() {
+        None | Some("") => Ok(None),
+        Some(s) => s.parse().map(Some).map_err(de::Error::custom),
+    }
+}
+
 /// Deserialize a string or number as u64
 pub fn deserialize_string_or_number_to_u64<'de, D>(deserializer: D) -> Result<u64, D::Error>
 where
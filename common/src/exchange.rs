@@ -0,0 +1,98 @@
+//! Supported exchanges
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Parse exchange error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseExchangeError(String);
+
+impl std::error::Error for ParseExchangeError {}
+
+impl fmt::Display for ParseExchangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown exchange: {}", self.0)
+    }
+}
+
+/// Supported exchange
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Exchange {
+    /// Binance
+    Binance,
+    /// Bitfinex
+    Bitfinex,
+    /// Coinbase
+    Coinbase,
+    /// Kraken
+    Kraken,
+    /// OKX
+    Okx,
+    /// Strike
+    Strike,
+}
+
+impl Exchange {
+    /// All supported exchanges
+    pub const ALL: [Self; 6] = [
+        Self::Binance,
+        Self::Bitfinex,
+        Self::Coinbase,
+        Self::Kraken,
+        Self::Okx,
+        Self::Strike,
+    ];
+
+    /// Get the exchange name (i.e., `binance`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Binance => "binance",
+            Self::Bitfinex => "bitfinex",
+            Self::Coinbase => "coinbase",
+            Self::Kraken => "kraken",
+            Self::Okx => "okx",
+            Self::Strike => "strike",
+        }
+    }
+}
+
+impl fmt::Display for Exchange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Exchange {
+    type Err = ParseExchangeError;
+
+    /// Parse the exchange name, case-insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|exchange| exchange.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseExchangeError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_roundtrip() {
+        for exchange in Exchange::ALL {
+            let name: String = exchange.to_string();
+            assert_eq!(Exchange::from_str(&name).unwrap(), exchange);
+        }
+    }
+
+    #[test]
+    fn test_exchange_from_str() {
+        assert_eq!(Exchange::from_str("OKX").unwrap(), Exchange::Okx);
+        assert_eq!(Exchange::from_str("Binance").unwrap(), Exchange::Binance);
+        assert_eq!(
+            Exchange::from_str("ftx").unwrap_err(),
+            ParseExchangeError(String::from("ftx"))
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Request and response interceptors
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+type RequestHook = Arc<dyn Fn(RequestBuilder) -> RequestBuilder + Send + Sync>;
+type ResponseHook = Arc<dyn Fn(&Response) + Send + Sync>;
+
+/// Request and response interceptors
+///
+/// Hooks applied by the clients to every request they send (i.e., to add a `X-Request-Id` header
+/// or record the latency). Retried requests go through the hooks again.
+#[derive(Clone, Default)]
+pub struct Interceptors {
+    request: Option<RequestHook>,
+    response: Option<ResponseHook>,
+}
+
+impl fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interceptors")
+            .field("request", &self.request.is_some())
+            .field("response", &self.response.is_some())
+            .finish()
+    }
+}
+
+impl Interceptors {
+    /// Construct empty interceptors (requests are sent as built by the client)
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Modify every request right before it's sent
+    ///
+    /// Runs after the client added its own headers and signature.
+    #[inline]
+    pub fn on_request<F>(mut self, f: F) -> Self
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder + Send + Sync + 'static,
+    {
+        self.request = Some(Arc::new(f));
+        self
+    }
+
+    /// Observe every response, before it's handled by the client
+    #[inline]
+    pub fn on_response<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Response) + Send + Sync + 'static,
+    {
+        self.response = Some(Arc::new(f));
+        self
+    }
+
+    /// Apply the request interceptor, if any
+    #[inline]
+    pub fn intercept_request(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.request {
+            Some(hook) => hook(request),
+            None => request,
+        }
+    }
+
+    /// Apply the response observer, if any
+    #[inline]
+    pub fn observe_response(&self, response: &Response) {
+        if let Some(hook) = &self.response {
+            hook(response);
+        }
+    }
+
+    /// Send a request through the interceptors
+    ///
+    /// Errors carry the method and path of the request.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, RequestError> {
+        let (client, request) = self.intercept_request(request).build_split();
+
+        let request = request.map_err(|e| RequestError::new(None, e))?;
+        let method: Method = request.method().clone();
+
+        let mut response: Response = client
+            .execute(request)
+            .await
+            .map_err(|e| RequestError::new(Some(method.clone()), e))?;
+
+        // Keep the method, for the errors of `error_for_status` and `json`
+        response.extensions_mut().insert(method);
+
+        self.observe_response(&response);
+
+        Ok(response)
+    }
+}
+
+/// Fail if the response has an error status (4xx or 5xx)
+///
+/// Unlike [`Response::error_for_status`], the error doesn't hold the URL of the request.
+pub fn error_for_status(response: Response) -> Result<Response, RequestError> {
+    let method: Option<Method> = response.extensions().get::<Method>().cloned();
+    response
+        .error_for_status()
+        .map_err(|e| RequestError::new(method, e))
+}
+
+/// Fail with a [`ServerError`] if the response has a server error status (5xx)
+///
+/// Other responses, including the client errors (4xx), are returned as they are.
+pub async fn error_for_server_status(response: Response) -> Result<Response, ServerError> {
+    let status: StatusCode = response.status();
+    if !status.is_server_error() {
+        return Ok(response);
+    }
+
+    let body: String = response.text().await.unwrap_or_default();
+    Err(ServerError::new(status.as_u16(), &body))
+}
+
+/// Deserialize the JSON body of a response
+///
+/// Errors carry the method and path of the request.
+pub async fn json<T>(response: Response) -> Result<T, RequestError>
+where
+    T: DeserializeOwned,
+{
+    let method: Option<Method> = response.extensions().get::<Method>().cloned();
+    let path: String = response.url().path().to_string();
+    response.json().await.map_err(|e| RequestError {
+        method,
+        path,
+        source: e.without_url(),
+    })
+}
+
+/// Failed request
+///
+/// Only the path of the URL is kept: the query string may hold signatures or API keys.
+#[derive(Debug)]
+pub struct RequestError {
+    method: Option<Method>,
+    path: String,
+    source: reqwest::Error,
+}
+
+impl RequestError {
+    /// Wrap the error of a request, dropping the query string of its URL
+    pub fn new(method: Option<Method>, source: reqwest::Error) -> Self {
+        let path: String = source
+            .url()
+            .map(|url| url.path().to_string())
+            .unwrap_or_default();
+
+        Self {
+            method,
+            path,
+            source: source.without_url(),
+        }
+    }
+
+    /// Get the HTTP method, if the request was built
+    #[inline]
+    pub fn method(&self) -> Option<&Method> {
+        self.method.as_ref()
+    }
+
+    /// Get the URL path (i.e., `/api/v3/account`)
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the underlying error
+    #[inline]
+    pub fn inner(&self) -> &reqwest::Error {
+        &self.source
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.method {
+            Some(method) => write!(f, "{method} {}: {}", self.path, self.source),
+            None => write!(f, "{}: {}", self.path, self.source),
+        }
+    }
+}
+
+impl StdError for RequestError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Exchange-side error (HTTP 5xx): the request can be retried later
+///
+/// The body is truncated to [`ServerError::BODY_MAX_LEN`] bytes: a proxy may return a whole
+/// HTML page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerError {
+    /// HTTP status code
+    pub status: u16,
+    /// Response body (truncated)
+    pub body: String,
+}
+
+impl ServerError {
+    /// Max length of the stored body, in bytes
+    pub const BODY_MAX_LEN: usize = 256;
+
+    /// Construct a server error, truncating the body
+    pub fn new(status: u16, body: &str) -> Self {
+        let body: String = if body.len() > Self::BODY_MAX_LEN {
+            let mut end: usize = Self::BODY_MAX_LEN;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            format!("{}...", &body[..end])
+        } else {
+            body.to_string()
+        };
+
+        Self { status, body }
+    }
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "server error (HTTP {}): {}", self.status, self.body)
+    }
+}
+
+impl StdError for ServerError {}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Client;
+
+    use super::*;
+
+    #[test]
+    fn test_intercept_request() {
+        let interceptors =
+            Interceptors::new().on_request(|request| request.header("X-Request-Id", "abc"));
+
+        let request = interceptors
+            .intercept_request(Client::new().get("http://localhost"))
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["X-Request-Id"], "abc");
+
+        // No interceptor
+        let request = Interceptors::new()
+            .intercept_request(Client::new().get("http://localhost"))
+            .build()
+            .unwrap();
+        assert!(request.headers().is_empty());
+    }
+
+    #[test]
+    fn test_send_error_hides_query() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let request = Client::new().get("http://127.0.0.1:1/api/v3/account?signature=secret");
+
+        let err = runtime
+            .block_on(Interceptors::new().send(request))
+            .unwrap_err();
+        assert_eq!(err.method(), Some(&Method::GET));
+        assert_eq!(err.path(), "/api/v3/account");
+        assert!(err.to_string().starts_with("GET /api/v3/account: "));
+        assert!(!err.to_string().contains("secret"));
+        assert!(err.inner().is_connect());
+    }
+
+    #[test]
+    fn test_server_error_truncates_body() {
+        let err = ServerError::new(502, "bad gateway");
+        assert_eq!(err.body, "bad gateway");
+        assert_eq!(err.to_string(), "server error (HTTP 502): bad gateway");
+
+        let body: String = "é".repeat(ServerError::BODY_MAX_LEN);
+        let err = ServerError::new(503, &body);
+        assert_eq!(err.status, 503);
+        assert_eq!(err.body.len(), ServerError::BODY_MAX_LEN + "...".len());
+        assert!(err.body.ends_with("é..."));
+    }
+}
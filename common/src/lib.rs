@@ -5,4 +5,14 @@
 #![warn(clippy::large_futures)]
 #![warn(rustdoc::bare_urls)]
 
+pub mod amount;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
+pub mod config;
 pub mod deser;
+pub mod exchange;
+pub mod interceptor;
+pub mod pagination;
+pub mod rate_limit;
+pub mod trade;
@@ -0,0 +1,127 @@
+//! Paginated fetching
+
+use std::future::Future;
+
+use futures_util::stream::{self, Stream, TryStreamExt};
+
+/// Page of items, with the cursor of the next one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T, C> {
+    /// Items
+    pub items: Vec<T>,
+    /// Cursor of the next page (`None` if this is the last page)
+    pub next: Option<C>,
+}
+
+impl<T, C> Page<T, C> {
+    /// Construct a page with an explicit next cursor (i.e., returned by the exchange)
+    #[inline]
+    pub fn new(items: Vec<T>, next: Option<C>) -> Self {
+        Self { items, next }
+    }
+
+    /// Construct a page of an API paginated by item (i.e., `after` the last item ID)
+    ///
+    /// The next cursor is extracted from the last item. A page with fewer than `limit` items is
+    /// the last one.
+    pub fn from_last_item<F>(items: Vec<T>, limit: usize, cursor: F) -> Self
+    where
+        F: FnOnce(&T) -> C,
+    {
+        let next: Option<C> = if items.len() < limit {
+            None
+        } else {
+            items.last().map(cursor)
+        };
+
+        Self { items, next }
+    }
+}
+
+/// Stream the items of all the pages
+///
+/// `fetch` gets a page from its cursor (`None` for the first page). Pages are fetched as the
+/// stream is consumed, until a page without next cursor or the first error.
+pub fn paginate<T, C, E, F, Fut>(mut fetch: F) -> impl Stream<Item = Result<T, E>>
+where
+    F: FnMut(Option<C>) -> Fut,
+    Fut: Future<Output = Result<Page<T, C>, E>>,
+{
+    // `None` once the last page has been fetched
+    let state: Option<Option<C>> = Some(None);
+
+    stream::try_unfold(state, move |state| {
+        // Boxed to keep the stream small, whatever the size of the request future
+        let page = state.map(|cursor| Box::pin(fetch(cursor)));
+        async move {
+            match page {
+                Some(page) => {
+                    let page: Page<T, C> = page.await?;
+                    Ok(Some((page.items, page.next.map(Some))))
+                }
+                None => Ok(None),
+            }
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures_util::StreamExt;
+
+    use super::*;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_page_from_last_item() {
+        let page = Page::from_last_item(vec![1, 2, 3], 3, |item| item * 10);
+        assert_eq!(page.next, Some(30));
+
+        let page = Page::from_last_item(vec![1, 2], 3, |item| item * 10);
+        assert_eq!(page.next, None);
+
+        let page = Page::from_last_item(Vec::<u32>::new(), 0, |item| item * 10);
+        assert_eq!(page.next, None);
+    }
+
+    #[test]
+    fn test_paginate() {
+        let cursors: Arc<Mutex<Vec<Option<u32>>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let stream = paginate(|cursor: Option<u32>| {
+            cursors.lock().unwrap().push(cursor);
+            async move {
+                let start: u32 = cursor.unwrap_or_default();
+                let items: Vec<u32> = (start..(start + 2).min(5)).collect();
+                Ok::<_, ()>(Page::from_last_item(items, 2, |item| item + 1))
+            }
+        });
+
+        let items: Vec<u32> = block_on(stream.try_collect()).unwrap();
+        assert_eq!(items, vec![0, 1, 2, 3, 4]);
+        assert_eq!(*cursors.lock().unwrap(), vec![None, Some(2), Some(4)]);
+    }
+
+    #[test]
+    fn test_paginate_stops_on_error() {
+        let stream = paginate(|cursor: Option<u32>| async move {
+            match cursor {
+                None => Ok(Page::new(vec![1, 2], Some(1))),
+                Some(..) => Err("boom"),
+            }
+        });
+
+        let items: Vec<Result<u32, &str>> = block_on(stream.collect());
+        assert_eq!(items, vec![Ok(1), Ok(2), Err("boom")]);
+    }
+}
@@ -0,0 +1,166 @@
+//! Client-side rate limiting
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tokio::time;
+
+/// Sliding-window rate limiter
+///
+/// Allows up to `limit` weight within any `window`. Unlike limiters driven by the response headers,
+/// it holds requests back before a burst exceeds the limit, so it also works for exchanges that
+/// don't report the used weight.
+///
+/// Wrap it in an [`Arc`](std::sync::Arc) to share it across clients hitting the same limit.
+pub struct SlidingWindow {
+    limit: u32,
+    window: Duration,
+    /// Time and weight of the requests within the window, oldest first
+    entries: Mutex<VecDeque<(Instant, u32)>>,
+}
+
+impl fmt::Debug for SlidingWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlidingWindow")
+            .field("limit", &self.limit)
+            .field("window", &self.window)
+            .finish()
+    }
+}
+
+impl SlidingWindow {
+    /// Construct a limiter allowing `limit` weight per `window` (i.e., 20 requests per 2 secs)
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit: limit.max(1),
+            window,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Wait until `weight` is available, then take it
+    ///
+    /// A weight above the limit is capped to the limit.
+    pub async fn acquire(&self, weight: u32) {
+        while let Err(wait) = self.try_acquire_at(weight, Instant::now()) {
+            time::sleep(wait).await;
+        }
+    }
+
+    /// Take `weight` if available, without waiting
+    ///
+    /// Returns the time to wait before retrying if not available.
+    #[inline]
+    pub fn try_acquire(&self, weight: u32) -> Result<(), Duration> {
+        self.try_acquire_at(weight, Instant::now())
+    }
+
+    /// Get the weight used within the current window
+    pub fn used(&self) -> u32 {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        self.prune(&mut entries, Instant::now());
+        entries.iter().map(|(_, weight)| weight).sum()
+    }
+
+    fn try_acquire_at(&self, weight: u32, now: Instant) -> Result<(), Duration> {
+        let weight: u32 = weight.min(self.limit);
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        self.prune(&mut entries, now);
+
+        let used: u32 = entries.iter().map(|(_, weight)| weight).sum();
+
+        if used + weight <= self.limit {
+            entries.push_back((now, weight));
+            return Ok(());
+        }
+
+        // Wait until enough of the oldest requests leave the window
+        let mut to_free: u32 = used + weight - self.limit;
+        for (at, weight) in entries.iter() {
+            to_free = to_free.saturating_sub(*weight);
+            if to_free == 0 {
+                return Err((*at + self.window).saturating_duration_since(now));
+            }
+        }
+
+        // Unreachable: the window holds at most `limit` weight
+        Err(self.window)
+    }
+
+    /// Remove the requests that left the window
+    fn prune(&self, entries: &mut VecDeque<(Instant, u32)>, now: Instant) {
+        while let Some((at, _)) = entries.front() {
+            if now.saturating_duration_since(*at) < self.window {
+                break;
+            }
+            entries.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sliding_window() {
+        let limiter = SlidingWindow::new(5, Duration::from_secs(2));
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire_at(2, start).is_ok());
+        assert!(
+            limiter
+                .try_acquire_at(3, start + Duration::from_secs(1))
+                .is_ok()
+        );
+
+        // Full: the first request leaves the window at 2 secs
+        assert_eq!(
+            limiter.try_acquire_at(1, start + Duration::from_millis(1500)),
+            Err(Duration::from_millis(500))
+        );
+
+        // Both requests must leave the window
+        assert_eq!(
+            limiter.try_acquire_at(4, start + Duration::from_millis(1500)),
+            Err(Duration::from_millis(1500))
+        );
+
+        // The first request left the window
+        assert!(
+            limiter
+                .try_acquire_at(2, start + Duration::from_secs(2))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_sliding_window_caps_weight() {
+        let limiter = SlidingWindow::new(5, Duration::from_secs(1));
+        assert!(limiter.try_acquire(10).is_ok());
+        assert_eq!(limiter.used(), 5);
+        assert!(limiter.try_acquire(1).is_err());
+    }
+
+    #[test]
+    fn test_acquire_waits() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let limiter = SlidingWindow::new(2, Duration::from_millis(100));
+            let start = Instant::now();
+
+            limiter.acquire(1).await;
+            limiter.acquire(1).await;
+            limiter.acquire(1).await;
+
+            assert!(start.elapsed() >= Duration::from_millis(100));
+        });
+    }
+}
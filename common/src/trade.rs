@@ -0,0 +1,103 @@
+//! Normalized trade
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::amount::{Fee, Price, Quantity};
+use crate::exchange::Exchange;
+
+/// Trade side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TradeSide {
+    /// Buy
+    Buy,
+    /// Sell
+    Sell,
+}
+
+impl TradeSide {
+    /// Get the side name (i.e., `BUY`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Buy => "BUY",
+            Self::Sell => "SELL",
+        }
+    }
+}
+
+impl fmt::Display for TradeSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Trade normalized across exchanges
+///
+/// Quantities are always positive: the direction is given by [`NormalizedTrade::side`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedTrade {
+    /// Exchange
+    pub exchange: Exchange,
+    /// Symbol, in the exchange format (i.e., `BTCUSDT`, `BTC-USDT`, `tBTCUSD`)
+    pub symbol: String,
+    /// Side
+    pub side: TradeSide,
+    /// Base quantity
+    pub base_qty: Quantity,
+    /// Quote quantity
+    pub quote_qty: Quantity,
+    /// Execution price
+    pub price: Price,
+    /// Fee, positive when charged and negative for rebates
+    pub fee: Fee,
+    /// Fee currency
+    pub fee_currency: String,
+    /// Execution timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Trade ID
+    pub trade_id: String,
+}
+
+impl fmt::Display for NormalizedTrade {
+    /// Format as `BUY 0.5 BTC-USDT @ 51858 (fee 0.0000019 BTC) on okx`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} @ {} (fee {} {}) on {}",
+            self.side,
+            self.base_qty,
+            self.symbol,
+            self.price,
+            self.fee,
+            self.fee_currency,
+            self.exchange
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_normalized_trade() {
+        let trade = NormalizedTrade {
+            exchange: Exchange::Okx,
+            symbol: String::from("BTC-USDT"),
+            side: TradeSide::Buy,
+            base_qty: Quantity(0.5),
+            quote_qty: Quantity(25929.0),
+            price: Price(51858.0),
+            fee: Fee(0.0000019),
+            fee_currency: String::from("BTC"),
+            timestamp: DateTime::from_timestamp(1597026383, 0).unwrap(),
+            trade_id: String::from("1"),
+        };
+
+        assert_eq!(
+            trade.to_string(),
+            "BUY 0.5 BTC-USDT @ 51858 (fee 0.0000019 BTC) on okx"
+        );
+    }
+}
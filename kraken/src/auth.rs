@@ -6,6 +6,7 @@ use std::time::SystemTime;
 
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
+use common::config::ApiKeys;
 use hmac::{Hmac, Mac};
 use sha2::{Digest, Sha256, Sha512};
 use url::Url;
@@ -40,6 +41,12 @@ impl fmt::Debug for KrakenAuth {
     }
 }
 
+impl From<ApiKeys> for KrakenAuth {
+    fn from(keys: ApiKeys) -> Self {
+        Self::api_keys(keys.api_key, keys.api_secret)
+    }
+}
+
 impl KrakenAuth {
     /// Construct API key credentials
     pub fn api_keys<K, S>(key: K, secret: S) -> Self
@@ -0,0 +1,85 @@
+//! Blocking Kraken client
+
+use common::blocking::Runtime;
+
+use crate::client::KrakenClient;
+use crate::error::Error;
+use crate::response::{DepositTransaction, Trade, WithdrawTransaction};
+
+impl KrakenClient {
+    /// Get a blocking client sharing this client
+    ///
+    /// See [`BlockingKrakenClient`].
+    pub fn blocking(&self) -> Result<BlockingKrakenClient, Error> {
+        Ok(BlockingKrakenClient {
+            client: self.clone(),
+            runtime: Runtime::new()?,
+        })
+    }
+}
+
+/// Blocking Kraken client
+///
+/// Thin wrapper that runs the [`KrakenClient`] requests on an internal current-thread runtime.
+/// Must NOT be used from within an async context.
+#[derive(Debug, Clone)]
+pub struct BlockingKrakenClient {
+    client: KrakenClient,
+    runtime: Runtime,
+}
+
+impl BlockingKrakenClient {
+    /// Get the async client
+    #[inline]
+    pub fn as_async(&self) -> &KrakenClient {
+        &self.client
+    }
+
+    /// Check that the API is reachable and that the exchange is online
+    ///
+    /// See [`KrakenClient::healthcheck`].
+    #[inline]
+    pub fn healthcheck(&self) -> Result<(), Error> {
+        self.runtime.block_on(self.client.healthcheck())
+    }
+
+    /// Get **bitcoin** balance.
+    ///
+    /// See [`KrakenClient::balance`].
+    #[inline]
+    pub fn balance(&self) -> Result<f64, Error> {
+        self.runtime.block_on(self.client.balance())
+    }
+
+    /// Get a **bitcoin** deposit address.
+    ///
+    /// See [`KrakenClient::bitcoin_deposit_address`].
+    #[inline]
+    pub fn bitcoin_deposit_address(&self) -> Result<String, Error> {
+        self.runtime.block_on(self.client.bitcoin_deposit_address())
+    }
+
+    /// Get **bitcoin** deposit transactions.
+    ///
+    /// See [`KrakenClient::deposit_transactions`].
+    #[inline]
+    pub fn deposit_transactions(&self) -> Result<Vec<DepositTransaction>, Error> {
+        self.runtime.block_on(self.client.deposit_transactions())
+    }
+
+    /// Get **bitcoin** withdraw transactions.
+    ///
+    /// See [`KrakenClient::withdraw_transactions`].
+    #[inline]
+    pub fn withdraw_transactions(&self) -> Result<Vec<WithdrawTransaction>, Error> {
+        self.runtime.block_on(self.client.withdraw_transactions())
+    }
+
+    /// Get **bitcoin** trade history.
+    ///
+    /// See [`KrakenClient::trade_history`].
+    #[inline]
+    pub fn trade_history(&self) -> Result<Vec<Trade>, Error> {
+        self.runtime.block_on(self.client.trade_history())
+    }
+}
@@ -2,8 +2,9 @@
 
 use std::time::Duration;
 
+use common::interceptor::{self, Interceptors};
 use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{Client, Response};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::de::DeserializeOwned;
 use url::Url;
 
@@ -15,8 +16,8 @@ use crate::request::{
     WithdrawStatus,
 };
 use crate::response::{
-    BitcoinBalances, DepositAddress, DepositTransaction, KrakenResult, Trade, TradesHistory,
-    WithdrawTransaction,
+    BitcoinBalances, DepositAddress, DepositTransaction, KrakenResult, SystemStatus, Trade,
+    TradesHistory, WithdrawTransaction,
 };
 
 enum Api<'a> {
@@ -92,6 +93,8 @@ pub struct KrakenClient {
     client: Client,
     /// Authentication
     auth: KrakenAuth,
+    /// Request and response interceptors
+    interceptors: Interceptors,
 }
 
 impl KrakenClient {
@@ -99,32 +102,50 @@ impl KrakenClient {
     pub fn new(auth: KrakenAuth) -> Result<Self, Error> {
         Ok(Self {
             root_url: Url::parse(API_ROOT_URL)?,
-            client: Client::builder()
-                .user_agent(USER_AGENT_NAME)
-                .timeout(Duration::from_secs(25))
-                .build()?,
+            client: build_http_client(HeaderMap::new())?,
             auth,
+            interceptors: Interceptors::default(),
         })
     }
 
-    async fn query<T>(&self, url: Url, headers: HeaderMap, body_json: String) -> Result<T, Error>
+    /// Set a custom root URL (i.e., a proxy or a mock server)
+    #[inline]
+    pub fn with_root_url(mut self, root_url: Url) -> Self {
+        self.root_url = root_url;
+        self
+    }
+
+    /// Set headers added to every request (default: none)
+    ///
+    /// The client headers (i.e., authentication) win on conflict. Rebuilds the HTTP client.
+    #[inline]
+    pub fn with_default_headers(mut self, headers: HeaderMap) -> Result<Self, Error> {
+        self.client = build_http_client(headers)?;
+        Ok(self)
+    }
+
+    /// Set request and response interceptors (default: none)
+    ///
+    /// The request interceptor runs after the authentication headers are added.
+    #[inline]
+    pub fn with_interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
+    async fn query<T>(&self, request: RequestBuilder) -> Result<T, Error>
     where
         T: DeserializeOwned,
     {
-        // Post request
-        let response: Response = self
-            .client
-            .post(url)
-            .headers(headers)
-            .body(body_json)
-            .send()
-            .await?;
+        let response: Response = self.interceptors.send(request).await?;
+
+        let response: Response = interceptor::error_for_server_status(response).await?;
 
         // If HTTP error, return error
-        let response: Response = response.error_for_status()?;
+        let response: Response = interceptor::error_for_status(response)?;
 
         // Parse the response as JSON
-        let result: KrakenResult<T> = response.json().await?;
+        let result: KrakenResult<T> = interceptor::json(response).await?;
 
         // Extract the result
         result.extract()
@@ -156,13 +177,43 @@ impl KrakenClient {
                 headers.insert("API-Sign", HeaderValue::from_str(&sig)?);
                 headers.insert("Content-Type", HeaderValue::from_static("application/json"));
 
-                // Query
-                self.query(url, headers, body_json).await
+                // Post request
+                let request = self.client.post(url).headers(headers).body(body_json);
+                self.query(request).await
             }
             KrakenAuth::None => Err(Error::MissingCredentials),
         }
     }
 
+    async fn query_public<T>(&self, method: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let path: String = format!("/{API_VERSION}/public/{method}");
+        let url: Url = self.root_url.join(&path)?;
+        self.query(self.client.get(url)).await
+    }
+
+    /// Check that the API is reachable and that the exchange is online
+    ///
+    /// Returns [`Error::NotOnline`] if the exchange is in maintenance or in a restricted mode.
+    /// If API keys are configured, they are verified by fetching the balances.
+    ///
+    /// <https://docs.kraken.com/api/docs/rest-api/get-system-status>
+    pub async fn healthcheck(&self) -> Result<(), Error> {
+        let status: SystemStatus = self.query_public("SystemStatus").await?;
+
+        if status.status != "online" {
+            return Err(Error::NotOnline(status.status));
+        }
+
+        if let KrakenAuth::ApiKeys(..) = self.auth {
+            let _: BitcoinBalances = self.query_private(Api::Balance).await?;
+        }
+
+        Ok(())
+    }
+
     /// Get **bitcoin** balance.
     pub async fn balance(&self) -> Result<f64, Error> {
         // Get bitcoin balances
@@ -214,10 +265,19 @@ impl KrakenClient {
         // Filter for trades involving Bitcoin (checking if pair contains the ticker)
         let trades: Vec<Trade> = history
             .trades
-            .into_values()
-            .filter(|trade| trade.pair.contains(XBT_TICKER))
+            .into_iter()
+            .filter(|(_, trade)| trade.pair.contains(XBT_TICKER))
+            .map(|(txid, trade)| Trade { txid, ..trade })
             .collect();
 
         Ok(trades)
     }
 }
+
+fn build_http_client(default_headers: HeaderMap) -> Result<Client, Error> {
+    Ok(Client::builder()
+        .default_headers(default_headers)
+        .user_agent(USER_AGENT_NAME)
+        .timeout(Duration::from_secs(25))
+        .build()?)
+}
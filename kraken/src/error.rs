@@ -1,10 +1,12 @@
 //! Kraken error
 
+use common::interceptor::{RequestError, ServerError};
 use reqwest::header::InvalidHeaderValue;
 use thiserror::Error;
 
 /// Kraken error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Base64 error
     #[error(transparent)]
@@ -12,6 +14,13 @@ pub enum Error {
     /// Reqwest error
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
+    /// Request error, with the method and path of the request
+    #[error(transparent)]
+    Request(#[from] RequestError),
+    /// Blocking runtime error
+    #[cfg(feature = "blocking")]
+    #[error(transparent)]
+    Runtime(#[from] std::io::Error),
     /// Invalid header error
     #[error(transparent)]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
@@ -30,6 +39,12 @@ pub enum Error {
     /// Missing result in response
     #[error("missing result")]
     MissingResult,
+    /// Server error (HTTP 5xx)
+    #[error(transparent)]
+    ServerError(#[from] ServerError),
+    /// The exchange is not online (i.e., maintenance, cancel only or post only mode)
+    #[error("exchange not online: {0}")]
+    NotOnline(String),
     /// Missing deposit address in response
     #[error("missing deposit address")]
     MissingDepositAddress,
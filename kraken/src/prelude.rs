@@ -5,9 +5,13 @@
 #![allow(ambiguous_glob_reexports)]
 #![doc(hidden)]
 
+pub use ::common::interceptor::{Interceptors, RequestError, ServerError};
+pub use ::reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 pub use ::url::*;
 
 pub use crate::auth::*;
+#[cfg(feature = "blocking")]
+pub use crate::blocking::*;
 pub use crate::client::*;
 pub use crate::constant::*;
 pub use crate::error::*;
@@ -3,7 +3,10 @@
 use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
+use common::amount::{Fee, Price, Quantity};
 use common::deser::{deserialize_string_to_f64, deserialize_unix_timestamp_seconds_to_utc_seconds};
+use common::exchange::Exchange;
+use common::trade::{NormalizedTrade, TradeSide};
 use serde::{Deserialize, Deserializer, de};
 
 use crate::constant::TICKERS;
@@ -28,6 +31,13 @@ impl<T> KrakenResult<T> {
     }
 }
 
+/// System status
+#[derive(Debug, Deserialize)]
+pub(crate) struct SystemStatus {
+    /// `online`, `maintenance`, `cancel_only` or `post_only`
+    pub(crate) status: String,
+}
+
 /// Bitcoin balances
 ///
 /// This struct deserializes ONLY the bitcoin balances (see [`TICKERS`]).
@@ -184,6 +194,9 @@ pub enum TrateType {
 /// Trade history trade entry
 #[derive(Debug, Deserialize)]
 pub struct Trade {
+    /// Transaction ID (the key of the trade in the history)
+    #[serde(skip)]
+    pub txid: String,
     /// Unique identifier of trade executed
     #[serde(rename = "trade_id")]
     pub id: String,
@@ -218,6 +231,60 @@ pub struct Trade {
     pub vol: f64,
 }
 
+impl From<Trade> for NormalizedTrade {
+    /// The fee is reported in the quote currency, taken from the pair (empty if not recognized).
+    fn from(trade: Trade) -> Self {
+        Self {
+            exchange: Exchange::Kraken,
+            fee_currency: quote_currency(&trade.pair).to_string(),
+            symbol: trade.pair,
+            side: match trade.r#type {
+                TrateType::Buy => TradeSide::Buy,
+                TrateType::Sell => TradeSide::Sell,
+            },
+            base_qty: Quantity(trade.vol),
+            quote_qty: Quantity(trade.cost),
+            price: Price(trade.price),
+            fee: Fee(trade.fee),
+            timestamp: trade.time,
+            trade_id: trade.txid,
+        }
+    }
+}
+
+/// Quote currencies, with the legacy `X`/`Z` prefixed codes first (i.e., `XXBTZUSD`)
+const QUOTE_CURRENCIES: [(&str, &str); 20] = [
+    ("ZUSD", "USD"),
+    ("ZEUR", "EUR"),
+    ("ZGBP", "GBP"),
+    ("ZCAD", "CAD"),
+    ("ZJPY", "JPY"),
+    ("ZCHF", "CHF"),
+    ("ZAUD", "AUD"),
+    ("XXBT", "XBT"),
+    ("XETH", "ETH"),
+    ("USDT", "USDT"),
+    ("USDC", "USDC"),
+    ("USD", "USD"),
+    ("EUR", "EUR"),
+    ("GBP", "GBP"),
+    ("CAD", "CAD"),
+    ("JPY", "JPY"),
+    ("CHF", "CHF"),
+    ("AUD", "AUD"),
+    ("XBT", "XBT"),
+    ("ETH", "ETH"),
+];
+
+/// Get the quote currency of a pair (i.e., `USD` for `XXBTZUSD` and `XBTUSD`)
+fn quote_currency(pair: &str) -> &'static str {
+    QUOTE_CURRENCIES
+        .iter()
+        .find(|(code, _)| pair.len() > code.len() && pair.ends_with(code))
+        .map(|(_, currency)| *currency)
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,4 +354,37 @@ mod tests {
         assert_eq!(address.address, "32iVBEu4dxkUQk9dJbZUiBiQdmypcEyJRf");
         //assert!(address.new);
     }
+
+    #[test]
+    fn test_normalized_trade() {
+        let json = r#"{"ordertxid":"OQCLML-BW3P3-BUCMWZ","postxid":"TKH2SE-M7IF5-CFI7LT","pair":"XXBTZUSD","time":1688667796,"type":"buy","ordertype":"limit","price":"30010.00000","cost":"600.20000","fee":"0.00000","vol":"0.02000000","margin":"0.00000","misc":"","trade_id":"40274859"}"#;
+
+        let trade: Trade = serde_json::from_str(json).expect("Failed to deserialize");
+        let trade = Trade {
+            txid: String::from("TCWJEG-FL4SZ-3FKGH6"),
+            ..trade
+        };
+
+        let normalized = NormalizedTrade::from(trade);
+        assert_eq!(normalized.exchange, Exchange::Kraken);
+        assert_eq!(normalized.symbol, "XXBTZUSD");
+        assert_eq!(normalized.side, TradeSide::Buy);
+        assert_eq!(normalized.base_qty, Quantity(0.02));
+        assert_eq!(normalized.quote_qty, Quantity(600.2));
+        assert_eq!(normalized.price, Price(30010.0));
+        assert_eq!(normalized.fee, Fee(0.0));
+        assert_eq!(normalized.fee_currency, "USD");
+        assert_eq!(normalized.timestamp.timestamp(), 1688667796);
+        assert_eq!(normalized.trade_id, "TCWJEG-FL4SZ-3FKGH6");
+    }
+
+    #[test]
+    fn test_quote_currency() {
+        assert_eq!(quote_currency("XXBTZUSD"), "USD");
+        assert_eq!(quote_currency("XBTUSDT"), "USDT");
+        assert_eq!(quote_currency("XBTEUR"), "EUR");
+        assert_eq!(quote_currency("XETHXXBT"), "XBT");
+        assert_eq!(quote_currency("SOLXBT"), "XBT");
+        assert_eq!(quote_currency("XBTDAI"), "");
+    }
 }
@@ -0,0 +1,145 @@
+//! Replay recorded responses through the client
+
+use kraken_api::prelude::*;
+use wiremock::matchers::{header, header_exists, header_regex, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(server: &MockServer) -> KrakenClient {
+    let auth = KrakenAuth::ApiKeys(KrakenApiCredentials {
+        key: String::from("key"),
+        // Base64 of `secret`
+        secret: String::from("c2VjcmV0"),
+    });
+
+    KrakenClient::new(auth)
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap())
+}
+
+/// Match the authentication headers
+fn authenticated(http_path: &str) -> wiremock::MockBuilder {
+    Mock::given(method("POST"))
+        .and(path(http_path))
+        .and(header("API-Key", "key"))
+        .and(header_exists("API-Sign"))
+}
+
+#[tokio::test]
+async fn test_balance() {
+    let server = MockServer::start().await;
+
+    authenticated("/0/private/Balance")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balance.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let balance = client(&server).balance().await.unwrap();
+    assert_eq!(balance, 0.75);
+}
+
+#[tokio::test]
+async fn test_healthcheck() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/0/public/SystemStatus"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"error":[],"result":{"status":"online","timestamp":"2023-07-06T18:52:00Z"}}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    authenticated("/0/private/Balance")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balance.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).healthcheck().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_healthcheck_not_online() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/0/public/SystemStatus"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"error":[],"result":{"status":"maintenance","timestamp":"2023-07-06T18:52:00Z"}}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server).healthcheck().await.unwrap_err();
+    assert!(matches!(err, Error::NotOnline(ref status) if status == "maintenance"));
+}
+
+#[tokio::test]
+async fn test_bitcoin_deposit_address() {
+    let server = MockServer::start().await;
+
+    authenticated("/0/private/DepositAddresses")
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/deposit_addresses.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let address = client(&server).bitcoin_deposit_address().await.unwrap();
+    assert_eq!(address, "bc1qxdsh4sdd29h6ldehz0se5c61asq8cgwyjf2y3z");
+
+    // The request parameters are sent as JSON
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["asset"], "XBT");
+    assert_eq!(body["method"], "Bitcoin");
+    assert!(body["nonce"].is_u64());
+}
+
+#[tokio::test]
+async fn test_api_error() {
+    let server = MockServer::start().await;
+
+    authenticated("/0/private/Balance")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/error.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(client(&server).balance().await.is_err());
+}
+
+#[tokio::test]
+async fn test_default_headers() {
+    let server = MockServer::start().await;
+
+    authenticated("/0/private/Balance")
+        .and(header("X-Gateway-Token", "token"))
+        .and(header_regex("User-Agent", "^kraken-api/"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balance.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Conflicting headers: the Kraken ones must win
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Gateway-Token", HeaderValue::from_static("token"));
+    headers.insert("API-Key", HeaderValue::from_static("other"));
+    headers.insert("User-Agent", HeaderValue::from_static("gateway"));
+
+    let client = client(&server).with_default_headers(headers).unwrap();
+    assert_eq!(client.balance().await.unwrap(), 0.75);
+}
@@ -0,0 +1,51 @@
+//! Place an order on OKX demo trading
+//!
+//! ```sh
+//! OKX_API_KEY=.. OKX_API_SECRET=.. OKX_PASSPHRASE=.. ALLOW_WRITES=1 cargo run --example okx_place_order
+//! ```
+//!
+//! Demo trading keys are created from the demo trading account. Without `ALLOW_WRITES=1` the
+//! order is only printed.
+//!
+//! A withdrawal example (`okx/examples/withdraw.rs`) is deferred: OKX demo trading doesn't
+//! support withdrawals.
+
+use std::env;
+
+use okx_api::prelude::*;
+
+#[tokio::main]
+async fn main() {
+    let credentials = OkxApiCredentials {
+        api_key: env::var("OKX_API_KEY").expect("OKX_API_KEY not set"),
+        api_secret: env::var("OKX_API_SECRET").expect("OKX_API_SECRET not set"),
+        passphrase: env::var("OKX_PASSPHRASE").expect("OKX_PASSPHRASE not set"),
+    };
+
+    // Route the requests to demo trading
+    let interceptors =
+        Interceptors::new().on_request(|request| request.header("x-simulated-trading", "1"));
+
+    let client = OkxClient::builder(credentials)
+        .interceptors(interceptors)
+        .build()
+        .unwrap();
+
+    // Sell 0.001 BTC at 1,000,000 USDT (won't be filled)
+    let order = OrderRequest::limit(
+        "BTC-USDT",
+        TradeMode::Cash,
+        TradeSide::Sell,
+        Quantity(0.001),
+        Price(1_000_000.0),
+    );
+
+    if env::var("ALLOW_WRITES").as_deref() != Ok("1") {
+        println!("Dry run, set ALLOW_WRITES=1 to place the order:");
+        println!("{order:#?}");
+        return;
+    }
+
+    let order = client.place_order(order).await.unwrap();
+    println!("Order: {order:#?}");
+}
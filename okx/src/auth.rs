@@ -5,6 +5,7 @@ use std::fmt;
 use base64::Engine;
 use base64::engine::general_purpose;
 use chrono::{DateTime, Utc};
+use common::config::PassphraseApiKeys;
 use hmac::{Hmac, Mac};
 use reqwest::Method;
 use sha2::Sha256;
@@ -31,6 +32,42 @@ impl fmt::Debug for OkxApiCredentials {
     }
 }
 
+impl From<PassphraseApiKeys> for OkxApiCredentials {
+    fn from(keys: PassphraseApiKeys) -> Self {
+        Self {
+            api_key: keys.api_key,
+            api_secret: keys.api_secret,
+            passphrase: keys.passphrase,
+        }
+    }
+}
+
+impl OkxApiCredentials {
+    /// Check that the credentials are well-formed
+    ///
+    /// Catches common misconfigurations (i.e., empty or badly pasted fields) before the first
+    /// request is sent. It does NOT check that the credentials are accepted by OKX.
+    pub fn validate(&self) -> Result<(), Error> {
+        validate_field("API key", &self.api_key)?;
+        validate_field("API secret", &self.api_secret)?;
+        validate_field("API passphrase", &self.passphrase)
+    }
+}
+
+fn validate_field(name: &str, value: &str) -> Result<(), Error> {
+    if value.is_empty() {
+        return Err(Error::AuthenticationError(format!("{name} is empty")));
+    }
+
+    if value.trim() != value {
+        return Err(Error::AuthenticationError(format!(
+            "{name} has leading or trailing whitespaces"
+        )));
+    }
+
+    Ok(())
+}
+
 /// <https://www.okx.com/docs-v5/en/#overview-rest-authentication>
 pub(crate) fn generate_signature(
     api_secret: &str,
@@ -65,6 +102,32 @@ mod tests {
 
     use super::*;
 
+    fn make_credentials(api_key: &str, api_secret: &str, passphrase: &str) -> OkxApiCredentials {
+        OkxApiCredentials {
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+            passphrase: passphrase.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_credentials() {
+        let credentials = make_credentials("key", "22582BD0CFF14C41EDBF1AB98506286D", "pass");
+        assert!(credentials.validate().is_ok());
+
+        let credentials = make_credentials("key", "22582BD0CFF14C41EDBF1AB98506286D", "");
+        assert_eq!(
+            credentials.validate().unwrap_err().to_string(),
+            "authentication: API passphrase is empty"
+        );
+
+        let credentials = make_credentials(" key\n", "22582BD0CFF14C41EDBF1AB98506286D", "pass");
+        assert_eq!(
+            credentials.validate().unwrap_err().to_string(),
+            "authentication: API key has leading or trailing whitespaces"
+        );
+    }
+
     #[test]
     fn test_generate_signature() {
         let timestamp = DateTime::from_str("2020-12-08T09:08:57.715Z").unwrap();
@@ -0,0 +1,220 @@
+//! Blocking OKX client
+
+use common::blocking::Runtime;
+
+use crate::client::OkxClient;
+use crate::error::Error;
+use crate::request::{InstrumentType, OrderRequest, TransferRequest};
+use crate::response::{
+    Account, AccountConfig, Bill, CurrencyInfo, DepositAddress, DepositTransaction, OkxResponse,
+    OkxResponseItem, PlacedOrder, SubAccount, Trade, Transfer, WithdrawalStatus,
+    WithdrawalTransaction,
+};
+
+impl OkxClient {
+    /// Get a blocking client sharing this client
+    ///
+    /// See [`BlockingOkxClient`].
+    pub fn blocking(&self) -> Result<BlockingOkxClient, Error> {
+        Ok(BlockingOkxClient {
+            client: self.clone(),
+            runtime: Runtime::new()?,
+        })
+    }
+}
+
+/// Blocking OKX client
+///
+/// Thin wrapper that runs the [`OkxClient`] requests on an internal current-thread runtime.
+/// Must NOT be used from within an async context.
+#[derive(Debug, Clone)]
+pub struct BlockingOkxClient {
+    client: OkxClient,
+    runtime: Runtime,
+}
+
+impl BlockingOkxClient {
+    /// Get the async client
+    #[inline]
+    pub fn as_async(&self) -> &OkxClient {
+        &self.client
+    }
+
+    /// Check that the API is reachable
+    ///
+    /// See [`OkxClient::healthcheck`].
+    #[inline]
+    pub fn healthcheck(&self) -> Result<(), Error> {
+        self.runtime.block_on(self.client.healthcheck())
+    }
+
+    /// Get the **bitcoin** balance
+    ///
+    /// See [`OkxClient::balance`].
+    #[inline]
+    pub fn balance(&self) -> Result<f64, Error> {
+        self.runtime.block_on(self.client.balance())
+    }
+
+    /// Get all the non-zero balances, as `(currency, equity)` pairs
+    ///
+    /// See [`OkxClient::all_balances`].
+    #[inline]
+    pub fn all_balances(&self) -> Result<Vec<(String, f64)>, Error> {
+        self.runtime.block_on(self.client.all_balances())
+    }
+
+    /// Get the account configuration
+    ///
+    /// See [`OkxClient::account_config`].
+    #[inline]
+    pub fn account_config(&self) -> Result<AccountConfig, Error> {
+        self.runtime.block_on(self.client.account_config())
+    }
+
+    /// Get the sub-accounts
+    ///
+    /// See [`OkxClient::subaccounts`].
+    #[inline]
+    pub fn subaccounts(&self) -> Result<Vec<SubAccount>, Error> {
+        self.runtime.block_on(self.client.subaccounts())
+    }
+
+    /// Get the trading account balance of a sub-account
+    ///
+    /// See [`OkxClient::subaccount_balances`].
+    #[inline]
+    pub fn subaccount_balances(&self, sub_account: &str) -> Result<Vec<Account>, Error> {
+        self.runtime
+            .block_on(self.client.subaccount_balances(sub_account))
+    }
+
+    /// Get the currencies info, per chain
+    ///
+    /// See [`OkxClient::currencies`].
+    #[inline]
+    pub fn currencies(&self, currency: Option<&str>) -> Result<Vec<CurrencyInfo>, Error> {
+        self.runtime.block_on(self.client.currencies(currency))
+    }
+
+    /// Get all the deposit addresses of a currency
+    ///
+    /// See [`OkxClient::deposit_addresses`].
+    #[inline]
+    pub fn deposit_addresses(&self, currency: &str) -> Result<Vec<DepositAddress>, Error> {
+        self.runtime
+            .block_on(self.client.deposit_addresses(currency))
+    }
+
+    /// Get a **bitcoin** deposit address.
+    ///
+    /// See [`OkxClient::bitcoin_deposit_address`].
+    #[inline]
+    pub fn bitcoin_deposit_address(&self) -> Result<String, Error> {
+        self.runtime.block_on(self.client.bitcoin_deposit_address())
+    }
+
+    /// Get **bitcoin** account deposit history
+    ///
+    /// See [`OkxClient::deposit_history`].
+    #[inline]
+    pub fn deposit_history(&self) -> Result<Vec<DepositTransaction>, Error> {
+        self.runtime.block_on(self.client.deposit_history())
+    }
+
+    /// Get **bitcoin** account withdrawals history
+    ///
+    /// See [`OkxClient::withdrawal_history`].
+    #[inline]
+    pub fn withdrawal_history(&self) -> Result<Vec<WithdrawalTransaction>, Error> {
+        self.runtime.block_on(self.client.withdrawal_history())
+    }
+
+    /// Get **bitcoin** account withdrawals history, filtered by state
+    ///
+    /// See [`OkxClient::withdrawal_history_by_state`].
+    #[inline]
+    pub fn withdrawal_history_by_state(
+        &self,
+        state: WithdrawalStatus,
+    ) -> Result<Vec<WithdrawalTransaction>, Error> {
+        self.runtime
+            .block_on(self.client.withdrawal_history_by_state(state))
+    }
+
+    /// Get the account bills (last 7 days), from the most recent
+    ///
+    /// See [`OkxClient::bills`].
+    #[inline]
+    pub fn bills(
+        &self,
+        instrument_type: Option<&str>,
+        currency: Option<&str>,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> Result<Vec<Bill>, Error> {
+        self.runtime
+            .block_on(self.client.bills(instrument_type, currency, before, after))
+    }
+
+    /// Transfer funds between the funding and the trading accounts
+    ///
+    /// See [`OkxClient::transfer`].
+    #[inline]
+    pub fn transfer(&self, req: TransferRequest) -> Result<Transfer, Error> {
+        self.runtime.block_on(self.client.transfer(req))
+    }
+
+    /// Transfer funds between the funding and the trading accounts, keeping the per-item status
+    ///
+    /// See [`OkxClient::transfer_full`].
+    #[inline]
+    pub fn transfer_full(&self, req: TransferRequest) -> Result<OkxResponse<Transfer>, Error> {
+        self.runtime.block_on(self.client.transfer_full(req))
+    }
+
+    /// Place an order
+    ///
+    /// See [`OkxClient::place_order`].
+    #[inline]
+    pub fn place_order(&self, req: OrderRequest) -> Result<OkxResponseItem<PlacedOrder>, Error> {
+        self.runtime.block_on(self.client.place_order(req))
+    }
+
+    /// Place an order, keeping the whole response (i.e., the server-side timing)
+    ///
+    /// See [`OkxClient::place_order_full`].
+    #[inline]
+    pub fn place_order_full(&self, req: OrderRequest) -> Result<OkxResponse<PlacedOrder>, Error> {
+        self.runtime.block_on(self.client.place_order_full(req))
+    }
+
+    /// Get **bitcoin** spot trades.
+    ///
+    /// See [`OkxClient::trade_history`].
+    #[inline]
+    pub fn trade_history(&self) -> Result<Vec<Trade>, Error> {
+        self.runtime.block_on(self.client.trade_history())
+    }
+
+    /// Get spot trades of an instrument (i.e., `BTC-USDT`)
+    ///
+    /// See [`OkxClient::trade_history_for_instrument`].
+    #[inline]
+    pub fn trade_history_for_instrument(&self, instrument: &str) -> Result<Vec<Trade>, Error> {
+        self.runtime
+            .block_on(self.client.trade_history_for_instrument(instrument))
+    }
+
+    /// Get all the trades of an instrument type
+    ///
+    /// See [`OkxClient::trade_history_for_type`].
+    #[inline]
+    pub fn trade_history_for_type(
+        &self,
+        instrument_type: InstrumentType,
+    ) -> Result<Vec<Trade>, Error> {
+        self.runtime
+            .block_on(self.client.trade_history_for_type(instrument_type))
+    }
+}
@@ -0,0 +1,139 @@
+//! OKX client builder
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::interceptor::Interceptors;
+use common::rate_limit::SlidingWindow;
+use reqwest::Client;
+use reqwest::header::HeaderMap;
+use url::Url;
+
+use crate::auth::OkxApiCredentials;
+use crate::client::OkxClient;
+use crate::constant::DEFAULT_TIMEOUT;
+use crate::error::Error;
+
+/// OKX client builder
+#[derive(Debug, Clone)]
+pub struct OkxClientBuilder {
+    /// API credentials
+    pub credentials: OkxApiCredentials,
+    /// Request timeout, including the connection phase
+    pub timeout: Duration,
+    /// Connection timeout
+    pub connect_timeout: Option<Duration>,
+    /// Upper bound of a whole operation, including pagination
+    pub overall_deadline: Option<Duration>,
+    /// Custom root URL (default: `https://www.okx.com`)
+    pub root_url: Option<Url>,
+    /// Pre-built HTTP client
+    pub reqwest_client: Option<Client>,
+    /// Client-side rate limiter
+    pub rate_limiter: Option<Arc<SlidingWindow>>,
+    /// Request and response interceptors
+    pub interceptors: Interceptors,
+    /// Headers added to every request
+    pub default_headers: HeaderMap,
+}
+
+impl OkxClientBuilder {
+    /// New builder
+    #[inline]
+    pub fn new(credentials: OkxApiCredentials) -> Self {
+        Self {
+            credentials,
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            overall_deadline: None,
+            root_url: None,
+            reqwest_client: None,
+            rate_limiter: None,
+            interceptors: Interceptors::default(),
+            default_headers: HeaderMap::new(),
+        }
+    }
+
+    /// Set timeout (default: 25 secs)
+    ///
+    /// Covers the whole request, from connecting to reading the response.
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set connection timeout
+    ///
+    /// Allows to fail fast on unreachable hosts while keeping a longer [`OkxClientBuilder::timeout`].
+    #[inline]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the overall deadline of an operation (default: none)
+    ///
+    /// Unlike [`OkxClientBuilder::timeout`], it covers the pagination (i.e.,
+    /// [`OkxClient::trade_history`]). [`Error::Deadline`] is returned if exceeded.
+    #[inline]
+    pub fn overall_deadline(mut self, deadline: Duration) -> Self {
+        self.overall_deadline = Some(deadline);
+        self
+    }
+
+    /// Set a custom root URL (i.e., a proxy or a mock server)
+    #[inline]
+    pub fn root_url(mut self, root_url: Url) -> Self {
+        self.root_url = Some(root_url);
+        self
+    }
+
+    /// Use a pre-built HTTP client (i.e., to share its connection pool, proxy and TLS config)
+    ///
+    /// The OKX headers are added to each request. [`OkxClientBuilder::timeout`] and
+    /// [`OkxClientBuilder::connect_timeout`] are ignored: configure them on the client.
+    #[inline]
+    pub fn reqwest_client(mut self, client: Client) -> Self {
+        self.reqwest_client = Some(client);
+        self
+    }
+
+    /// Set a client-side rate limiter (default: disabled)
+    ///
+    /// Each request takes a weight of 1. Share the same limiter across clients to enforce a
+    /// common limit (i.e., the sub-account limits).
+    #[inline]
+    pub fn rate_limiter(mut self, limiter: Arc<SlidingWindow>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Set request and response interceptors (default: none)
+    ///
+    /// The request interceptor runs after the OKX headers and signature are added.
+    #[inline]
+    pub fn interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
+    /// Set headers added to every request (default: none)
+    ///
+    /// Added to each request, so they also apply with [`OkxClientBuilder::reqwest_client`].
+    /// The OKX headers (i.e., `OK-ACCESS-SIGN`) win on conflict.
+    #[inline]
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Build client
+    ///
+    /// Fails if the credentials are malformed (see [`OkxApiCredentials::validate`]).
+    #[inline]
+    pub fn build(self) -> Result<OkxClient, Error> {
+        self.credentials.validate()?;
+        OkxClient::from_builder(self)
+    }
+}
@@ -1,31 +1,78 @@
 //! OKX client
 
 use std::borrow::Cow;
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use common::interceptor::{Interceptors, ServerError};
+use common::pagination::{self, Page};
+use common::rate_limit::SlidingWindow;
+use futures_util::{Stream, TryStreamExt, future};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue, USER_AGENT};
 use reqwest::{Client, Method, Response, StatusCode};
-use serde::de::DeserializeOwned;
-use serde_json::Deserializer;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde_json::{Deserializer, Value};
+use tokio::time;
 use url::Url;
 
 use crate::auth::{self, OkxApiCredentials};
-use crate::constant::{API_ROOT_URL, BTC_TICKER, USER_AGENT_NAME};
+use crate::builder::OkxClientBuilder;
+use crate::constant::{API_ROOT_URL, BTC_TICKER, TRACING_TARGET, USER_AGENT_NAME};
 use crate::error::Error;
+use crate::request::{InstrumentType, OrderRequest, TransferRequest};
 use crate::response::{
-    Account, DepositAddress, DepositTransaction, OkxApiErrorData, OkxApiResponse, Trade,
-    WithdrawalTransaction,
+    Account, AccountConfig, Bill, CurrencyInfo, DepositAddress, DepositTransaction,
+    OkxApiErrorData, OkxApiResponse, OkxResponse, OkxResponseItem, PlacedOrder, SubAccount, Trade,
+    Transfer, WithdrawalStatus, WithdrawalTransaction,
 };
 use crate::util;
 
 const BTC_NATIVE_CHAIN: &str = "BTC-Bitcoin";
+const FILLS_HISTORY_MAX_LIMIT: usize = 100;
+const SUCCESS_CODE: &str = "0";
+/// Returned by batch operations when only some items succeeded
+const PARTIAL_SUCCESS_CODE: &str = "2";
 
 enum Api<'a> {
-    Balance { currency: Option<&'a str> },
-    DepositAddress { currency: &'a str },
-    DepositHistory { currency: Option<&'a str> },
-    WithdrawalHistory { currency: Option<&'a str> },
-    FillsHistory { instrument_type: Option<&'a str> },
+    Balance {
+        currency: Option<&'a str>,
+    },
+    SubAccountBalances {
+        sub_account: &'a str,
+    },
+    SubAccounts,
+    AccountConfig,
+    Currencies {
+        currency: Option<&'a str>,
+    },
+    DepositAddress {
+        currency: &'a str,
+    },
+    DepositHistory {
+        currency: Option<&'a str>,
+    },
+    WithdrawalHistory {
+        currency: Option<&'a str>,
+        state: Option<WithdrawalStatus>,
+    },
+    FillsHistory {
+        instrument_type: Option<&'a str>,
+        instrument_id: Option<&'a str>,
+        after: Option<&'a str>,
+        limit: usize,
+    },
+    Bills {
+        instrument_type: Option<&'a str>,
+        currency: Option<&'a str>,
+        before: Option<&'a str>,
+        after: Option<&'a str>,
+    },
+    Transfer,
+    PlaceOrder,
+    SystemTime,
 }
 
 impl<'a> Api<'a> {
@@ -35,6 +82,15 @@ impl<'a> Api<'a> {
                 Some(currency) => Cow::Owned(format!("/api/v5/account/balance?ccy={currency}")),
                 None => Cow::Borrowed("/api/v5/account/balance"),
             },
+            Self::SubAccountBalances { sub_account } => Cow::Owned(format!(
+                "/api/v5/account/subaccount/balances?subAcct={sub_account}"
+            )),
+            Self::SubAccounts => Cow::Borrowed("/api/v5/users/subaccount/list"),
+            Self::AccountConfig => Cow::Borrowed("/api/v5/account/config"),
+            Self::Currencies { currency } => match currency {
+                Some(currency) => Cow::Owned(format!("/api/v5/asset/currencies?ccy={currency}")),
+                None => Cow::Borrowed("/api/v5/asset/currencies"),
+            },
             Self::DepositAddress { currency } => {
                 Cow::Owned(format!("/api/v5/asset/deposit-address?ccy={currency}"))
             }
@@ -44,28 +100,88 @@ impl<'a> Api<'a> {
                 }
                 None => Cow::Borrowed("/api/v5/asset/deposit-history"),
             },
-            Self::WithdrawalHistory { currency } => match currency {
-                Some(currency) => {
-                    Cow::Owned(format!("/api/v5/asset/withdrawal-history?ccy={currency}"))
+            Self::WithdrawalHistory { currency, state } => {
+                let query: Vec<String> = [
+                    ("ccy", *currency),
+                    ("state", state.as_ref().map(WithdrawalStatus::code)),
+                ]
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|value| format!("{key}={value}")))
+                .collect();
+
+                if query.is_empty() {
+                    Cow::Borrowed("/api/v5/asset/withdrawal-history")
+                } else {
+                    Cow::Owned(format!(
+                        "/api/v5/asset/withdrawal-history?{}",
+                        query.join("&")
+                    ))
                 }
-                None => Cow::Borrowed("/api/v5/asset/withdrawal-history"),
-            },
-            Self::FillsHistory { instrument_type } => match instrument_type {
-                Some(instrument_type) => Cow::Owned(format!(
-                    "/api/v5/trade/fills-history?instType={instrument_type}"
-                )),
-                None => Cow::Borrowed("/api/v5/trade/fills-history"),
-            },
+            }
+            Self::FillsHistory {
+                instrument_type,
+                instrument_id,
+                after,
+                limit,
+            } => {
+                let mut path: String = format!("/api/v5/trade/fills-history?limit={limit}");
+
+                if let Some(instrument_type) = instrument_type {
+                    path.push_str(&format!("&instType={instrument_type}"));
+                }
+
+                if let Some(instrument_id) = instrument_id {
+                    path.push_str(&format!("&instId={instrument_id}"));
+                }
+
+                if let Some(after) = after {
+                    path.push_str(&format!("&after={after}"));
+                }
+
+                Cow::Owned(path)
+            }
+            Self::Bills {
+                instrument_type,
+                currency,
+                before,
+                after,
+            } => {
+                let query: Vec<String> = [
+                    ("instType", instrument_type),
+                    ("ccy", currency),
+                    ("before", before),
+                    ("after", after),
+                ]
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|value| format!("{key}={value}")))
+                .collect();
+
+                if query.is_empty() {
+                    Cow::Borrowed("/api/v5/account/bills")
+                } else {
+                    Cow::Owned(format!("/api/v5/account/bills?{}", query.join("&")))
+                }
+            }
+            Self::Transfer => Cow::Borrowed("/api/v5/asset/transfer"),
+            Self::PlaceOrder => Cow::Borrowed("/api/v5/trade/order"),
+            Self::SystemTime => Cow::Borrowed("/api/v5/public/time"),
         }
     }
 
     fn http_method(&self) -> Method {
         match self {
             Self::Balance { .. }
+            | Self::SubAccountBalances { .. }
+            | Self::SubAccounts
+            | Self::AccountConfig
+            | Self::Currencies { .. }
             | Self::DepositAddress { .. }
             | Self::DepositHistory { .. }
             | Self::WithdrawalHistory { .. }
-            | Self::FillsHistory { .. } => Method::GET,
+            | Self::FillsHistory { .. }
+            | Self::Bills { .. }
+            | Self::SystemTime => Method::GET,
+            Self::Transfer | Self::PlaceOrder => Method::POST,
         }
     }
 }
@@ -79,29 +195,139 @@ pub struct OkxClient {
     client: Client,
     /// Authentication
     credentials: OkxApiCredentials,
+    /// Upper bound of a whole operation
+    overall_deadline: Option<Duration>,
+    /// Client-side rate limiter
+    rate_limiter: Option<Arc<SlidingWindow>>,
+    /// Request and response interceptors
+    interceptors: Interceptors,
+    /// Headers added to every request, before the OKX ones
+    default_headers: HeaderMap,
 }
 
 impl OkxClient {
     /// Construct a new client.
+    #[inline]
     pub fn new(credentials: OkxApiCredentials) -> Result<Self, Error> {
+        Self::builder(credentials).build()
+    }
+
+    /// Get a new builder
+    #[inline]
+    pub fn builder(credentials: OkxApiCredentials) -> OkxClientBuilder {
+        OkxClientBuilder::new(credentials)
+    }
+
+    pub(super) fn from_builder(builder: OkxClientBuilder) -> Result<Self, Error> {
+        let client: Client = match builder.reqwest_client {
+            Some(client) => client,
+            None => {
+                let mut client = Client::builder().timeout(builder.timeout);
+
+                if let Some(connect_timeout) = builder.connect_timeout {
+                    client = client.connect_timeout(connect_timeout);
+                }
+
+                client.build()?
+            }
+        };
+
+        let root_url: Url = match builder.root_url {
+            Some(root_url) => root_url,
+            None => Url::parse(API_ROOT_URL)?,
+        };
+
         Ok(Self {
-            root_url: Url::parse(API_ROOT_URL)?,
-            client: Client::builder()
-                .user_agent(USER_AGENT_NAME)
-                .timeout(Duration::from_secs(25))
-                .build()?,
-            credentials,
+            root_url,
+            client,
+            credentials: builder.credentials,
+            overall_deadline: builder.overall_deadline,
+            rate_limiter: builder.rate_limiter,
+            interceptors: builder.interceptors,
+            default_headers: builder.default_headers,
         })
     }
 
+    /// Run an operation (i.e., including pagination) within the overall deadline
+    async fn with_deadline<F, T>(&self, operation: F) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        match self.overall_deadline {
+            Some(deadline) => time::timeout(deadline, operation)
+                .await
+                .map_err(|_| Error::Deadline(deadline))?,
+            None => operation.await,
+        }
+    }
+
+    #[inline]
     async fn send_request<T>(&self, api: Api<'_>) -> Result<T, Error>
     where
         T: DeserializeOwned,
     {
+        self.send_request_with_body(api, String::new()).await
+    }
+
+    /// Send a request with a JSON body, included in the signature.
+    async fn send_request_with_body<T>(&self, api: Api<'_>, body: String) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let result: OkxApiResponse = self.send_raw_request(api, body).await?;
+
+        if result.code == SUCCESS_CODE {
+            return Ok(serde_json::from_value(result.data)?);
+        }
+
+        Err(api_error(result))
+    }
+
+    /// Send a request with a JSON body, keeping the per-item status codes.
+    ///
+    /// Partially successful batches are returned instead of failing.
+    async fn send_request_full_with_body<T>(
+        &self,
+        api: Api<'_>,
+        body: String,
+    ) -> Result<OkxResponse<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let result: OkxApiResponse = self.send_raw_request(api, body).await?;
+
+        if result.code == SUCCESS_CODE || result.code == PARTIAL_SUCCESS_CODE {
+            return Ok(OkxResponse {
+                timing: result.timing(),
+                code: result.code,
+                msg: result.msg,
+                data: serde_json::from_value(result.data)?,
+            });
+        }
+
+        Err(api_error(result))
+    }
+
+    /// Send a request and parse the response envelope, whatever its code.
+    #[inline]
+    async fn send_raw_request(&self, api: Api<'_>, body: String) -> Result<OkxApiResponse, Error> {
+        self.with_deadline(self.send_raw_request_unbounded(api, body))
+            .await
+    }
+
+    async fn send_raw_request_unbounded(
+        &self,
+        api: Api<'_>,
+        body: String,
+    ) -> Result<OkxApiResponse, Error> {
         let method: Method = api.http_method();
         let path: Cow<str> = api.url_path();
         let path: &str = path.as_ref();
-        let body: &str = "";
+
+        // Wait for the rate limiter before signing
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire(1).await;
+        }
 
         // Get current timestamp
         let timestamp: DateTime<Utc> = Utc::now();
@@ -112,27 +338,48 @@ impl OkxClient {
             &timestamp,
             &method,
             path,
-            body,
+            &body,
         )?;
 
         let url: Url = self.root_url.join(path)?;
 
-        let response: Response = self
+        // Replace the default headers on conflict
+        let mut headers: HeaderMap = HeaderMap::with_capacity(6);
+        headers.insert(USER_AGENT, HeaderValue::from_static(USER_AGENT_NAME));
+        headers.insert(
+            "OK-ACCESS-KEY",
+            HeaderValue::from_str(&self.credentials.api_key)?,
+        );
+        headers.insert("OK-ACCESS-SIGN", HeaderValue::from_str(&signature)?);
+        headers.insert(
+            "OK-ACCESS-TIMESTAMP",
+            HeaderValue::from_str(&util::format_timestamp(&timestamp))?,
+        );
+        headers.insert(
+            "OK-ACCESS-PASSPHRASE",
+            HeaderValue::from_str(&self.credentials.passphrase)?,
+        );
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let request = self
             .client
             .request(method, url)
-            .header("OK-ACCESS-KEY", &self.credentials.api_key)
-            .header("OK-ACCESS-SIGN", signature)
-            .header("OK-ACCESS-TIMESTAMP", util::format_timestamp(&timestamp))
-            .header("OK-ACCESS-PASSPHRASE", &self.credentials.passphrase)
-            .header("Content-Type", "application/json")
-            .body(body.to_string())
-            .send()
-            .await?;
+            .headers(self.default_headers.clone())
+            .headers(headers)
+            .body(body);
+
+        let response: Response = self.interceptors.send(request).await?;
 
         let status_code: StatusCode = response.status();
         let response_body: String = response.text().await?;
 
-        tracing::debug!("okx result: {response_body}");
+        tracing::debug!(
+            target: TRACING_TARGET,
+            endpoint = path,
+            status = status_code.as_u16(),
+            body = %response_body,
+            "response received"
+        );
 
         match status_code {
             StatusCode::OK => {
@@ -140,41 +387,70 @@ impl OkxClient {
                 let deserializer = &mut Deserializer::from_str(&response_body);
                 let result: OkxApiResponse = serde_path_to_error::deserialize(deserializer)?;
 
-                if result.code == "0" {
-                    return Ok(serde_json::from_value(result.data)?);
+                if let Some(timing) = result.timing() {
+                    tracing::debug!(
+                        target: TRACING_TARGET,
+                        endpoint = path,
+                        processing_time = ?timing.processing_time(),
+                        "server-side timing"
+                    );
                 }
 
-                // Attempt to extract sMsg from the first element of the data array
-                //
-                // result={"code":"1","data":[{"clOrdId":"","ordId":"","sCode":"51000","sMsg":"Parameter ordId error","ts":"1752558485701"}],"inTime":"1752558485701589","msg":"All operations failed","outTime":"1752558485701884"}
-                let smg: String = match serde_json::from_value(result.data) {
-                    Ok(errors) => {
-                        let errors: Vec<OkxApiErrorData> = errors;
-                        let mut iter = errors.into_iter();
-                        iter.next()
-                            .and_then(|e| e.s_msg)
-                            .unwrap_or_else(|| String::from("Unknown error"))
-                    }
-                    Err(..) => String::from("Failed to parse error message"),
-                };
-
-                tracing::error!("OKX API Error Response: {response_body}");
-                Err(Error::OkxApiError {
-                    code: result.code,
-                    message: result.msg,
-                    smg,
-                })
+                if result.code == PARTIAL_SUCCESS_CODE {
+                    tracing::warn!(
+                        target: TRACING_TARGET,
+                        endpoint = path,
+                        code = %result.code,
+                        message = %result.msg,
+                        items = ?item_codes(&result.data),
+                        "partial success"
+                    );
+                } else if result.code != SUCCESS_CODE {
+                    tracing::error!(
+                        target: TRACING_TARGET,
+                        endpoint = path,
+                        status = status_code.as_u16(),
+                        code = %result.code,
+                        message = %result.msg,
+                        items = ?item_codes(&result.data),
+                        "API error"
+                    );
+                }
+
+                Ok(result)
             }
             StatusCode::NOT_FOUND => {
-                tracing::error!("OKX API Error Response: {response_body}");
+                tracing::error!(
+                    target: TRACING_TARGET,
+                    endpoint = path,
+                    status = status_code.as_u16(),
+                    body = %response_body,
+                    "HTTP error"
+                );
                 Err(Error::OkxApiError {
                     code: "404".to_string(),
                     message: format!("API not found: '{path}'"),
                     smg: String::new(),
                 })
             }
+            status_code if status_code.is_server_error() => {
+                tracing::error!(
+                    target: TRACING_TARGET,
+                    endpoint = path,
+                    status = status_code.as_u16(),
+                    body = %response_body,
+                    "server error"
+                );
+                Err(ServerError::new(status_code.as_u16(), &response_body).into())
+            }
             _ => {
-                tracing::error!("OKX API Error Response: {response_body}");
+                tracing::error!(
+                    target: TRACING_TARGET,
+                    endpoint = path,
+                    status = status_code.as_u16(),
+                    body = %response_body,
+                    "HTTP error"
+                );
                 Err(Error::OkxApiError {
                     code: status_code.to_string(),
                     message: response_body,
@@ -184,6 +460,17 @@ impl OkxClient {
         }
     }
 
+    /// Check that the API is reachable and that the API keys are valid
+    ///
+    /// Fetches the system time, then the account configuration (see [`OkxClient::account_config`]).
+    ///
+    /// <https://www.okx.com/docs-v5/en/#public-data-rest-api-get-system-time>
+    pub async fn healthcheck(&self) -> Result<(), Error> {
+        let _: IgnoredAny = self.send_request(Api::SystemTime).await?;
+        self.account_config().await?;
+        Ok(())
+    }
+
     /// Get the **bitcoin** balance
     pub async fn balance(&self) -> Result<f64, Error> {
         let accounts: Vec<Account> = self
@@ -207,13 +494,61 @@ impl OkxClient {
         Ok(total)
     }
 
+    /// Get all the non-zero balances, as `(currency, equity)` pairs
+    pub async fn all_balances(&self) -> Result<Vec<(String, f64)>, Error> {
+        let accounts: Vec<Account> = self.send_request(Api::Balance { currency: None }).await?;
+        Ok(non_zero_balances(accounts))
+    }
+
+    /// Get the account configuration (i.e., account and position modes)
+    ///
+    /// <https://www.okx.com/docs-v5/en/#trading-account-rest-api-get-account-configuration>
+    pub async fn account_config(&self) -> Result<AccountConfig, Error> {
+        let configs: Vec<AccountConfig> = self.send_request(Api::AccountConfig).await?;
+        configs
+            .into_iter()
+            .next()
+            .ok_or(Error::MissingAccountConfig)
+    }
+
+    /// Get the sub-accounts
+    ///
+    /// <https://www.okx.com/docs-v5/en/#sub-account-rest-api-get-sub-account-list>
+    #[inline]
+    pub async fn subaccounts(&self) -> Result<Vec<SubAccount>, Error> {
+        self.send_request(Api::SubAccounts).await
+    }
+
+    /// Get the trading account balance of a sub-account
+    ///
+    /// <https://www.okx.com/docs-v5/en/#sub-account-rest-api-get-sub-account-trading-balance>
+    #[inline]
+    pub async fn subaccount_balances(&self, sub_account: &str) -> Result<Vec<Account>, Error> {
+        self.send_request(Api::SubAccountBalances { sub_account })
+            .await
+    }
+
+    /// Get the currencies info, per chain (i.e., withdrawal limits and fees)
+    ///
+    /// Filter by currency (i.e., `BTC`), or get all of them if `None`.
+    ///
+    /// <https://www.okx.com/docs-v5/en/#funding-account-rest-api-get-currencies>
+    #[inline]
+    pub async fn currencies(&self, currency: Option<&str>) -> Result<Vec<CurrencyInfo>, Error> {
+        self.send_request(Api::Currencies { currency }).await
+    }
+
+    /// Get all the deposit addresses of a currency (i.e., `BTC`)
+    ///
+    /// <https://www.okx.com/docs-v5/en/#funding-account-rest-api-get-deposit-address>
+    #[inline]
+    pub async fn deposit_addresses(&self, currency: &str) -> Result<Vec<DepositAddress>, Error> {
+        self.send_request(Api::DepositAddress { currency }).await
+    }
+
     /// Get a **bitcoin** deposit address.
     pub async fn bitcoin_deposit_address(&self) -> Result<String, Error> {
-        let addresses: Vec<DepositAddress> = self
-            .send_request(Api::DepositAddress {
-                currency: BTC_TICKER,
-            })
-            .await?;
+        let addresses: Vec<DepositAddress> = self.deposit_addresses(BTC_TICKER).await?;
 
         let address: DepositAddress = addresses
             .into_iter()
@@ -235,24 +570,481 @@ impl OkxClient {
     pub async fn withdrawal_history(&self) -> Result<Vec<WithdrawalTransaction>, Error> {
         self.send_request(Api::WithdrawalHistory {
             currency: Some(BTC_TICKER),
+            state: None,
+        })
+        .await
+    }
+
+    /// Get **bitcoin** account withdrawals history, filtered by state
+    ///
+    /// The filter is applied by OKX.
+    pub async fn withdrawal_history_by_state(
+        &self,
+        state: WithdrawalStatus,
+    ) -> Result<Vec<WithdrawalTransaction>, Error> {
+        self.send_request(Api::WithdrawalHistory {
+            currency: Some(BTC_TICKER),
+            state: Some(state),
+        })
+        .await
+    }
+
+    /// Get the account bills (last 7 days), from the most recent
+    ///
+    /// Bills include trades, transfers, fees and rebates. Pagination uses bill IDs: `after`
+    /// returns older bills, `before` newer ones.
+    ///
+    /// <https://www.okx.com/docs-v5/en/#trading-account-rest-api-get-bills-details-last-7-days>
+    pub async fn bills(
+        &self,
+        instrument_type: Option<&str>,
+        currency: Option<&str>,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> Result<Vec<Bill>, Error> {
+        self.send_request(Api::Bills {
+            instrument_type,
+            currency,
+            before,
+            after,
         })
         .await
     }
 
-    /// Get **bitcoin** spot trades.
+    /// Transfer funds between the funding and the trading accounts
+    ///
+    /// <https://www.okx.com/docs-v5/en/#funding-account-rest-api-funds-transfer>
+    pub async fn transfer(&self, req: TransferRequest) -> Result<Transfer, Error> {
+        let body: String = serde_json::to_string(&req)?;
+
+        let transfers: Vec<Transfer> = self.send_request_with_body(Api::Transfer, body).await?;
+
+        transfers.into_iter().next().ok_or(Error::MissingTransfer)
+    }
+
+    /// Transfer funds between the funding and the trading accounts, keeping the per-item status
+    ///
+    /// See [`OkxClient::transfer`].
+    pub async fn transfer_full(
+        &self,
+        req: TransferRequest,
+    ) -> Result<OkxResponse<Transfer>, Error> {
+        let body: String = serde_json::to_string(&req)?;
+        self.send_request_full_with_body(Api::Transfer, body).await
+    }
+
+    /// Place an order
+    ///
+    /// The request is validated before being sent. The returned item holds the `ordId`/`clOrdId`
+    /// and the `sCode`/`sMsg` status.
+    ///
+    /// <https://www.okx.com/docs-v5/en/#order-book-trading-trade-post-place-order>
+    pub async fn place_order(
+        &self,
+        req: OrderRequest,
+    ) -> Result<OkxResponseItem<PlacedOrder>, Error> {
+        let res: OkxResponse<PlacedOrder> = self.place_order_full(req).await?;
+        res.data.into_iter().next().ok_or(Error::MissingOrder)
+    }
+
+    /// Place an order, keeping the whole response (i.e., the server-side timing)
+    ///
+    /// See [`OkxClient::place_order`].
+    pub async fn place_order_full(
+        &self,
+        req: OrderRequest,
+    ) -> Result<OkxResponse<PlacedOrder>, Error> {
+        req.validate()?;
+
+        let body: String = serde_json::to_string(&req)?;
+        self.send_request_full_with_body(Api::PlaceOrder, body)
+            .await
+    }
+
+    /// Get **bitcoin** spot trades, deduplicated and sorted by timestamp (ascending).
+    ///
+    /// Loads all the pages: use [`OkxClient::trade_history_stream`] to process them lazily.
     pub async fn trade_history(&self) -> Result<Vec<Trade>, Error> {
         let trades: Vec<Trade> = self
-            .send_request(Api::FillsHistory {
-                instrument_type: Some("SPOT"),
-            })
+            .with_deadline(self.trade_history_stream().try_collect())
             .await?;
+        Ok(sort_and_dedup_trades(trades))
+    }
 
+    /// Stream **bitcoin** spot trades, from the most recent.
+    ///
+    /// Pages are fetched as the stream is consumed.
+    pub fn trade_history_stream(&self) -> impl Stream<Item = Result<Trade, Error>> + '_ {
         // Keep only trades that involve BTC in the pair.
-        let trades: Vec<Trade> = trades
+        self.fills_history_stream(InstrumentType::Spot, None)
+            .try_filter(|trade| future::ready(trade.instrument_id.contains(BTC_TICKER)))
+    }
+
+    /// Get spot trades of an instrument (i.e., `BTC-USDT`), deduplicated and sorted by timestamp
+    /// (ascending).
+    ///
+    /// The instrument is normalized: `btc/usdt` and `BTC_USDT` are accepted, while `BTCUSDT` is
+    /// rejected as ambiguous.
+    pub async fn trade_history_for_instrument(
+        &self,
+        instrument: &str,
+    ) -> Result<Vec<Trade>, Error> {
+        let instrument: String = util::normalize_instrument(instrument)?;
+        let trades: Vec<Trade> = self
+            .with_deadline(
+                self.fills_history_stream(InstrumentType::Spot, Some(instrument))
+                    .try_collect(),
+            )
+            .await?;
+        Ok(sort_and_dedup_trades(trades))
+    }
+
+    /// Get all the trades of an instrument type (i.e., margin or perpetual swap), deduplicated
+    /// and sorted by timestamp (ascending).
+    ///
+    /// Unlike [`OkxClient::trade_history`], trades are not filtered by currency.
+    pub async fn trade_history_for_type(
+        &self,
+        instrument_type: InstrumentType,
+    ) -> Result<Vec<Trade>, Error> {
+        let trades: Vec<Trade> = self
+            .with_deadline(
+                self.fills_history_stream(instrument_type, None)
+                    .try_collect(),
+            )
+            .await?;
+        Ok(sort_and_dedup_trades(trades))
+    }
+
+    /// Stream fills of an instrument type, from the most recent, optionally filtered by
+    /// instrument ID.
+    fn fills_history_stream(
+        &self,
+        instrument_type: InstrumentType,
+        instrument_id: Option<String>,
+    ) -> impl Stream<Item = Result<Trade, Error>> + '_ {
+        pagination::paginate(move |after: Option<String>| {
+            let instrument_id = instrument_id.clone();
+            async move {
+                let trades: Vec<Trade> = self
+                    .send_request(Api::FillsHistory {
+                        instrument_type: Some(instrument_type.as_str()),
+                        instrument_id: instrument_id.as_deref(),
+                        after: after.as_deref(),
+                        limit: FILLS_HISTORY_MAX_LIMIT,
+                    })
+                    .await?;
+
+                // Fills are sorted from the most recent: the next page starts after the last one
+                Ok::<_, Error>(Page::from_last_item(
+                    trades,
+                    FILLS_HISTORY_MAX_LIMIT,
+                    |trade| trade.bill_id.clone(),
+                ))
+            }
+        })
+    }
+}
+
+/// Remove the duplicated trades and sort them by timestamp, ascending
+///
+/// Trade IDs are unique only per instrument: duplicates are keyed on the instrument and the ID.
+fn sort_and_dedup_trades(mut trades: Vec<Trade>) -> Vec<Trade> {
+    let mut seen: HashSet<(String, String)> = HashSet::with_capacity(trades.len());
+    trades.retain(|trade| seen.insert((trade.instrument_id.clone(), trade.id.clone())));
+    trades.sort_by(|a, b| {
+        a.timestamp
+            .cmp(&b.timestamp)
+            .then_with(|| a.instrument_id.cmp(&b.instrument_id))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    trades
+}
+
+fn non_zero_balances(accounts: Vec<Account>) -> Vec<(String, f64)> {
+    let mut balances: Vec<(String, f64)> = Vec::new();
+
+    for detail in accounts.into_iter().flat_map(|account| account.details) {
+        match balances
+            .iter_mut()
+            .find(|(currency, _)| *currency == detail.currency)
+        {
+            Some((_, amount)) => *amount += detail.amount,
+            None => balances.push((detail.currency, detail.amount)),
+        }
+    }
+
+    balances.retain(|(_, amount)| *amount != 0.0);
+    balances
+}
+
+/// Get the per-item status codes (`sCode`) of a batch response, in order
+fn item_codes(data: &Value) -> Vec<&str> {
+    data.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.get("sCode").and_then(Value::as_str))
+        .collect()
+}
+
+/// Build the error from an OKX response with a non-zero code.
+///
+/// The per-item errors are extracted from the data array, if any:
+///
+/// result={"code":"1","data":[{"clOrdId":"","ordId":"","sCode":"51000","sMsg":"Parameter ordId error","ts":"1752558485701"}],"inTime":"1752558485701589","msg":"All operations failed","outTime":"1752558485701884"}
+fn api_error(result: OkxApiResponse) -> Error {
+    let errors: Vec<OkxApiErrorData> = match serde_json::from_value(result.data) {
+        Ok(errors) => errors,
+        Err(..) => {
+            return Error::OkxApiError {
+                code: result.code,
+                message: result.msg,
+                smg: String::from("Failed to parse error message"),
+            };
+        }
+    };
+
+    if errors.len() > 1 {
+        return Error::OkxApiBatchError {
+            code: result.code,
+            message: result.msg,
+            items: errors
+                .into_iter()
+                .map(|e| (e.s_code.unwrap_or_default(), e.s_msg.unwrap_or_default()))
+                .collect(),
+        };
+    }
+
+    let smg: String = errors
+        .into_iter()
+        .next()
+        .and_then(|e| e.s_msg)
+        .unwrap_or_else(|| String::from("Unknown error"));
+
+    Error::OkxApiError {
+        code: result.code,
+        message: result.msg,
+        smg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_and_dedup_trades() {
+        let trade = |id: &str, ts: i64| -> Trade {
+            serde_json::from_str(&format!(
+                r#"{{"tradeId":"{id}","instType":"SPOT","instId":"BTC-USDT","ordId":"1","billId":"{id}","side":"buy","fillSz":"1","fillPx":"1","fee":"0","feeCcy":"BTC","ts":"{ts}"}}"#
+            ))
+            .unwrap()
+        };
+
+        let trades = vec![
+            trade("3", 3000),
+            trade("1", 1000),
+            trade("2", 2000),
+            trade("3", 3000),
+        ];
+        let ids: Vec<String> = sort_and_dedup_trades(trades)
             .into_iter()
-            .filter(|trade| trade.instrument_id.contains(BTC_TICKER))
+            .map(|trade| trade.id)
             .collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_sort_and_dedup_trades_per_instrument() {
+        let trade = |instrument: &str, id: &str| -> Trade {
+            serde_json::from_str(&format!(
+                r#"{{"tradeId":"{id}","instType":"SWAP","instId":"{instrument}","ordId":"1","billId":"{instrument}{id}","side":"buy","fillSz":"1","fillPx":"1","fee":"0","feeCcy":"USDT","ts":"1000"}}"#
+            ))
+            .unwrap()
+        };
+
+        // Same trade ID on two instruments
+        let trades = vec![
+            trade("ETH-USDT-SWAP", "7"),
+            trade("BTC-USDT-SWAP", "7"),
+            trade("BTC-USDT-SWAP", "7"),
+        ];
+        let keys: Vec<(String, String)> = sort_and_dedup_trades(trades)
+            .into_iter()
+            .map(|trade| (trade.instrument_id, trade.id))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (String::from("BTC-USDT-SWAP"), String::from("7")),
+                (String::from("ETH-USDT-SWAP"), String::from("7")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_zero_balances() {
+        let accounts: Vec<Account> = serde_json::from_str(
+            r#"[{"details":[{"ccy":"BTC","eq":"0.5"},{"ccy":"USDT","eq":"0"},{"ccy":"ETH","eq":"2"}]}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            non_zero_balances(accounts),
+            vec![(String::from("BTC"), 0.5), (String::from("ETH"), 2.0)]
+        );
+    }
+
+    #[test]
+    fn test_withdrawal_history_url_path() {
+        let api = Api::WithdrawalHistory {
+            currency: Some("BTC"),
+            state: None,
+        };
+        assert_eq!(api.url_path(), "/api/v5/asset/withdrawal-history?ccy=BTC");
+
+        let api = Api::WithdrawalHistory {
+            currency: Some("BTC"),
+            state: Some(WithdrawalStatus::Canceled),
+        };
+        assert_eq!(
+            api.url_path(),
+            "/api/v5/asset/withdrawal-history?ccy=BTC&state=-2"
+        );
+    }
+
+    #[test]
+    fn test_bills_url_path() {
+        let api = Api::Bills {
+            instrument_type: None,
+            currency: None,
+            before: None,
+            after: None,
+        };
+        assert_eq!(api.url_path(), "/api/v5/account/bills");
+
+        let api = Api::Bills {
+            instrument_type: Some("SPOT"),
+            currency: Some("BTC"),
+            before: None,
+            after: Some("623950854533513219"),
+        };
+        assert_eq!(
+            api.url_path(),
+            "/api/v5/account/bills?instType=SPOT&ccy=BTC&after=623950854533513219"
+        );
+    }
+
+    #[test]
+    fn test_fills_history_url_path() {
+        let api = Api::FillsHistory {
+            instrument_type: Some("SPOT"),
+            instrument_id: None,
+            after: None,
+            limit: 100,
+        };
+        assert_eq!(
+            api.url_path(),
+            "/api/v5/trade/fills-history?limit=100&instType=SPOT"
+        );
+
+        let api = Api::FillsHistory {
+            instrument_type: Some("SPOT"),
+            instrument_id: None,
+            after: Some("680800019754098688"),
+            limit: 100,
+        };
+        assert_eq!(
+            api.url_path(),
+            "/api/v5/trade/fills-history?limit=100&instType=SPOT&after=680800019754098688"
+        );
+
+        let api = Api::FillsHistory {
+            instrument_type: Some("SPOT"),
+            instrument_id: Some("BTC-USDT"),
+            after: None,
+            limit: 100,
+        };
+        assert_eq!(
+            api.url_path(),
+            "/api/v5/trade/fills-history?limit=100&instType=SPOT&instId=BTC-USDT"
+        );
+    }
+
+    #[test]
+    fn test_api_error_single_item() {
+        let json = r#"{"code":"1","data":[{"clOrdId":"","ordId":"","sCode":"51000","sMsg":"Parameter ordId error","ts":"1752558485701"}],"inTime":"1752558485701589","msg":"All operations failed","outTime":"1752558485701884"}"#;
+        let result: OkxApiResponse = serde_json::from_str(json).unwrap();
+
+        match api_error(result) {
+            Error::OkxApiError { code, message, smg } => {
+                assert_eq!(code, "1");
+                assert_eq!(message, "All operations failed");
+                assert_eq!(smg, "Parameter ordId error");
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_response_timing() {
+        let json = r#"{"code":"0","data":[],"inTime":"1752558485701589","msg":"","outTime":"1752558485701884"}"#;
+        let result: OkxApiResponse = serde_json::from_str(json).unwrap();
+
+        let timing = result.timing().unwrap();
+        assert_eq!(timing.in_time.timestamp_micros(), 1752558485701589);
+        assert_eq!(timing.processing_time(), Duration::from_micros(295));
+
+        let json = r#"{"code":"0","data":[],"msg":""}"#;
+        let result: OkxApiResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(result.timing(), None);
+    }
+
+    #[test]
+    fn test_api_error_batch() {
+        let json = r#"{"code":"1","data":[{"ordId":"1","sCode":"51000","sMsg":"Parameter ordId error"},{"ordId":"2","sCode":"51008","sMsg":"Insufficient balance"}],"msg":"All operations failed"}"#;
+        let result: OkxApiResponse = serde_json::from_str(json).unwrap();
+
+        match api_error(result) {
+            Error::OkxApiBatchError {
+                code,
+                message,
+                items,
+            } => {
+                assert_eq!(code, "1");
+                assert_eq!(message, "All operations failed");
+                assert_eq!(
+                    items,
+                    vec![
+                        (String::from("51000"), String::from("Parameter ordId error")),
+                        (String::from("51008"), String::from("Insufficient balance")),
+                    ]
+                );
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_api_error_without_data() {
+        let json = r#"{"code":"50111","data":[],"msg":"Invalid OK-ACCESS-KEY"}"#;
+        let result: OkxApiResponse = serde_json::from_str(json).unwrap();
+
+        match api_error(result) {
+            Error::OkxApiError { code, smg, .. } => {
+                assert_eq!(code, "50111");
+                assert_eq!(smg, "Unknown error");
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_item_codes() {
+        let json = r#"{"code":"2","data":[{"ordId":"1","sCode":"0","sMsg":""},{"ordId":"","sCode":"51008","sMsg":"Insufficient balance"}],"msg":""}"#;
+        let result: OkxApiResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(item_codes(&result.data), vec!["0", "51008"]);
 
-        Ok(trades)
+        assert!(item_codes(&Value::Null).is_empty());
     }
 }
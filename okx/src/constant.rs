@@ -1,7 +1,14 @@
+use std::time::Duration;
+
 pub(crate) const API_ROOT_URL: &str = "https://www.okx.com";
 
 /// User Agent for the client
 pub(super) const USER_AGENT_NAME: &str =
     concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// Target of the `tracing` events
+pub(crate) const TRACING_TARGET: &str = "exchanges::okx";
+
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(25);
+
 pub(crate) const BTC_TICKER: &str = "BTC";
@@ -1,14 +1,25 @@
 //! OKX error
 
+use common::interceptor::{RequestError, ServerError};
 use reqwest::header::InvalidHeaderValue;
 use thiserror::Error;
 
+use crate::request::InstrumentType;
+
 /// OKX error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Reqwest error
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
+    /// Request error, with the method and path of the request
+    #[error(transparent)]
+    Request(#[from] RequestError),
+    /// Blocking runtime error
+    #[cfg(feature = "blocking")]
+    #[error(transparent)]
+    Runtime(#[from] std::io::Error),
     /// Invalid header error
     #[error(transparent)]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
@@ -34,7 +45,131 @@ pub enum Error {
         /// Error message details
         smg: String,
     },
+    /// OKX API batch error
+    ///
+    /// Returned when a request operating on multiple items fails for more than one of them.
+    #[error("OKX API error (code: {code}): {message}, {} items failed", items.len())]
+    OkxApiBatchError {
+        /// Error code
+        code: String,
+        /// Error message
+        message: String,
+        /// Per-item errors as `(sCode, sMsg)` pairs
+        items: Vec<(String, String)>,
+    },
+    /// Invalid order request
+    #[error("invalid order: {0}")]
+    InvalidOrder(String),
+    /// Ambiguous instrument ID (i.e., `BTCUSDT`): use the `BTC-USDT` format
+    #[error("ambiguous instrument: {0} (expected format: BTC-USDT)")]
+    AmbiguousInstrument(String),
+    /// Server error (HTTP 5xx)
+    #[error(transparent)]
+    ServerError(#[from] ServerError),
+    /// Overall deadline exceeded (see [`OkxClientBuilder::overall_deadline`])
+    ///
+    /// [`OkxClientBuilder::overall_deadline`]: crate::builder::OkxClientBuilder::overall_deadline
+    #[error("deadline of {0:?} exceeded")]
+    Deadline(std::time::Duration),
     /// Missing deposit address in response
     #[error("missing deposit address")]
     MissingDepositAddress,
+    /// Missing transfer in response
+    #[error("missing transfer")]
+    MissingTransfer,
+    /// Missing order in response
+    #[error("missing order")]
+    MissingOrder,
+    /// Missing account configuration in response
+    #[error("missing account configuration")]
+    MissingAccountConfig,
+    /// Derivative trade: the size is in contracts and can't be normalized
+    #[error("unsupported instrument type: {}", .0.as_str())]
+    UnsupportedInstrumentType(InstrumentType),
+}
+
+/// Rate limit codes
+///
+/// <https://www.okx.com/docs-v5/en/#error-code-rest-api-public>
+const RATE_LIMIT_CODES: [&str; 3] = [
+    "50011", // Rate limit reached
+    "50013", // Systems are busy
+    "50026", // System error, try again later
+];
+
+/// Other temporary codes
+const TEMPORARY_CODES: [&str; 2] = [
+    "50001", // Service temporarily unavailable
+    "50004", // Endpoint request timeout
+];
+
+impl Error {
+    /// Check if the request can be retried later
+    ///
+    /// Returns `true` for transport errors, exchange-side errors (HTTP 5xx) and rate limits,
+    /// `false` for the errors that will fail again (i.e., authentication or invalid parameters).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Reqwest(e) => is_retryable_reqwest_error(e),
+            Self::Request(e) => is_retryable_reqwest_error(e.inner()),
+            Self::ServerError(..) => true,
+            Self::OkxApiError { code, .. } => is_retryable_code(code),
+            Self::OkxApiBatchError { items, .. } => {
+                !items.is_empty() && items.iter().all(|(code, _)| is_retryable_code(code))
+            }
+            _ => false,
+        }
+    }
+}
+
+fn is_retryable_reqwest_error(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect() || e.is_request()
+}
+
+fn is_retryable_code(code: &str) -> bool {
+    // Non-200 HTTP responses use the status as code (i.e., `429 Too Many Requests`)
+    code.starts_with("429") || RATE_LIMIT_CODES.contains(&code) || TEMPORARY_CODES.contains(&code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(code: &str) -> Error {
+        Error::OkxApiError {
+            code: code.to_string(),
+            message: String::new(),
+            smg: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        // Rate limits
+        assert!(api_error("50011").is_retryable());
+        assert!(api_error("429 Too Many Requests").is_retryable());
+
+        // Temporary
+        assert!(api_error("50001").is_retryable());
+        assert!(Error::ServerError(ServerError::new(503, "")).is_retryable());
+
+        // Authentication
+        assert!(!api_error("50111").is_retryable());
+
+        // Parameters
+        assert!(!api_error("51000").is_retryable());
+        assert!(!Error::AmbiguousInstrument(String::from("BTCUSDT")).is_retryable());
+
+        // Batch
+        let batch = |codes: &[&str]| Error::OkxApiBatchError {
+            code: String::from("1"),
+            message: String::new(),
+            items: codes
+                .iter()
+                .map(|code| (code.to_string(), String::new()))
+                .collect(),
+        };
+        assert!(batch(&["50011", "50013"]).is_retryable());
+        assert!(!batch(&["50011", "51008"]).is_retryable());
+    }
 }
@@ -6,9 +6,13 @@
 #![warn(rustdoc::bare_urls)]
 
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod builder;
 pub mod client;
 mod constant;
 pub mod error;
 pub mod prelude;
+pub mod request;
 pub mod response;
 mod util;
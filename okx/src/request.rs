@@ -0,0 +1,377 @@
+//! OKX API requests
+
+use std::fmt;
+
+use common::amount::{Price, Quantity};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::Error;
+use crate::response::TradeSide;
+
+fn serialize_as_string<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: fmt::Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+fn serialize_optional_as_string<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: fmt::Display,
+    S: Serializer,
+{
+    match value {
+        Some(value) => serialize_as_string(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Account type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AccountType {
+    /// Funding account
+    #[serde(rename = "6")]
+    Funding,
+    /// Trading account
+    #[serde(rename = "18")]
+    Trading,
+}
+
+/// Instrument type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum InstrumentType {
+    /// Spot
+    Spot,
+    /// Margin
+    Margin,
+    /// Perpetual swap
+    Swap,
+    /// Expiry futures
+    Futures,
+    /// Option
+    Option,
+}
+
+impl InstrumentType {
+    /// Get the `instType` code (i.e., `SPOT`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Spot => "SPOT",
+            Self::Margin => "MARGIN",
+            Self::Swap => "SWAP",
+            Self::Futures => "FUTURES",
+            Self::Option => "OPTION",
+        }
+    }
+}
+
+/// Funds transfer request
+///
+/// <https://www.okx.com/docs-v5/en/#funding-account-rest-api-funds-transfer>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransferRequest {
+    /// Currency (i.e., `BTC`)
+    #[serde(rename = "ccy")]
+    pub currency: String,
+    /// Amount to be transferred
+    #[serde(rename = "amt")]
+    #[serde(serialize_with = "serialize_as_string")]
+    pub amount: f64,
+    /// Account to transfer from
+    pub from: AccountType,
+    /// Account to transfer to
+    pub to: AccountType,
+}
+
+impl TransferRequest {
+    /// New transfer request
+    pub fn new<S>(currency: S, amount: f64, from: AccountType, to: AccountType) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            currency: currency.into(),
+            amount,
+            from,
+            to,
+        }
+    }
+}
+
+/// Trade mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeMode {
+    /// Spot, without margin
+    Cash,
+    /// Cross margin
+    Cross,
+    /// Isolated margin
+    Isolated,
+}
+
+/// Order type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderType {
+    /// Market order
+    Market,
+    /// Limit order
+    Limit,
+    /// Limit order, only added to the book as maker
+    PostOnly,
+    /// Fill-or-kill limit order
+    Fok,
+    /// Immediate-or-cancel limit order
+    Ioc,
+}
+
+impl OrderType {
+    /// Check if the order type requires a price
+    #[inline]
+    pub fn requires_price(&self) -> bool {
+        !matches!(self, Self::Market)
+    }
+}
+
+/// Unit of the size of a spot market order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetCurrency {
+    /// Size in the base currency (i.e., `BTC` of `BTC-USDT`)
+    BaseCcy,
+    /// Size in the quote currency (i.e., `USDT` of `BTC-USDT`)
+    QuoteCcy,
+}
+
+/// Place order request
+///
+/// <https://www.okx.com/docs-v5/en/#order-book-trading-trade-post-place-order>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderRequest {
+    /// Instrument ID (i.e., `BTC-USDT`)
+    #[serde(rename = "instId")]
+    pub instrument_id: String,
+    /// Trade mode
+    #[serde(rename = "tdMode")]
+    pub trade_mode: TradeMode,
+    /// Order side
+    pub side: TradeSide,
+    /// Order type
+    #[serde(rename = "ordType")]
+    pub order_type: OrderType,
+    /// Quantity to buy or sell (number of contracts for derivatives)
+    ///
+    /// In the base currency, unless [`OrderRequest::target_currency`] says otherwise.
+    #[serde(rename = "sz")]
+    #[serde(serialize_with = "serialize_as_string")]
+    pub size: Quantity,
+    /// Unit of the size of a spot market order
+    ///
+    /// If not set, OKX reads the size of market **buys** in the quote currency.
+    #[serde(rename = "tgtCcy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_currency: Option<TargetCurrency>,
+    /// Order price: required by the limit types, forbidden for market orders
+    #[serde(rename = "px")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_as_string"
+    )]
+    pub price: Option<Price>,
+    /// Client-supplied order ID
+    #[serde(rename = "clOrdId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
+}
+
+impl OrderRequest {
+    /// New market order
+    ///
+    /// On spot instruments (i.e., `BTC-USDT`), `size` is in the base currency: use
+    /// [`OrderRequest::target_currency`] to spend an amount of the quote currency instead.
+    pub fn market<S>(
+        instrument_id: S,
+        trade_mode: TradeMode,
+        side: TradeSide,
+        size: Quantity,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        let instrument_id: String = instrument_id.into();
+
+        // `tgtCcy` only applies to spot instruments (`BASE-QUOTE`), not to derivatives
+        let target_currency: Option<TargetCurrency> = if instrument_id.split('-').count() == 2 {
+            Some(TargetCurrency::BaseCcy)
+        } else {
+            None
+        };
+
+        Self {
+            instrument_id,
+            trade_mode,
+            side,
+            order_type: OrderType::Market,
+            size,
+            target_currency,
+            price: None,
+            client_order_id: None,
+        }
+    }
+
+    /// New limit order
+    ///
+    /// Use [`OrderRequest::order_type`] for the post-only, FOK and IOC variants.
+    pub fn limit<S>(
+        instrument_id: S,
+        trade_mode: TradeMode,
+        side: TradeSide,
+        size: Quantity,
+        price: Price,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            instrument_id: instrument_id.into(),
+            trade_mode,
+            side,
+            order_type: OrderType::Limit,
+            size,
+            target_currency: None,
+            price: Some(price),
+            client_order_id: None,
+        }
+    }
+
+    /// Set the order type
+    #[inline]
+    pub fn order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = order_type;
+        self
+    }
+
+    /// Set the unit of the size of a spot market order
+    #[inline]
+    pub fn target_currency(mut self, target_currency: TargetCurrency) -> Self {
+        self.target_currency = Some(target_currency);
+        self
+    }
+
+    /// Set the client-supplied order ID
+    #[inline]
+    pub fn client_order_id<S>(mut self, client_order_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    /// Check that the price is set only when the order type requires it
+    pub fn validate(&self) -> Result<(), Error> {
+        match (self.order_type.requires_price(), self.price) {
+            (true, None) => Err(Error::InvalidOrder(format!(
+                "price required for {:?} orders",
+                self.order_type
+            ))),
+            (false, Some(..)) => Err(Error::InvalidOrder(String::from(
+                "price not allowed for market orders",
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_transfer_request() {
+        let req = TransferRequest::new("BTC", 0.5, AccountType::Funding, AccountType::Trading);
+        let json: String = serde_json::to_string(&req).unwrap();
+        assert_eq!(json, r#"{"ccy":"BTC","amt":"0.5","from":"6","to":"18"}"#);
+    }
+
+    #[test]
+    fn test_serialize_order_request() {
+        let req = OrderRequest::limit(
+            "BTC-USDT",
+            TradeMode::Cash,
+            TradeSide::Buy,
+            Quantity(0.01),
+            Price(60000.5),
+        )
+        .order_type(OrderType::PostOnly)
+        .client_order_id("b15");
+        let json: String = serde_json::to_string(&req).unwrap();
+        assert_eq!(
+            json,
+            r#"{"instId":"BTC-USDT","tdMode":"cash","side":"buy","ordType":"post_only","sz":"0.01","px":"60000.5","clOrdId":"b15"}"#
+        );
+
+        let req =
+            OrderRequest::market("BTC-USDT", TradeMode::Cross, TradeSide::Sell, Quantity(2.0));
+        let json: String = serde_json::to_string(&req).unwrap();
+        assert_eq!(
+            json,
+            r#"{"instId":"BTC-USDT","tdMode":"cross","side":"sell","ordType":"market","sz":"2","tgtCcy":"base_ccy"}"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_market_order_target_currency() {
+        let req =
+            OrderRequest::market("BTC-USDT", TradeMode::Cash, TradeSide::Buy, Quantity(100.0))
+                .target_currency(TargetCurrency::QuoteCcy);
+        let json: String = serde_json::to_string(&req).unwrap();
+        assert_eq!(
+            json,
+            r#"{"instId":"BTC-USDT","tdMode":"cash","side":"buy","ordType":"market","sz":"100","tgtCcy":"quote_ccy"}"#
+        );
+
+        // Derivatives are sized in contracts
+        let req = OrderRequest::market(
+            "BTC-USDT-SWAP",
+            TradeMode::Cross,
+            TradeSide::Buy,
+            Quantity(1.0),
+        );
+        let json: String = serde_json::to_string(&req).unwrap();
+        assert_eq!(
+            json,
+            r#"{"instId":"BTC-USDT-SWAP","tdMode":"cross","side":"buy","ordType":"market","sz":"1"}"#
+        );
+    }
+
+    #[test]
+    fn test_validate_order_request() {
+        let market =
+            OrderRequest::market("BTC-USDT", TradeMode::Cash, TradeSide::Buy, Quantity(1.0));
+        assert!(market.validate().is_ok());
+
+        let mut priced_market = market.clone();
+        priced_market.price = Some(Price(1.0));
+        assert!(matches!(
+            priced_market.validate(),
+            Err(Error::InvalidOrder(..))
+        ));
+
+        let ioc = market.order_type(OrderType::Ioc);
+        assert!(matches!(ioc.validate(), Err(Error::InvalidOrder(..))));
+
+        let limit = OrderRequest::limit(
+            "BTC-USDT",
+            TradeMode::Isolated,
+            TradeSide::Sell,
+            Quantity(1.0),
+            Price(2.0),
+        )
+        .order_type(OrderType::Fok);
+        assert!(limit.validate().is_ok());
+    }
+}
@@ -1,35 +1,144 @@
 //! OKX API responses
 
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
+use common::amount::{Fee, Price, Quantity};
 use common::deser::{
-    deserialize_string_to_f64, deserialize_unix_timestamp_milliseconds_to_utc_seconds,
+    deserialize_optional_string_to_f64, deserialize_string_to_f64,
+    deserialize_unix_timestamp_milliseconds_to_utc_seconds,
 };
+use common::exchange::Exchange;
+use common::trade::{NormalizedTrade, TradeSide as NormalizedTradeSide};
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+#[cfg(feature = "decimal")]
+use rust_decimal::prelude::ToPrimitive;
 use serde::de::DeserializeOwned;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+use crate::error::Error;
+use crate::request::InstrumentType;
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct OkxApiResponse {
     pub code: String,
     pub msg: String,
     pub data: Value,
+    /// Request received by the OKX gateway (Unix timestamp in microseconds)
+    #[serde(rename = "inTime", default)]
+    pub in_time: Option<String>,
+    /// Response sent by the OKX gateway (Unix timestamp in microseconds)
+    #[serde(rename = "outTime", default)]
+    pub out_time: Option<String>,
+}
+
+impl OkxApiResponse {
+    /// Get the server-side timing, if returned by the endpoint
+    pub fn timing(&self) -> Option<ResponseTiming> {
+        let in_time: i64 = self.in_time.as_deref()?.parse().ok()?;
+        let out_time: i64 = self.out_time.as_deref()?.parse().ok()?;
+
+        Some(ResponseTiming {
+            in_time: DateTime::from_timestamp_micros(in_time)?,
+            out_time: DateTime::from_timestamp_micros(out_time)?,
+        })
+    }
+}
+
+/// Server-side timing of a response
+///
+/// Returned only by some endpoints (i.e., trading ones). Compare the
+/// [`ResponseTiming::processing_time`] with the round-trip time to tell the network latency apart
+/// from the OKX one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseTiming {
+    /// Request received by the OKX gateway
+    pub in_time: DateTime<Utc>,
+    /// Response sent by the OKX gateway
+    pub out_time: DateTime<Utc>,
+}
+
+impl ResponseTiming {
+    /// Time spent by OKX processing the request
+    #[inline]
+    pub fn processing_time(&self) -> Duration {
+        (self.out_time - self.in_time).to_std().unwrap_or_default()
+    }
+}
+
+/// Full OKX response, including the per-item status codes
+///
+/// Batch operations may succeed only for some items: check [`OkxResponse::is_partial_success`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OkxResponse<T> {
+    /// Response code (`0`: success, `2`: partial success)
+    pub code: String,
+    /// Response message
+    pub msg: String,
+    /// Items
+    pub data: Vec<OkxResponseItem<T>>,
+    /// Server-side timing, if returned by the endpoint
+    pub timing: Option<ResponseTiming>,
+}
+
+impl<T> OkxResponse<T> {
+    /// Check if some items failed
+    pub fn is_partial_success(&self) -> bool {
+        self.data.iter().any(|item| !item.is_success())
+    }
+
+    /// Get the failed items
+    pub fn failed_items(&self) -> impl Iterator<Item = &OkxResponseItem<T>> {
+        self.data.iter().filter(|item| !item.is_success())
+    }
+
+    /// Get the data of the items, dropping the status codes
+    pub fn into_data(self) -> Vec<T> {
+        self.data.into_iter().map(|item| item.data).collect()
+    }
+}
+
+/// Item of a [`OkxResponse`]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OkxResponseItem<T> {
+    /// Item status code (`0` or missing: success)
+    #[serde(rename = "sCode", default)]
+    pub code: Option<String>,
+    /// Item status message
+    #[serde(rename = "sMsg", default)]
+    pub message: Option<String>,
+    /// Item data
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T> OkxResponseItem<T> {
+    /// Check if the item succeeded
+    pub fn is_success(&self) -> bool {
+        matches!(self.code.as_deref(), None | Some("" | "0"))
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct OkxApiErrorData {
+    #[serde(rename = "sCode")]
+    pub(crate) s_code: Option<String>,
     #[serde(rename = "sMsg")]
     pub(crate) s_msg: Option<String>,
 }
 
+/// Account balance.
 #[derive(Debug, Clone, Deserialize)]
-pub(crate) struct Account {
+pub struct Account {
     /// Detailed asset information per currency
     pub details: Vec<CurrencyDetail>,
 }
 
 /// Detailed asset information per currency
 #[derive(Debug, Clone, Deserialize)]
-pub(crate) struct CurrencyDetail {
+pub struct CurrencyDetail {
     /// Currency
     #[serde(rename = "ccy")]
     pub currency: String,
@@ -39,6 +148,80 @@ pub(crate) struct CurrencyDetail {
     pub amount: f64,
 }
 
+/// Account configuration.
+///
+/// <https://www.okx.com/docs-v5/en/#trading-account-rest-api-get-account-configuration>
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AccountConfig {
+    /// Account ID.
+    pub uid: String,
+    /// Account mode.
+    #[serde(rename = "acctLv")]
+    pub account_level: AccountLevel,
+    /// Position mode.
+    #[serde(rename = "posMode")]
+    pub position_mode: PositionMode,
+    /// Whether borrowing is automatic (multi-currency margin mode only).
+    #[serde(rename = "autoLoan")]
+    pub auto_loan: bool,
+}
+
+/// Account mode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub enum AccountLevel {
+    /// Spot mode.
+    #[serde(rename = "1")]
+    Spot,
+    /// Spot and futures mode.
+    #[serde(rename = "2")]
+    SpotAndFutures,
+    /// Multi-currency margin mode.
+    #[serde(rename = "3")]
+    MultiCurrencyMargin,
+    /// Portfolio margin mode.
+    #[serde(rename = "4")]
+    PortfolioMargin,
+    /// Account mode not known by this library.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Position mode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionMode {
+    /// Long/short positions (only for futures and swaps).
+    LongShortMode,
+    /// Net positions.
+    NetMode,
+    /// Position mode not known by this library.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// Sub-account.
+///
+/// <https://www.okx.com/docs-v5/en/#sub-account-rest-api-get-sub-account-list>
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SubAccount {
+    /// Sub-account name.
+    #[serde(rename = "subAcct")]
+    pub name: String,
+    /// Sub-account note.
+    pub label: String,
+    /// Sub-account type (`1`: standard, `2`: managed trading, `5`: custody trading).
+    pub r#type: String,
+    /// Whether the sub-account is enabled (or frozen).
+    pub enable: bool,
+    /// Whether the sub-account can transfer out.
+    #[serde(rename = "canTransOut")]
+    pub can_transfer_out: bool,
+    /// Creation timestamp.
+    #[serde(rename = "ts")]
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
 /// Deposit address.
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct DepositAddress {
@@ -50,10 +233,50 @@ pub struct DepositAddress {
     /// Deposit address.
     #[serde(rename = "addr")]
     pub address: String,
+    /// Beneficiary account (`6`: funding account, `18`: trading account).
+    pub to: String,
     /// Whether this address is selected by default.
     pub selected: bool,
 }
 
+/// Currency info, per chain.
+///
+/// <https://www.okx.com/docs-v5/en/#funding-account-rest-api-get-currencies>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CurrencyInfo {
+    /// Currency.
+    #[serde(rename = "ccy")]
+    pub currency: String,
+    /// Chain name (i.e., `BTC-Bitcoin`).
+    pub chain: String,
+    /// Minimum withdrawal amount (`None` if not provided for the chain).
+    #[serde(rename = "minWd", default)]
+    #[serde(deserialize_with = "deserialize_optional_string_to_f64")]
+    pub min_withdrawal: Option<f64>,
+    /// Maximum withdrawal amount (`None` if not provided for the chain).
+    #[serde(rename = "maxWd", default)]
+    #[serde(deserialize_with = "deserialize_optional_string_to_f64")]
+    pub max_withdrawal: Option<f64>,
+    /// Current withdrawal fee (`None` if not provided for the chain).
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_optional_string_to_f64")]
+    pub fee: Option<f64>,
+    /// Minimum withdrawal fee (`None` if not provided for the chain).
+    #[serde(rename = "minFee", default)]
+    #[serde(deserialize_with = "deserialize_optional_string_to_f64")]
+    pub min_fee: Option<f64>,
+    /// Maximum withdrawal fee (`None` if not provided for the chain).
+    #[serde(rename = "maxFee", default)]
+    #[serde(deserialize_with = "deserialize_optional_string_to_f64")]
+    pub max_fee: Option<f64>,
+    /// Whether withdrawals are enabled.
+    #[serde(rename = "canWd")]
+    pub can_withdraw: bool,
+    /// Whether deposits are enabled.
+    #[serde(rename = "canDep")]
+    pub can_deposit: bool,
+}
+
 /// Status of deposit
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 pub enum DepositStatus {
@@ -137,6 +360,29 @@ pub enum WithdrawalStatus {
     InsufficientHotWalletBalance,
 }
 
+impl WithdrawalStatus {
+    /// Numeric state code, as used by the `state` query parameter.
+    ///
+    /// [`WithdrawalStatus::WaitingManualReview`] groups several codes: `4` is returned.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::WaitingWithdrawal => "0",
+            Self::WaitingManualReview => "4",
+            Self::Approved => "7",
+            Self::Withdrawing => "1",
+            Self::WaitingTransfer => "10",
+            Self::PendingTransactionValidation => "15",
+            Self::RegulatoryDelay => "16",
+            Self::Canceling => "-3",
+            Self::Canceled => "-2",
+            Self::Failed => "-1",
+            Self::WithdrawalSuccessful => "2",
+            Self::PendingTravelRuleVendor => "17",
+            Self::InsufficientHotWalletBalance => "19",
+        }
+    }
+}
+
 fn deserialize_optional_enum<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
 where
     D: Deserializer<'de>,
@@ -146,6 +392,14 @@ where
     Ok(value.and_then(|value| serde_json::from_value(value).ok()))
 }
 
+fn deserialize_empty_string_as_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    Ok(value.filter(|value| !value.is_empty()))
+}
+
 /// Deposit transaction
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct DepositTransaction {
@@ -200,7 +454,7 @@ pub struct WithdrawalTransaction {
 }
 
 /// Trade side.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TradeSide {
     /// Buy trade.
@@ -209,31 +463,87 @@ pub enum TradeSide {
     Sell,
 }
 
+/// Numeric value of a trade (size, price and fee).
+///
+/// `Decimal` with the `decimal` feature, keeping the exact value sent by OKX; `f64` otherwise.
+/// Used instead of [`Price`], [`Quantity`] and [`Fee`] (`f64`) for that reason: convert with
+/// [`Trade::into_normalized`].
+#[cfg(feature = "decimal")]
+pub type TradeValue = Decimal;
+
+/// Numeric value of a trade (size, price and fee).
+///
+/// `Decimal` with the `decimal` feature, keeping the exact value sent by OKX; `f64` otherwise.
+/// Used instead of [`Price`], [`Quantity`] and [`Fee`] (`f64`) for that reason: convert with
+/// [`Trade::into_normalized`].
+#[cfg(not(feature = "decimal"))]
+pub type TradeValue = f64;
+
+#[cfg(feature = "decimal")]
+fn parse_trade_value(s: &str) -> Result<TradeValue, rust_decimal::Error> {
+    Decimal::from_str_exact(s)
+}
+
+#[cfg(not(feature = "decimal"))]
+fn parse_trade_value(s: &str) -> Result<TradeValue, std::num::ParseFloatError> {
+    s.parse()
+}
+
+#[cfg(feature = "decimal")]
+#[inline]
+fn trade_value_to_f64(value: TradeValue) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+#[cfg(not(feature = "decimal"))]
+#[inline]
+fn trade_value_to_f64(value: TradeValue) -> f64 {
+    value
+}
+
+fn deserialize_string_to_trade_value<'de, D>(deserializer: D) -> Result<TradeValue, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = String::deserialize(deserializer)?;
+    parse_trade_value(&s).map_err(serde::de::Error::custom)
+}
+
 /// Executed trade.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct Trade {
     /// Trade identifier.
     #[serde(rename = "tradeId")]
     pub id: String,
+    /// Instrument type.
+    #[serde(rename = "instType")]
+    pub instrument_type: InstrumentType,
     /// Instrument identifier (for example, `BTC-USDT`).
     #[serde(rename = "instId")]
     pub instrument_id: String,
     /// Order identifier.
     #[serde(rename = "ordId")]
     pub order_id: String,
+    /// Client-supplied order identifier.
+    #[serde(rename = "clOrdId")]
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub client_order_id: Option<String>,
+    /// Bill identifier, used for pagination.
+    #[serde(rename = "billId")]
+    pub bill_id: String,
     /// Trade side.
     pub side: TradeSide,
-    /// Filled size.
+    /// Filled size: base currency for spot and margin, number of contracts for derivatives.
     #[serde(rename = "fillSz")]
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub size: f64,
+    #[serde(deserialize_with = "deserialize_string_to_trade_value")]
+    pub size: TradeValue,
     /// Fill price.
     #[serde(rename = "fillPx")]
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub price: f64,
+    #[serde(deserialize_with = "deserialize_string_to_trade_value")]
+    pub price: TradeValue,
     /// Trade fee.
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub fee: f64,
+    #[serde(deserialize_with = "deserialize_string_to_trade_value")]
+    pub fee: TradeValue,
     /// Fee currency.
     #[serde(rename = "feeCcy")]
     pub fee_currency: String,
@@ -243,6 +553,107 @@ pub struct Trade {
     pub timestamp: DateTime<Utc>,
 }
 
+impl Trade {
+    /// Get the fee and its currency
+    ///
+    /// The fee is positive when charged and negative for rebates (OKX uses the opposite sign).
+    #[inline]
+    pub fn normalized_fee(&self) -> (Fee, &str) {
+        (Fee(-trade_value_to_f64(self.fee)), &self.fee_currency)
+    }
+
+    /// Convert to a [`NormalizedTrade`]
+    ///
+    /// See [`NormalizedTrade::try_from`].
+    #[inline]
+    pub fn into_normalized(self) -> Result<NormalizedTrade, Error> {
+        NormalizedTrade::try_from(self)
+    }
+}
+
+impl TryFrom<Trade> for NormalizedTrade {
+    type Error = Error;
+
+    /// Only spot and margin trades are supported: derivative sizes are in contracts, whose
+    /// value (`ctVal`) isn't returned with the fills.
+    fn try_from(trade: Trade) -> Result<Self, Self::Error> {
+        if !matches!(
+            trade.instrument_type,
+            InstrumentType::Spot | InstrumentType::Margin
+        ) {
+            return Err(Error::UnsupportedInstrumentType(trade.instrument_type));
+        }
+
+        Ok(Self {
+            exchange: Exchange::Okx,
+            symbol: trade.instrument_id,
+            side: match trade.side {
+                TradeSide::Buy => NormalizedTradeSide::Buy,
+                TradeSide::Sell => NormalizedTradeSide::Sell,
+            },
+            base_qty: Quantity(trade_value_to_f64(trade.size)),
+            quote_qty: Quantity(trade_value_to_f64(trade.size * trade.price)),
+            price: Price(trade_value_to_f64(trade.price)),
+            fee: Fee(-trade_value_to_f64(trade.fee)),
+            fee_currency: trade.fee_currency,
+            timestamp: trade.timestamp,
+            trade_id: trade.id,
+        })
+    }
+}
+
+/// Account bill.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Bill {
+    /// Bill identifier.
+    #[serde(rename = "billId")]
+    pub bill_id: String,
+    /// Currency.
+    #[serde(rename = "ccy")]
+    pub currency: String,
+    /// Balance change.
+    #[serde(rename = "balChg")]
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub balance_change: f64,
+    /// Bill type (for example, `1`: transfer, `2`: trade).
+    #[serde(rename = "type")]
+    pub bill_type: String,
+    /// Bill sub-type.
+    #[serde(rename = "subType")]
+    pub sub_type: String,
+    /// Fee (negative when charged, positive for rebates).
+    #[serde(deserialize_with = "deserialize_string_to_f64")]
+    pub fee: f64,
+    /// Bill timestamp, normalized to UTC seconds.
+    #[serde(rename = "ts")]
+    #[serde(deserialize_with = "deserialize_unix_timestamp_milliseconds_to_utc_seconds")]
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Funds transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Transfer {
+    /// Transfer identifier.
+    #[serde(rename = "transId")]
+    pub id: String,
+    /// Client-supplied identifier.
+    #[serde(rename = "clientId")]
+    pub client_id: String,
+}
+
+/// Placed order
+///
+/// <https://www.okx.com/docs-v5/en/#order-book-trading-trade-post-place-order>
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PlacedOrder {
+    /// Order ID
+    #[serde(rename = "ordId")]
+    pub order_id: String,
+    /// Client-supplied order ID
+    #[serde(rename = "clOrdId", default)]
+    pub client_order_id: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +690,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deserialize_subaccount() {
+        let json = r#"{
+      "canTransOut": false,
+      "enable": true,
+      "frozenFunc": [],
+      "gAuth": false,
+      "label": "D456DDDLx",
+      "mobile": "",
+      "subAcct": "D456DDDL",
+      "ts": "1659334756000",
+      "type": "1",
+      "uid": "3400***********7413",
+      "subAcctLv": "1",
+      "firstLvSubAcct": "D456DDDL",
+      "ifDma": false
+    }"#;
+        let sub_account: SubAccount = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            sub_account,
+            SubAccount {
+                name: "D456DDDL".to_string(),
+                label: "D456DDDLx".to_string(),
+                r#type: "1".to_string(),
+                enable: true,
+                can_transfer_out: false,
+                created_at: DateTime::from_timestamp(1659334756, 0).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_currency_info() {
+        let json = r#"{
+      "burningFeeRate": "",
+      "canDep": true,
+      "canInternal": false,
+      "canWd": true,
+      "ccy": "BTC",
+      "chain": "BTC-Bitcoin",
+      "depQuotaFixed": "",
+      "depQuoteDailyLayer2": "",
+      "fee": "0.0002",
+      "logoLink": "https://static.coinall.ltd/cdn/oksupport/asset/currency/icon/btc20230419112752.png",
+      "mainNet": true,
+      "maxFee": "0.0004",
+      "maxFeeForCtAddr": "",
+      "maxWd": "500",
+      "minDep": "0.0005",
+      "minDepArrivalConfirm": "1",
+      "minFee": "0.0002",
+      "minFeeForCtAddr": "",
+      "minInternal": "0.0001",
+      "minWd": "0.0005",
+      "minWdUnlockConfirm": "2",
+      "name": "Bitcoin",
+      "needTag": false,
+      "usedDepQuotaFixed": "",
+      "usedWdQuota": "0",
+      "wdQuota": "10000000",
+      "wdTickSz": "8"
+    }"#;
+        let info: CurrencyInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            info,
+            CurrencyInfo {
+                currency: "BTC".to_string(),
+                chain: "BTC-Bitcoin".to_string(),
+                min_withdrawal: Some(0.0005),
+                max_withdrawal: Some(500.0),
+                fee: Some(0.0002),
+                min_fee: Some(0.0002),
+                max_fee: Some(0.0004),
+                can_withdraw: true,
+                can_deposit: true,
+            }
+        );
+
+        // Empty amounts, on chains without withdrawals
+        let json = r#"{
+      "canDep": true,
+      "canWd": false,
+      "ccy": "USDT",
+      "chain": "USDT-Omni",
+      "fee": "",
+      "maxFee": "",
+      "maxWd": "",
+      "minFee": "",
+      "minWd": "2"
+    }"#;
+        let info: CurrencyInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.min_withdrawal, Some(2.0));
+        assert_eq!(info.max_withdrawal, None);
+        assert_eq!(info.fee, None);
+        assert_eq!(info.min_fee, None);
+        assert_eq!(info.max_fee, None);
+    }
+
     #[test]
     fn test_deserialize_withdrawal_tx() {
         let json = r#"{
@@ -348,6 +857,87 @@ mod tests {
         assert_eq!(tx.state, None);
     }
 
+    #[test]
+    fn test_deserialize_partial_success_items() {
+        let json = r#"[
+        {"transId": "754147", "clientId": "", "sCode": "0", "sMsg": ""},
+        {"transId": "", "clientId": "", "sCode": "58350", "sMsg": "Insufficient balance"},
+        {"transId": "754148", "clientId": ""}
+    ]"#;
+
+        let data: Vec<OkxResponseItem<Transfer>> = serde_json::from_str(json).unwrap();
+        let response = OkxResponse {
+            code: String::from("2"),
+            msg: String::new(),
+            data,
+            timing: None,
+        };
+
+        assert!(response.is_partial_success());
+
+        let failed: Vec<&OkxResponseItem<Transfer>> = response.failed_items().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].code.as_deref(), Some("58350"));
+        assert_eq!(failed[0].message.as_deref(), Some("Insufficient balance"));
+
+        let transfers: Vec<Transfer> = response.into_data();
+        assert_eq!(transfers[0].id, "754147");
+        assert_eq!(transfers[2].id, "754148");
+    }
+
+    #[test]
+    fn test_deserialize_bill() {
+        let json = r#"{
+        "bal": "8694.2179403378290202",
+        "balChg": "0.0219338232210000",
+        "billId": "623950854533513219",
+        "ccy": "USDT",
+        "clOrdId": "",
+        "execType": "T",
+        "fee": "-0.000021955779",
+        "fillFwdPx": "",
+        "fillIdxPx": "27104.1",
+        "fillMarkPx": "",
+        "fillMarkVol": "",
+        "fillPxUsd": "",
+        "fillPxVol": "",
+        "fillTime": "1695033476166",
+        "from": "",
+        "instId": "BTC-USDT",
+        "instType": "SPOT",
+        "interest": "0",
+        "mgnMode": "isolated",
+        "notes": "",
+        "ordId": "623950854525124608",
+        "pnl": "0",
+        "posBal": "0",
+        "posBalChg": "0",
+        "px": "27105.9",
+        "subType": "1",
+        "sz": "0.021955779",
+        "tag": "",
+        "to": "",
+        "tradeId": "586760148",
+        "ts": "1695033476167",
+        "type": "2"
+    }"#;
+
+        let bill: Bill = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            bill,
+            Bill {
+                bill_id: "623950854533513219".to_string(),
+                currency: "USDT".to_string(),
+                balance_change: 0.021933823221,
+                bill_type: "2".to_string(),
+                sub_type: "1".to_string(),
+                fee: -0.000021955779,
+                timestamp: DateTime::from_timestamp(1695033476, 0).unwrap(),
+            }
+        );
+    }
+
     #[test]
     fn test_deserialize_deposit_address() {
         let json = r#"{
@@ -368,6 +958,7 @@ mod tests {
                 chain: "BTC-Bitcoin".to_string(),
                 currency: "BTC".to_string(),
                 address: "39XNxK1Ryqgg3Bsyn6HzoqV4Xji25pNkv6".to_string(),
+                to: "6".to_string(),
                 selected: true,
             }
         );
@@ -410,15 +1001,75 @@ mod tests {
             trade,
             Trade {
                 id: "744876980".to_string(),
+                instrument_type: InstrumentType::Spot,
                 instrument_id: "BTC-USDT".to_string(),
                 order_id: "680800019749904384".to_string(),
+                client_order_id: None,
+                bill_id: "680800019754098688".to_string(),
                 side: TradeSide::Buy,
-                size: 0.00192834,
-                price: 51858.0,
-                fee: -0.00000192834,
+                size: parse_trade_value("0.00192834").unwrap(),
+                price: parse_trade_value("51858").unwrap(),
+                fee: parse_trade_value("-0.00000192834").unwrap(),
                 fee_currency: "BTC".to_string(),
                 timestamp: DateTime::from_timestamp(1708587373, 0).unwrap(),
             }
-        )
+        );
+        assert_eq!(trade.normalized_fee(), (Fee(0.00000192834), "BTC"));
+
+        let trade: NormalizedTrade = trade.into_normalized().unwrap();
+        assert_eq!(trade.exchange, Exchange::Okx);
+        assert_eq!(trade.symbol, "BTC-USDT");
+        assert_eq!(trade.side, NormalizedTradeSide::Buy);
+        assert!((*trade.quote_qty - 0.00192834 * 51858.0).abs() < 1e-9);
+        assert_eq!(*trade.fee, 0.00000192834);
+        assert_eq!(trade.trade_id, "744876980");
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_trade_sizes_sum_exactly() {
+        let json = r#"{"tradeId":"1","instType":"SPOT","instId":"BTC-USDT","ordId":"2","billId":"3","side":"buy","fillSz":"0.00000001","fillPx":"51858.1","fee":"-0.00000001","feeCcy":"BTC","ts":"1708587373362"}"#;
+        let trade: Trade = serde_json::from_str(json).unwrap();
+
+        let trades: Vec<Trade> = vec![trade; 100_000];
+        let size: Decimal = trades.iter().map(|t| t.size).sum();
+        let fee: Decimal = trades.iter().map(|t| t.fee).sum();
+
+        assert_eq!(size, Decimal::new(1, 3));
+        assert_eq!(fee, Decimal::new(-1, 3));
+        assert_eq!(trades[0].price, Decimal::new(518581, 1));
+    }
+
+    #[test]
+    fn test_deserialize_trade_client_order_id() {
+        let json = r#"{"tradeId":"1","instType":"SPOT","instId":"BTC-USDT","ordId":"2","clOrdId":"my-order-1","billId":"3","side":"sell","fillSz":"1","fillPx":"1","fee":"0","feeCcy":"USDT","ts":"1708587373362"}"#;
+        let trade: Trade = serde_json::from_str(json).unwrap();
+        assert_eq!(trade.client_order_id.as_deref(), Some("my-order-1"));
+
+        let json = r#"{"tradeId":"1","instType":"SPOT","instId":"BTC-USDT","ordId":"2","billId":"3","side":"sell","fillSz":"1","fillPx":"1","fee":"0","feeCcy":"USDT","ts":"1708587373362"}"#;
+        let trade: Trade = serde_json::from_str(json).unwrap();
+        assert_eq!(trade.client_order_id, None);
+    }
+
+    #[test]
+    fn test_deserialize_transfer() {
+        let json = r#"{
+        "transId": "754147",
+        "ccy": "USDT",
+        "clientId": "",
+        "from": "6",
+        "amt": "0.1",
+        "to": "18"
+    }"#;
+
+        let transfer: Transfer = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            transfer,
+            Transfer {
+                id: "754147".to_string(),
+                client_id: String::new(),
+            }
+        );
     }
 }
@@ -1,7 +1,46 @@
 use chrono::{DateTime, Utc};
 
+use crate::error::Error;
+
 /// Format timestamp to the following format: YYYY-MM-DDTHH:mm:ss.sssZ (i.e., 2020-12-08T09:08:57.715Z)
 #[inline]
 pub(crate) fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
     timestamp.format("%Y-%m-%dT%H:%M:%S.%3fZ").to_string()
 }
+
+/// Normalize an instrument ID to the OKX format (i.e., `btc/usdt` -> `BTC-USDT`)
+///
+/// `/` and `_` separators are replaced by `-`. An input without separator (i.e., `BTCUSDT`) is
+/// ambiguous and rejected.
+pub(crate) fn normalize_instrument(input: &str) -> Result<String, Error> {
+    let instrument: String = input.trim().to_ascii_uppercase().replace(['/', '_'], "-");
+
+    if instrument.split('-').count() < 2 || instrument.split('-').any(str::is_empty) {
+        return Err(Error::AmbiguousInstrument(input.to_string()));
+    }
+
+    Ok(instrument)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_instrument() {
+        assert_eq!(normalize_instrument("BTC-USDT").unwrap(), "BTC-USDT");
+        assert_eq!(normalize_instrument("btc/usdt").unwrap(), "BTC-USDT");
+        assert_eq!(normalize_instrument(" eth_btc ").unwrap(), "ETH-BTC");
+        assert_eq!(
+            normalize_instrument("btc-usdt-swap").unwrap(),
+            "BTC-USDT-SWAP"
+        );
+
+        assert!(matches!(
+            normalize_instrument("BTCUSDT"),
+            Err(Error::AmbiguousInstrument(input)) if input == "BTCUSDT"
+        ));
+        assert!(normalize_instrument("BTC-").is_err());
+        assert!(normalize_instrument("").is_err());
+    }
+}
@@ -0,0 +1,453 @@
+//! Replay recorded responses through the client
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use okx_api::prelude::*;
+use wiremock::matchers::{
+    body_json, header, header_exists, header_regex, method, path, query_param,
+};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn credentials() -> OkxApiCredentials {
+    OkxApiCredentials {
+        api_key: String::from("key"),
+        api_secret: String::from("secret"),
+        passphrase: String::from("passphrase"),
+    }
+}
+
+fn client(server: &MockServer) -> OkxClient {
+    OkxClient::builder(credentials())
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .build()
+        .unwrap()
+}
+
+/// Match the authentication headers
+fn authenticated(http_method: &str, http_path: &str) -> wiremock::MockBuilder {
+    Mock::given(method(http_method))
+        .and(path(http_path))
+        .and(header("OK-ACCESS-KEY", "key"))
+        .and(header("OK-ACCESS-PASSPHRASE", "passphrase"))
+        .and(header_exists("OK-ACCESS-SIGN"))
+        .and(header_exists("OK-ACCESS-TIMESTAMP"))
+}
+
+#[tokio::test]
+async fn test_balance() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/account/balance")
+        .and(query_param("ccy", "BTC"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balance.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let balance = client(&server).balance().await.unwrap();
+    assert_eq!(balance, 0.25);
+}
+
+#[tokio::test]
+async fn test_bitcoin_deposit_address() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/asset/deposit-address")
+        .and(query_param("ccy", "BTC"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/deposit_address.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let address = client(&server).bitcoin_deposit_address().await.unwrap();
+    assert_eq!(address, "39XNxK1Ryqgg3Bsyn6HzoqV4Xji25pNkv6");
+}
+
+#[tokio::test]
+async fn test_subaccount_balances() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/account/subaccount/balances")
+        .and(query_param("subAcct", "strategy1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balance.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let accounts = client(&server)
+        .subaccount_balances("strategy1")
+        .await
+        .unwrap();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].details[0].currency, "BTC");
+}
+
+#[tokio::test]
+async fn test_currencies() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/asset/currencies")
+        .and(query_param("ccy", "BTC"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/currencies.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let currencies = client(&server).currencies(Some("BTC")).await.unwrap();
+    assert_eq!(currencies.len(), 2);
+    assert_eq!(currencies[0].chain, "BTC-Bitcoin");
+    assert_eq!(currencies[0].fee, Some(0.0002));
+    assert_eq!(currencies[0].min_fee, Some(0.0002));
+    assert_eq!(currencies[0].max_withdrawal, Some(500.0));
+    assert!(!currencies[1].can_deposit);
+}
+
+#[tokio::test]
+async fn test_api_error() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/account/balance")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/error.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server).balance().await.unwrap_err();
+    match err {
+        Error::OkxApiError { code, message, .. } => {
+            assert_eq!(code, "50113");
+            assert_eq!(message, "Invalid Sign");
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_full_partial_success() {
+    let server = MockServer::start().await;
+
+    authenticated("POST", "/api/v5/asset/transfer")
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/transfer_partial.json")),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let req = TransferRequest::new("BTC", 0.1, AccountType::Funding, AccountType::Trading);
+
+    let response = client.transfer_full(req.clone()).await.unwrap();
+    assert!(response.is_partial_success());
+    assert_eq!(response.failed_items().count(), 1);
+
+    // Without the per-item codes, the partial success is an error
+    assert!(client.transfer(req).await.is_err());
+}
+
+#[tokio::test]
+async fn test_place_order() {
+    let server = MockServer::start().await;
+
+    authenticated("POST", "/api/v5/trade/order")
+        .and(body_json(serde_json::json!({
+            "instId": "BTC-USDT",
+            "tdMode": "cash",
+            "side": "buy",
+            "ordType": "limit",
+            "sz": "0.01",
+            "px": "60000",
+            "clOrdId": "b15"
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/place_order.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let req = OrderRequest::limit(
+        "BTC-USDT",
+        TradeMode::Cash,
+        TradeSide::Buy,
+        Quantity(0.01),
+        Price(60000.0),
+    )
+    .client_order_id("b15");
+
+    let order = client.place_order(req).await.unwrap();
+    assert!(order.is_success());
+    assert_eq!(order.code.as_deref(), Some("0"));
+    assert_eq!(order.data.order_id, "312269865356374016");
+    assert_eq!(order.data.client_order_id, "b15");
+
+    // Rejected before being sent
+    let req = OrderRequest::market("BTC-USDT", TradeMode::Cash, TradeSide::Buy, Quantity(0.01))
+        .order_type(OrderType::PostOnly);
+    assert!(matches!(
+        client.place_order(req).await.unwrap_err(),
+        Error::InvalidOrder(..)
+    ));
+}
+
+#[tokio::test]
+async fn test_place_order_full_timing() {
+    let server = MockServer::start().await;
+
+    authenticated("POST", "/api/v5/trade/order")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/place_order.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let req = OrderRequest::limit(
+        "BTC-USDT",
+        TradeMode::Cash,
+        TradeSide::Buy,
+        Quantity(0.01),
+        Price(60000.0),
+    );
+    let res = client(&server).place_order_full(req).await.unwrap();
+
+    let timing = res.timing.unwrap();
+    assert_eq!(timing.in_time.timestamp_micros(), 1695190491421339);
+    assert_eq!(timing.processing_time(), Duration::from_micros(1901));
+    assert_eq!(res.data[0].data.order_id, "312269865356374016");
+}
+
+#[tokio::test]
+async fn test_overall_deadline() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/account/balance")
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/balance.json"))
+                .set_delay(std::time::Duration::from_secs(2)),
+        )
+        .mount(&server)
+        .await;
+
+    let credentials = OkxApiCredentials {
+        api_key: String::from("key"),
+        api_secret: String::from("secret"),
+        passphrase: String::from("passphrase"),
+    };
+
+    let client = OkxClient::builder(credentials)
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .overall_deadline(std::time::Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    let err = client.balance().await.unwrap_err();
+    assert!(matches!(err, Error::Deadline(..)));
+}
+
+#[tokio::test]
+async fn test_shared_reqwest_client() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/account/balance")
+        .and(header_exists("User-Agent"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balance.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let credentials = OkxApiCredentials {
+        api_key: String::from("key"),
+        api_secret: String::from("secret"),
+        passphrase: String::from("passphrase"),
+    };
+    let client = OkxClient::builder(credentials)
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .reqwest_client(reqwest::Client::new())
+        .build()
+        .unwrap();
+
+    client.balance().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_rate_limiter() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/account/balance")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balance.json")),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let credentials = OkxApiCredentials {
+        api_key: String::from("key"),
+        api_secret: String::from("secret"),
+        passphrase: String::from("passphrase"),
+    };
+    let limiter = Arc::new(SlidingWindow::new(1, Duration::from_millis(300)));
+    let client = OkxClient::builder(credentials)
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .rate_limiter(limiter.clone())
+        .build()
+        .unwrap();
+
+    let start = Instant::now();
+    client.balance().await.unwrap();
+    assert_eq!(limiter.used(), 1);
+
+    // Held back until the first request leaves the window
+    client.balance().await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn test_account_config() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/account/config")
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/account_config.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = client(&server).account_config().await.unwrap();
+    assert_eq!(config.uid, "44705892343619584");
+    assert_eq!(config.account_level, AccountLevel::SpotAndFutures);
+    assert_eq!(config.position_mode, PositionMode::LongShortMode);
+    assert!(!config.auto_loan);
+}
+
+#[tokio::test]
+async fn test_healthcheck() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/public/time"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"code":"0","msg":"","data":[{"ts":"1597026383085"}]}"#),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    authenticated("GET", "/api/v5/account/config")
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/account_config.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).healthcheck().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_trade_history_for_type() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/api/v5/trade/fills-history")
+        .and(query_param("instType", "SWAP"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/fills_history_swap.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let trades = client(&server)
+        .trade_history_for_type(InstrumentType::Swap)
+        .await
+        .unwrap();
+
+    // Not filtered by currency, sorted by timestamp
+    let instruments: Vec<&str> = trades
+        .iter()
+        .map(|trade| trade.instrument_id.as_str())
+        .collect();
+    assert_eq!(instruments, vec!["BTC-USDT-SWAP", "ETH-USDT-SWAP"]);
+    assert_eq!(trades[0].client_order_id.as_deref(), Some("b15"));
+
+    // Sizes are in contracts: not normalized
+    assert_eq!(trades[0].instrument_type, InstrumentType::Swap);
+    assert!(matches!(
+        trades[0].clone().into_normalized().unwrap_err(),
+        Error::UnsupportedInstrumentType(InstrumentType::Swap)
+    ));
+}
+
+/// Conflicting headers: the OKX ones must win
+fn default_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Gateway-Token", HeaderValue::from_static("token"));
+    headers.insert("OK-ACCESS-KEY", HeaderValue::from_static("other"));
+    headers.insert("User-Agent", HeaderValue::from_static("gateway"));
+    headers
+}
+
+async fn mount_default_headers(server: &MockServer) {
+    authenticated("GET", "/api/v5/account/config")
+        .and(header("X-Gateway-Token", "token"))
+        .and(header_regex("User-Agent", "^okx-api/"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/account_config.json")),
+        )
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_default_headers() {
+    let server = MockServer::start().await;
+    mount_default_headers(&server).await;
+
+    let client = OkxClient::builder(credentials())
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .default_headers(default_headers())
+        .build()
+        .unwrap();
+
+    client.account_config().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_default_headers_with_reqwest_client() {
+    let server = MockServer::start().await;
+    mount_default_headers(&server).await;
+
+    let client = OkxClient::builder(credentials())
+        .root_url(Url::parse(&server.uri()).unwrap())
+        .reqwest_client(reqwest::Client::new())
+        .default_headers(default_headers())
+        .build()
+        .unwrap();
+
+    client.account_config().await.unwrap();
+}
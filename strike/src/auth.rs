@@ -2,6 +2,8 @@
 
 use std::fmt;
 
+use common::config::ApiKey;
+
 /// Strike authentication
 #[derive(Clone)]
 pub enum StrikeAuth {
@@ -15,6 +17,12 @@ impl fmt::Debug for StrikeAuth {
     }
 }
 
+impl From<ApiKey> for StrikeAuth {
+    fn from(key: ApiKey) -> Self {
+        Self::ApiKey(key.api_key)
+    }
+}
+
 impl StrikeAuth {
     /// Construct API key credential
     pub fn api_key<K>(key: K) -> Self
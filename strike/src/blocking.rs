@@ -0,0 +1,77 @@
+//! Blocking Strike client
+
+use common::blocking::Runtime;
+
+use crate::client::StrikeClient;
+use crate::error::Error;
+use crate::response::{Balance, Deposit, Invoice};
+
+impl StrikeClient {
+    /// Get a blocking client sharing this client
+    ///
+    /// See [`BlockingStrikeClient`].
+    pub fn blocking(&self) -> Result<BlockingStrikeClient, Error> {
+        Ok(BlockingStrikeClient {
+            client: self.clone(),
+            runtime: Runtime::new()?,
+        })
+    }
+}
+
+/// Blocking Strike client
+///
+/// Thin wrapper that runs the [`StrikeClient`] requests on an internal current-thread runtime.
+/// Must NOT be used from within an async context.
+#[derive(Debug, Clone)]
+pub struct BlockingStrikeClient {
+    client: StrikeClient,
+    runtime: Runtime,
+}
+
+impl BlockingStrikeClient {
+    /// Get the async client
+    #[inline]
+    pub fn as_async(&self) -> &StrikeClient {
+        &self.client
+    }
+
+    /// Check that the API is reachable and that the API key is valid
+    ///
+    /// See [`StrikeClient::healthcheck`].
+    #[inline]
+    pub fn healthcheck(&self) -> Result<(), Error> {
+        self.runtime.block_on(self.client.healthcheck())
+    }
+
+    /// Get **bitcoin** balance.
+    ///
+    /// See [`StrikeClient::balance`].
+    #[inline]
+    pub fn balance(&self) -> Result<Balance, Error> {
+        self.runtime.block_on(self.client.balance())
+    }
+
+    /// Get a **bitcoin** deposit address.
+    ///
+    /// See [`StrikeClient::bitcoin_deposit_address`].
+    #[inline]
+    pub fn bitcoin_deposit_address(&self) -> Result<String, Error> {
+        self.runtime.block_on(self.client.bitcoin_deposit_address())
+    }
+
+    /// Get **bitcoin** deposits.
+    ///
+    /// See [`StrikeClient::deposits`].
+    #[inline]
+    pub fn deposits(&self) -> Result<Vec<Deposit>, Error> {
+        self.runtime.block_on(self.client.deposits())
+    }
+
+    /// Get **bitcoin** invoices.
+    ///
+    /// See [`StrikeClient::invoices`].
+    #[inline]
+    pub fn invoices(&self) -> Result<Vec<Invoice>, Error> {
+        self.runtime.block_on(self.client.invoices())
+    }
+}
@@ -2,10 +2,11 @@
 
 use std::time::Duration;
 
+use common::interceptor::{self, Interceptors};
 use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderMap, HeaderValue};
 use reqwest::{Client, Method, Response};
 use serde::Serialize;
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, IgnoredAny};
 use url::Url;
 
 use crate::auth::StrikeAuth;
@@ -59,6 +60,8 @@ pub struct StrikeClient {
     client: Client,
     /// Authentication
     auth: StrikeAuth,
+    /// Request and response interceptors
+    interceptors: Interceptors,
 }
 
 impl StrikeClient {
@@ -66,14 +69,37 @@ impl StrikeClient {
     pub fn new(auth: StrikeAuth) -> Result<Self, Error> {
         Ok(Self {
             root_url: Url::parse(API_ROOT_URL)?,
-            client: Client::builder()
-                .user_agent(USER_AGENT_NAME)
-                .timeout(Duration::from_secs(25))
-                .build()?,
+            client: build_http_client(HeaderMap::new())?,
             auth,
+            interceptors: Interceptors::default(),
         })
     }
 
+    /// Set a custom root URL (i.e., a proxy or a mock server)
+    #[inline]
+    pub fn with_root_url(mut self, root_url: Url) -> Self {
+        self.root_url = root_url;
+        self
+    }
+
+    /// Set headers added to every request (default: none)
+    ///
+    /// The client headers (i.e., authentication) win on conflict. Rebuilds the HTTP client.
+    #[inline]
+    pub fn with_default_headers(mut self, headers: HeaderMap) -> Result<Self, Error> {
+        self.client = build_http_client(headers)?;
+        Ok(self)
+    }
+
+    /// Set request and response interceptors (default: none)
+    ///
+    /// The request interceptor runs after the authentication headers are added.
+    #[inline]
+    pub fn with_interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
     async fn call_api<T>(&self, api: Api, body: Option<String>) -> Result<T, Error>
     where
         T: DeserializeOwned,
@@ -103,13 +129,25 @@ impl StrikeClient {
             request = request.body(body);
         }
 
-        let response: Response = request.send().await?;
+        let response: Response = self.interceptors.send(request).await?;
+
+        let response: Response = interceptor::error_for_server_status(response).await?;
 
         // Propagate error if any
-        let response: Response = response.error_for_status()?;
+        let response: Response = interceptor::error_for_status(response)?;
 
         // Deserialize response
-        Ok(response.json().await?)
+        Ok(interceptor::json(response).await?)
+    }
+
+    /// Check that the API is reachable and that the API key is valid
+    ///
+    /// Strike has no public status endpoint: the balances are fetched instead.
+    ///
+    /// <https://docs.strike.me/api/get-account-balance-details/>
+    pub async fn healthcheck(&self) -> Result<(), Error> {
+        let _: IgnoredAny = self.call_api(Api::Balances, None).await?;
+        Ok(())
     }
 
     /// Get **bitcoin** balance.
@@ -182,3 +220,11 @@ impl StrikeClient {
         Ok(invoices)
     }
 }
+
+fn build_http_client(default_headers: HeaderMap) -> Result<Client, Error> {
+    Ok(Client::builder()
+        .default_headers(default_headers)
+        .user_agent(USER_AGENT_NAME)
+        .timeout(Duration::from_secs(25))
+        .build()?)
+}
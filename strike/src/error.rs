@@ -1,14 +1,23 @@
 //! Strike error
 
+use common::interceptor::{RequestError, ServerError};
 use reqwest::header::InvalidHeaderValue;
 use thiserror::Error;
 
 /// Strike error
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Reqwest error
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
+    /// Request error, with the method and path of the request
+    #[error(transparent)]
+    Request(#[from] RequestError),
+    /// Blocking runtime error
+    #[cfg(feature = "blocking")]
+    #[error(transparent)]
+    Runtime(#[from] std::io::Error),
     /// Invalid header error
     #[error(transparent)]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
@@ -18,6 +27,9 @@ pub enum Error {
     /// Json error
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+    /// Server error (HTTP 5xx)
+    #[error(transparent)]
+    ServerError(#[from] ServerError),
     /// Missing deposit address in response
     #[error("missing deposit address")]
     MissingDepositAddress,
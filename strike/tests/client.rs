@@ -0,0 +1,95 @@
+//! Replay recorded responses through the client
+
+use strike_api::prelude::*;
+use wiremock::matchers::{body_json, header, header_regex, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(server: &MockServer) -> StrikeClient {
+    StrikeClient::new(StrikeAuth::ApiKey(String::from("key")))
+        .unwrap()
+        .with_root_url(Url::parse(&server.uri()).unwrap())
+}
+
+/// Match the authentication headers
+fn authenticated(http_method: &str, http_path: &str) -> wiremock::MockBuilder {
+    Mock::given(method(http_method))
+        .and(path(http_path))
+        .and(header("Authorization", "Bearer key"))
+}
+
+#[tokio::test]
+async fn test_balance() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/v1/balances")
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balances.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let balance = client(&server).balance().await.unwrap();
+    assert_eq!(balance.currency, "BTC");
+    assert_eq!(balance.available, 0.5);
+}
+
+#[tokio::test]
+async fn test_healthcheck() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/v1/balances")
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(client(&server).healthcheck().await.is_err());
+}
+
+#[tokio::test]
+async fn test_bitcoin_deposit_address() {
+    let server = MockServer::start().await;
+
+    authenticated("POST", "/v1/receive-requests")
+        .and(body_json(serde_json::json!({
+            "onchain": {},
+            "targetCurrency": "BTC",
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(include_str!("fixtures/receive_request.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let address = client(&server).bitcoin_deposit_address().await.unwrap();
+    assert_eq!(address, "bc1q7l6gd6kvc3kd4lw4yeqsn8jedquawz2hzqpssp");
+}
+
+#[tokio::test]
+async fn test_default_headers() {
+    let server = MockServer::start().await;
+
+    authenticated("GET", "/v1/balances")
+        .and(header("X-Gateway-Token", "token"))
+        .and(header("Accept", "application/json"))
+        .and(header_regex("User-Agent", "^strike-api/"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(include_str!("fixtures/balances.json")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Conflicting headers: the Strike ones must win
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Gateway-Token", HeaderValue::from_static("token"));
+    headers.insert("Authorization", HeaderValue::from_static("Bearer other"));
+    headers.insert("Accept", HeaderValue::from_static("text/plain"));
+    headers.insert("User-Agent", HeaderValue::from_static("gateway"));
+
+    let client = client(&server).with_default_headers(headers).unwrap();
+    client.balance().await.unwrap();
+}